@@ -4,7 +4,7 @@ use elixir_runtime::{
     AccountId, BalancesConfig, GenesisConfig, GrandpaConfig, ImOnlineConfig,
     IndicesConfig, SessionConfig, SessionKeys, StakingConfig, SudoConfig, SystemConfig,
     WASM_BINARY, Signature, AuraConfig, CouncilConfig, TechnicalCommitteeConfig,
-    ElixirPalletConfig,
+    ElixirPalletConfig, ElixirOracleConfig,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -194,6 +194,11 @@ fn testnet_genesis(
             min_temperature: 200u32, // 20C with 1 decimal place
             max_temperature: 240u32, // 24C with 1 decimal place
         },
+        elixir_oracle: ElixirOracleConfig {
+            // Validators register themselves once the chain is running
+            validators: vec![],
+            assets: vec![],
+        },
     }
 }
 
@@ -6,7 +6,7 @@ use sp_runtime::traits::{IdentifyAccount, Verify};
 use elxr_runtime::{
     AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig,
     SudoConfig, SystemConfig, WASM_BINARY, Signature, 
-    DaemonlessOracleConfig, UnifiedLiquidityPoolConfig, 
+    DaemonlessOracleConfig, ElixirOracleConfig, UnifiedLiquidityPoolConfig, 
     ZeroSpreadDexConfig, TelemetryConfig
 };
 
@@ -251,6 +251,10 @@ fn testnet_genesis(
             initial_data_providers: vec![],
             initial_feed_ids: vec![],
         },
+        elixir_oracle: ElixirOracleConfig {
+            validators: vec![],
+            assets: vec![],
+        },
         unified_liquidity_pool: UnifiedLiquidityPoolConfig {
             initial_pools: vec![],
             initial_asset_ids: vec![],
@@ -26,22 +26,23 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
     construct_runtime, parameter_types,
-    traits::{KeyOwnerProofSystem, Randomness, StorageInfo},
+    traits::{Contains, Everything, KeyOwnerProofSystem, Randomness, StorageInfo},
     weights::{
         constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
         IdentityFee, Weight,
     },
-    StorageValue,
+    PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
 use pallet_transaction_payment::CurrencyAdapter;
 #[cfg(any(feature = "std", test))]
 pub use sp_runtime::BuildStorage;
-pub use sp_runtime::{Perbill, Permill};
+pub use sp_runtime::{Perbill, Percent, Permill};
 
 // Import ELXR pallet
 pub use elixir_pallet;
+use elixir_pallet::oracle::AssetId;
 
 /// An index to a block.
 pub type BlockNumber = u32;
@@ -112,6 +113,23 @@ pub fn native_version() -> NativeVersion {
     }
 }
 
+/// Native executor for the runtime, extended with the post-quantum host functions.
+#[cfg(feature = "std")]
+pub struct Executor;
+
+#[cfg(feature = "std")]
+impl sc_executor::NativeExecutionDispatch for Executor {
+    type ExtendHostFunctions = elixir_pallet::pq_crypto::HostFunctions;
+
+    fn dispatch(method: &str, data: &[u8]) -> Option<Vec<u8>> {
+        api::dispatch(method, data)
+    }
+
+    fn native_version() -> sc_executor::NativeVersion {
+        native_version()
+    }
+}
+
 const NORMAL_DISPATCH_RATIO: Perbill = Perbill::from_percent(75);
 
 parameter_types! {
@@ -227,11 +245,13 @@ impl pallet_timestamp::Config for Runtime {
 parameter_types! {
     pub const ExistentialDeposit: u128 = 500;
     pub const MaxLocks: u32 = 50;
+    /// Named reserves per account: oracle (`elxoracl`) and registry (`elxregst`) deposits.
+    pub const MaxReserves: u32 = 2;
 }
 
 impl pallet_balances::Config for Runtime {
     type MaxLocks = MaxLocks;
-    type MaxReserves = ();
+    type MaxReserves = MaxReserves;
     type ReserveIdentifier = [u8; 8];
     /// The type for recording an account's balance.
     type Balance = Balance;
@@ -266,6 +286,20 @@ parameter_types! {
     pub const MinBrewerStake: Balance = 100_000;
     pub const FermentationPeriod: BlockNumber = 100;
     pub const MaxKombuchaVerifiers: u32 = 50;
+    pub const VerificationQuorum: u32 = 3;
+    pub const InconclusiveRetryThreshold: u32 = 3;
+    pub const MaxVerificationDataLength: u32 = 4 * 1024;
+    pub ScobyHealthTargets: elixir_pallet::ScobyHealthTargets = elixir_pallet::ScobyHealthTargets {
+        ph_range: (25, 35),
+        max_temperature_spread: 30,
+        // Two weeks of primary fermentation, in milliseconds
+        expected_stage_duration: 14 * 24 * 60 * 60 * 1000,
+    };
+    pub const MaxTelemetrySamplesPerCall: u32 = 64;
+    pub const MaxTelemetrySamples: u32 = 4_096;
+    pub const MaxAuthorizedEndpoints: u32 = 16;
+    pub const MinTelemetrySamplesForVerification: u32 = 12;
+    pub const HealthyBatchValue: Balance = 250_000_000;
 }
 
 impl elixir_pallet::Config for Runtime {
@@ -274,9 +308,82 @@ impl elixir_pallet::Config for Runtime {
     type MinBrewerStake = MinBrewerStake;
     type FermentationPeriod = FermentationPeriod;
     type MaxVerifiers = MaxKombuchaVerifiers;
+    type VerificationQuorum = VerificationQuorum;
+    type InconclusiveRetryThreshold = InconclusiveRetryThreshold;
+    type MaxVerificationDataLength = MaxVerificationDataLength;
+    type ScobyHealthTargets = ScobyHealthTargets;
+    type MaxSamplesPerCall = MaxTelemetrySamplesPerCall;
+    type MaxTelemetrySamples = MaxTelemetrySamples;
+    type MaxAuthorizedEndpoints = MaxAuthorizedEndpoints;
+    type MinTelemetrySamplesForVerification = MinTelemetrySamplesForVerification;
+    type HealthyBatchValue = HealthyBatchValue;
+    type OracleConfig = Runtime;
+    type OracleLiquidityConfig = Runtime;
+    type WeightInfo = ();
+}
+
+// Configure the ELXR oracle
+parameter_types! {
+    pub const OracleMinValidators: u32 = 3;
+    pub const OracleConsensusThreshold: Percent = Percent::from_percent(67);
+    pub const OracleMinStake: Balance = 1_000_000;
+    pub const FullConfidenceStakeMultiple: u32 = 10;
+    pub const MaxStakePerValidator: Balance = 1_000_000_000;
+    pub const MaxEvidenceBatches: u32 = 8;
+    pub const MaxPriceAge: BlockNumber = 100;
+    pub const TwapWindow: BlockNumber = 60;
+    pub const ConfidenceDecayBlocks: BlockNumber = 600;
+    pub const MinBlocksBetweenSubmissions: BlockNumber = 5;
+    /// Aura slots, and so blocks, last twice the timestamp minimum period.
+    pub OracleBlockTimeMillis: u64 = MinimumPeriod::get() * 2;
+    pub const OraclePalletId: PalletId = PalletId(*b"elx/orcl");
+    pub const RewardPerFinalization: Balance = 1_000;
+    pub const MinOverrideConfidence: Percent = Percent::from_percent(50);
+}
+
+/// Asset ID of ELXR in the shared liquidity system
+pub const ELXR_ASSET_ID: AssetId = AssetId(2);
+
+/// Only ELXR prices may be derived from fermentation telemetry
+pub struct DerivedPriceAssets;
+
+impl Contains<AssetId> for DerivedPriceAssets {
+    fn contains(asset_id: &AssetId) -> bool {
+        *asset_id == ELXR_ASSET_ID
+    }
+}
+
+impl elixir_pallet::oracle::Config for Runtime {
+    type RuntimeEvent = Event;
+    type Currency = Balances;
+    type MinValidators = OracleMinValidators;
+    type ConsensusThreshold = OracleConsensusThreshold;
+    type MinStake = OracleMinStake;
+    type FullConfidenceStakeMultiple = FullConfidenceStakeMultiple;
+    type MaxStakePerValidator = MaxStakePerValidator;
+    type FeedConsumers = OracleLiquidity;
+    type Fermentation = ElixirModule;
+    type MaxEvidenceBatches = MaxEvidenceBatches;
+    type DerivedPriceAssets = DerivedPriceAssets;
+    type MaxPriceAge = MaxPriceAge;
+    type TwapWindow = TwapWindow;
+    type ConfidenceDecayBlocks = ConfidenceDecayBlocks;
+    type MinBlocksBetweenSubmissions = MinBlocksBetweenSubmissions;
+    type BlockTimeMillis = OracleBlockTimeMillis;
+    type UnixTime = Timestamp;
+    type PalletId = OraclePalletId;
+    type RewardPerFinalization = RewardPerFinalization;
+    type AllowedSignatureSchemes = Everything;
     type WeightInfo = ();
 }
 
+impl elixir_pallet::oracle_liquidity::Config for Runtime {
+    type AmmHandler = ();
+    type RuntimeEvent = Event;
+    type MinOverrideConfidence = MinOverrideConfidence;
+    type WeightInfo = elixir_pallet::oracle_liquidity::SubstrateWeight<Runtime>;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
     pub enum Runtime where
@@ -293,6 +400,8 @@ construct_runtime!(
         TransactionPayment: pallet_transaction_payment,
         Sudo: pallet_sudo,
         ElixirModule: elixir_pallet,
+        ElixirOracle: elixir_pallet::oracle,
+        OracleLiquidity: elixir_pallet::oracle_liquidity,
     }
 );
 
@@ -471,5 +580,54 @@ impl_runtime_apis! {
         fn check_fermentation_status(batch_id: Vec<u8>) -> Option<elixir_pallet::FermentationStatus> {
             ElixirModule::check_fermentation_status(batch_id)
         }
+
+        fn scoby_health(batch_id: Vec<u8>) -> Option<elixir_pallet::HealthScore> {
+            ElixirModule::scoby_health(batch_id)
+        }
+    }
+
+    impl elixir_pallet::OracleRuntimeApi<Block, Balance, BlockNumber> for Runtime {
+        fn prices(
+            asset_ids: Vec<AssetId>,
+        ) -> Vec<(AssetId, Option<elixir_pallet::PriceQuote<Balance, BlockNumber>>)> {
+            ElixirOracle::prices(asset_ids)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use elixir_pallet::{
+        oracle::{PriceFeed, PriceFeeds},
+        runtime_decl_for_OracleRuntimeApi::OracleRuntimeApi,
+        PriceQuote,
+    };
+
+    #[test]
+    fn oracle_api_quotes_prices_in_request_order() {
+        let storage = GenesisConfig::default().build_storage().unwrap();
+        sp_io::TestExternalities::new(storage).execute_with(|| {
+            PriceFeeds::<Runtime>::insert(
+                ELXR_ASSET_ID,
+                PriceFeed {
+                    asset_id: ELXR_ASSET_ID,
+                    price: 1_500,
+                    timestamp: 7,
+                    confidence: Percent::from_percent(80),
+                    signatures: Vec::new(),
+                    quantum_proof: Vec::new(),
+                },
+            );
+
+            let quotes = <Runtime as OracleRuntimeApi<Block, Balance, BlockNumber>>::prices(vec![
+                AssetId(9),
+                ELXR_ASSET_ID,
+                AssetId(1),
+            ]);
+
+            let elxr = PriceQuote { price: 1_500, updated_at: 7, confidence: Percent::from_percent(80) };
+            assert_eq!(quotes, vec![(AssetId(9), None), (ELXR_ASSET_ID, Some(elxr)), (AssetId(1), None)]);
+        });
     }
 }
@@ -0,0 +1,181 @@
+//! Backend abstraction over the Eigenlayer contracts
+use crate::eigenlayer::{
+    client::EigenlayerClient,
+    types::{RestakeInfo, QuorumInfo, OperatorStatus},
+};
+use anyhow::Result;
+use ethers::types::U256;
+use std::{collections::HashMap, sync::{atomic::{AtomicUsize, Ordering}, Mutex}};
+
+/// Source of operator and quorum state used by the operator manager
+pub trait EigenBackend: Send + Sync {
+    /// Get information about a specific operator
+    fn get_operator_info(&self, operator_address: &[u8]) -> Result<RestakeInfo>;
+
+    /// Get information about a specific quorum
+    fn get_quorum_info(&self, quorum_id: u8) -> Result<QuorumInfo>;
+
+    /// Get information about the operators registered in a single quorum
+    fn get_operators_in_quorum(&self, quorum_id: u8) -> Result<Vec<RestakeInfo>>;
+
+    /// Get all active operators and their information
+    fn get_all_operators(&self) -> Result<Vec<RestakeInfo>>;
+
+    /// Get information for all quorums
+    fn get_all_quorums(&self) -> Result<Vec<QuorumInfo>>;
+
+    /// Register a new operator, returning once the registration is confirmed
+    fn register_operator(&self, operator_address: &[u8], bls_public_key: &[u8], bls_signature: &[u8]) -> Result<()>;
+
+    /// Estimate the fee (in wei) of registering an operator, without registering it
+    fn estimate_register_operator_fee(&self, operator_address: &[u8], bls_public_key: &[u8]) -> Result<U256>;
+
+    /// Deregister an operator from every quorum it participates in, returning once
+    /// the deregistration is confirmed
+    fn deregister_operator(&self, operator_address: &[u8]) -> Result<()>;
+}
+
+impl EigenBackend for EigenlayerClient {
+    fn get_operator_info(&self, operator_address: &[u8]) -> Result<RestakeInfo> {
+        EigenlayerClient::get_operator_info(self, operator_address)
+    }
+
+    fn get_quorum_info(&self, quorum_id: u8) -> Result<QuorumInfo> {
+        EigenlayerClient::get_quorum_info(self, quorum_id)
+    }
+
+    fn get_operators_in_quorum(&self, quorum_id: u8) -> Result<Vec<RestakeInfo>> {
+        EigenlayerClient::get_operators_in_quorum(self, quorum_id)
+    }
+
+    fn get_all_operators(&self) -> Result<Vec<RestakeInfo>> {
+        EigenlayerClient::get_all_operators(self)
+    }
+
+    fn get_all_quorums(&self) -> Result<Vec<QuorumInfo>> {
+        EigenlayerClient::get_all_quorums(self)
+    }
+
+    fn register_operator(&self, operator_address: &[u8], bls_public_key: &[u8], bls_signature: &[u8]) -> Result<()> {
+        EigenlayerClient::register_operator(self, operator_address, bls_public_key, bls_signature).map(|_| ())
+    }
+
+    fn estimate_register_operator_fee(&self, operator_address: &[u8], bls_public_key: &[u8]) -> Result<U256> {
+        EigenlayerClient::estimate_register_operator_fee(self, operator_address, bls_public_key)
+    }
+
+    fn deregister_operator(&self, operator_address: &[u8]) -> Result<()> {
+        EigenlayerClient::deregister_operator(self, operator_address).map(|_| ())
+    }
+}
+
+/// In-memory backend with programmable operators and quorums, for use without an
+/// Ethereum node
+#[derive(Default)]
+pub struct MockEigenBackend {
+    /// Operators by address
+    operators: Mutex<HashMap<Vec<u8>, RestakeInfo>>,
+
+    /// Quorums by ID
+    quorums: Mutex<HashMap<u8, QuorumInfo>>,
+
+    /// Number of `get_all_operators` calls served
+    get_all_operators_calls: AtomicUsize,
+
+    /// Fee returned by registration estimates
+    registration_fee: Mutex<U256>,
+}
+
+impl MockEigenBackend {
+    /// Create an empty backend
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace an operator
+    pub fn set_operator(&self, info: RestakeInfo) {
+        self.operators.lock().unwrap().insert(info.operator_address.clone(), info);
+    }
+
+    /// Remove an operator
+    pub fn remove_operator(&self, operator_address: &[u8]) {
+        self.operators.lock().unwrap().remove(operator_address);
+    }
+
+    /// Add or replace a quorum
+    pub fn set_quorum(&self, info: QuorumInfo) {
+        self.quorums.lock().unwrap().insert(info.quorum_id, info);
+    }
+
+    /// Set the fee returned by registration estimates
+    pub fn set_registration_fee(&self, fee: U256) {
+        *self.registration_fee.lock().unwrap() = fee;
+    }
+
+    /// Number of times `get_all_operators` has been called
+    pub fn get_all_operators_calls(&self) -> usize {
+        self.get_all_operators_calls.load(Ordering::SeqCst)
+    }
+}
+
+impl EigenBackend for MockEigenBackend {
+    fn get_operator_info(&self, operator_address: &[u8]) -> Result<RestakeInfo> {
+        self.operators.lock().unwrap()
+            .get(operator_address)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Operator is not registered"))
+    }
+
+    fn get_quorum_info(&self, quorum_id: u8) -> Result<QuorumInfo> {
+        self.quorums.lock().unwrap()
+            .get(&quorum_id)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("Quorum {} does not exist", quorum_id))
+    }
+
+    fn get_operators_in_quorum(&self, quorum_id: u8) -> Result<Vec<RestakeInfo>> {
+        Ok(self.operators.lock().unwrap()
+            .values()
+            .filter(|op| op.quorum_ids.contains(&quorum_id))
+            .cloned()
+            .collect())
+    }
+
+    fn get_all_operators(&self) -> Result<Vec<RestakeInfo>> {
+        self.get_all_operators_calls.fetch_add(1, Ordering::SeqCst);
+        Ok(self.operators.lock().unwrap().values().cloned().collect())
+    }
+
+    fn get_all_quorums(&self) -> Result<Vec<QuorumInfo>> {
+        Ok(self.quorums.lock().unwrap().values().cloned().collect())
+    }
+
+    // Registers into no quorums; use `set_operator` to program membership
+    fn register_operator(&self, operator_address: &[u8], bls_public_key: &[u8], _bls_signature: &[u8]) -> Result<()> {
+        self.set_operator(RestakeInfo {
+            operator_address: operator_address.to_vec(),
+            restaked_amount: 0,
+            strategy_shares: Vec::new(),
+            public_key: bls_public_key.to_vec(),
+            quorum_ids: Vec::new(),
+            status: OperatorStatus::Active,
+        });
+        Ok(())
+    }
+
+    fn estimate_register_operator_fee(&self, _operator_address: &[u8], _bls_public_key: &[u8]) -> Result<U256> {
+        Ok(*self.registration_fee.lock().unwrap())
+    }
+
+    fn deregister_operator(&self, operator_address: &[u8]) -> Result<()> {
+        let mut operators = self.operators.lock().unwrap();
+        let info = operators.get_mut(operator_address)
+            .ok_or_else(|| anyhow::anyhow!("Operator is not registered"))?;
+        if info.quorum_ids.is_empty() {
+            return Err(anyhow::anyhow!("Operator {:?} is not registered in any quorum", operator_address));
+        }
+        info.quorum_ids.clear();
+        info.status = OperatorStatus::Removed;
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //! Client implementation for interacting with Eigenlayer contracts
 use crate::eigenlayer::{
     config::EigenConfig,
+    gas::{ConfiguredGasOracle, GasPricedProvider},
     types::{RestakeInfo, QuorumInfo, OperatorStatus, address_to_bytes, bytes_to_address},
 };
 use anyhow::{Result, Context};
@@ -14,12 +15,14 @@ use eigensdk::{
     eigen_crypto_bls::{BlsKeyPair, PublicKey, SecretKey}
 };
 use ethers::{
+    middleware::gas_oracle::{GasOracle, GasOracleMiddleware},
     providers::{Http, Provider, Middleware},
     signers::{LocalWallet, Signer, Wallet},
+    types::{transaction::eip2718::TypedTransaction, TransactionReceipt, H256, U256},
 };
-use alloy_primitives::{Address, U256};
+use alloy_primitives::Address;
 use tokio::runtime::Runtime;
-use std::{sync::Arc, str::FromStr, collections::HashMap};
+use std::{sync::Arc, str::FromStr, collections::HashMap, time::Duration};
 use log::{info, error, debug};
 
 /// Client for interacting with Eigenlayer contracts
@@ -31,10 +34,13 @@ pub struct EigenlayerClient {
     wallet: Wallet<LocalWallet>,
     
     /// EL contracts client
-    el_contracts: ELContracts<Provider<Http>, Wallet<LocalWallet>>,
+    el_contracts: ELContracts<GasPricedProvider, Wallet<LocalWallet>>,
     
     /// AVS registry client
-    avs_registry: AvsRegistryClient<Provider<Http>, Wallet<LocalWallet>>,
+    avs_registry: AvsRegistryClient<GasPricedProvider, Wallet<LocalWallet>>,
+    
+    /// Gas price source used for write calls, kept for fee estimates
+    gas_oracle: ConfiguredGasOracle,
     
     /// Configuration
     config: EigenConfig,
@@ -57,6 +63,12 @@ impl EigenlayerClient {
         
         let provider = Arc::new(provider);
         
+        // Write calls go through the gas oracle so the configured gas price applies
+        let gas_provider = Arc::new(GasOracleMiddleware::new(
+            provider.as_ref().clone(),
+            ConfiguredGasOracle::new(&config, provider.as_ref().clone()),
+        ));
+        
         // Create registry coordinator address from the config
         let registry_coordinator_addr = Address::from_str(&config.contract_addresses.elxr_registry_coordinator)
             .context("Invalid registry coordinator address")?;
@@ -68,7 +80,7 @@ impl EigenlayerClient {
         // Create registry client
         let avs_registry = runtime.block_on(async {
             AvsRegistryClient::new(
-                provider.clone(),
+                gas_provider.clone(),
                 wallet.clone(),
                 registry_coordinator_addr,
                 bls_pk_compendium_addr,
@@ -92,7 +104,7 @@ impl EigenlayerClient {
                 .context("Invalid slasher address")?;
                 
             ELContracts::new(
-                provider.clone(),
+                gas_provider.clone(),
                 wallet.clone(),
                 delegation_manager_addr,
                 avs_directory_addr,
@@ -103,11 +115,14 @@ impl EigenlayerClient {
             .context("Failed to create EL contracts client")
         })?;
         
+        let gas_oracle = ConfiguredGasOracle::new(&config, provider.as_ref().clone());
+        
         Ok(Self {
             provider,
             wallet,
             el_contracts,
             avs_registry,
+            gas_oracle,
             config,
             runtime,
         })
@@ -118,93 +133,143 @@ impl EigenlayerClient {
         let operator_addr = bytes_to_address(operator_address)
             .context("Invalid operator address")?;
             
-        // Run the async operations in the runtime
-        self.runtime.block_on(async {
-            // Get the operator's status
-            let is_registered = self.avs_registry.is_operator_registered(operator_addr).await?;
-            
-            if !is_registered {
-                return Err(anyhow::anyhow!("Operator is not registered"));
-            }
-            
-            // Get quorums the operator is registered for
-            let quorum_numbers = self.avs_registry.get_operator_quorum_bits_at_block_number(
-                operator_addr,
-                None, // Use latest block
-            ).await?;
-            
-            // Convert quorum numbers to vector of quorum IDs
-            let quorum_ids = quorum_numbers.get_quorum_ids();
+        self.runtime.block_on(self.fetch_operator_info(operator_addr))
+    }
+    
+    /// Query an operator's registration, quorums, key and shares
+    async fn fetch_operator_info(&self, operator_addr: Address) -> Result<RestakeInfo> {
+        // Get the operator's status
+        let is_registered = self.avs_registry.is_operator_registered(operator_addr).await?;
+        
+        if !is_registered {
+            return Err(anyhow::anyhow!("Operator is not registered"));
+        }
+        
+        // Get quorums the operator is registered for
+        let quorum_numbers = self.avs_registry.get_operator_quorum_bits_at_block_number(
+            operator_addr,
+            None, // Use latest block
+        ).await?;
+        
+        // Convert quorum numbers to vector of quorum IDs
+        let quorum_ids = quorum_numbers.get_quorum_ids();
+        
+        // Get operator's BLS public key
+        let public_key = self.avs_registry.get_operator_pubkey_hash(operator_addr)
+            .await?
+            .to_vec();
             
-            // Get operator's BLS public key
-            let public_key = self.avs_registry.get_operator_pubkey_hash(operator_addr)
-                .await?
-                .to_vec();
-                
-            // Get restaked amount from EL contracts
-            let operator_shares = self.el_contracts.get_operator_shares(operator_addr).await?;
+        // Get per-strategy shares from EL contracts
+        let (strategy_shares, restaked_amount) = self.strategy_shares(operator_addr).await?;
+        
+        // Determine operator status
+        let status = if self.avs_registry.is_operator_registered(operator_addr).await? {
+            OperatorStatus::Active
+        } else {
+            OperatorStatus::Removed
+        };
+        
+        Ok(RestakeInfo {
+            operator_address: address_to_bytes(&operator_addr),
+            restaked_amount,
+            strategy_shares,
+            public_key,
+            quorum_ids: quorum_ids.into_iter().map(|id| id as u8).collect(),
+            status,
+        })
+    }
+    
+    /// Shares an operator holds in each strategy, as (strategy address, shares), and their total
+    async fn strategy_shares(&self, operator_addr: Address) -> Result<(Vec<(Vec<u8>, u128)>, u128)> {
+        let operator_shares = self.el_contracts.get_operator_shares(operator_addr).await?;
+        
+        Ok(share_breakdown(operator_shares.iter().map(|(strategy, amount)| (*strategy, amount.to::<u128>()))))
+    }
+    
+    /// Total amount restaked by an operator, summed over all of its shares
+    async fn restaked_amount(&self, operator_addr: Address) -> Result<u128> {
+        let (_, restaked_amount) = self.strategy_shares(operator_addr).await?;
+        Ok(restaked_amount)
+    }
+    
+    /// Check that an operator meets the minimum stake of every required quorum
+    pub fn check_quorum_stake_requirements(&self, operator_address: &[u8]) -> Result<()> {
+        let operator_addr = bytes_to_address(operator_address)
+            .context("Invalid operator address")?;
             
-            // Calculate total restaked amount by summing all shares
-            let mut restaked_amount: u128 = 0;
+        let restaked_amount = self.runtime.block_on(self.restaked_amount(operator_addr))?;
+        
+        let quorums = self.config.avs_config.required_quorums.iter()
+            .map(|&quorum_id| self.get_quorum_info(quorum_id))
+            .collect::<Result<Vec<_>>>()?;
+        
+        ensure_quorum_stakes_met(operator_addr, restaked_amount, &quorums)
+    }
+    
+    /// Get information about a specific quorum
+    pub fn get_quorum_info(&self, quorum_id: u8) -> Result<QuorumInfo> {
+        self.runtime.block_on(self.fetch_quorum_info(quorum_id))
+    }
+    
+    /// Query a quorum's operators, minimum stake and total stake
+    async fn fetch_quorum_info(&self, quorum_id: u8) -> Result<QuorumInfo> {
+        // Get operators in this quorum
+        let operators = self.avs_registry.get_operators_in_quorum_at_block_number(
+            quorum_id as u8,
+            None, // Use latest block
+        ).await?;
+        
+        let operator_count = operators.len() as u32;
+        
+        // Get minimum stake for this quorum
+        let quorum_param = self.avs_registry.get_quorum_params(quorum_id as u8).await?;
+        let min_stake = quorum_param.minimum_stake.to::<u128>();
+        
+        // Calculate total stake in this quorum
+        let mut total_stake: u128 = 0;
+        for operator in operators {
+            let operator_shares = self.el_contracts.get_operator_shares(operator).await?;
             for (_, amount) in operator_shares.iter() {
-                restaked_amount += amount.to::<u128>();
+                total_stake += amount.to::<u128>();
             }
-            
-            // Determine operator status
-            let status = if self.avs_registry.is_operator_registered(operator_addr).await? {
-                OperatorStatus::Active
-            } else {
-                OperatorStatus::Removed
-            };
-            
-            Ok(RestakeInfo {
-                operator_address: address_to_bytes(&operator_addr),
-                restaked_amount,
-                public_key,
-                quorum_ids: quorum_ids.into_iter().map(|id| id as u8).collect(),
-                status,
-            })
+        }
+        
+        Ok(QuorumInfo {
+            quorum_id,
+            operator_count,
+            total_stake,
+            min_stake,
         })
     }
     
-    /// Get information about a specific quorum
-    pub fn get_quorum_info(&self, quorum_id: u8) -> Result<QuorumInfo> {
+    /// Get information about the operators registered in a single quorum
+    pub fn get_operators_in_quorum(&self, quorum_id: u8) -> Result<Vec<RestakeInfo>> {
         self.runtime.block_on(async {
-            // Get operators in this quorum
+            // Query the quorum's operator list directly from the AVS registry
             let operators = self.avs_registry.get_operators_in_quorum_at_block_number(
-                quorum_id as u8,
+                quorum_id,
                 None, // Use latest block
             ).await?;
             
-            let operator_count = operators.len() as u32;
-            
-            // Get minimum stake for this quorum
-            let quorum_param = self.avs_registry.get_quorum_params(quorum_id as u8).await?;
-            let min_stake = quorum_param.minimum_stake.to::<u128>();
-            
-            // Calculate total stake in this quorum
-            let mut total_stake: u128 = 0;
-            for operator in operators {
-                let operator_shares = self.el_contracts.get_operator_shares(operator).await?;
-                for (_, amount) in operator_shares.iter() {
-                    total_stake += amount.to::<u128>();
+            let mut operator_infos = Vec::new();
+            for operator_addr in operators {
+                // Skip invalid operators
+                match self.fetch_operator_info(operator_addr).await {
+                    Ok(info) => operator_infos.push(info),
+                    Err(e) => error!("Error getting operator info for {:?}: {:?}", operator_addr, e),
                 }
             }
             
-            Ok(QuorumInfo {
-                quorum_id,
-                operator_count,
-                total_stake,
-                min_stake,
-            })
+            Ok(operator_infos)
         })
     }
     
-    /// Register a new operator with Eigenlayer
+    /// Register a new operator with Eigenlayer, returning the receipt once it has
+    /// the configured number of confirmations
     pub fn register_operator(&self, 
                              operator_address: &[u8], 
                              bls_public_key: &[u8], 
-                             bls_signature: &[u8]) -> Result<()> {
+                             bls_signature: &[u8]) -> Result<TransactionReceipt> {
         let operator_addr = bytes_to_address(operator_address)
             .context("Invalid operator address")?;
             
@@ -212,9 +277,14 @@ impl EigenlayerClient {
         let public_key = PublicKey::from_bytes(bls_public_key)
             .context("Invalid BLS public key")?;
             
+        // Registering into a quorum whose minimum stake isn't met reverts on-chain
+        if !self.config.avs_config.skip_quorum_stake_check {
+            self.check_quorum_stake_requirements(operator_address)?;
+        }
+            
         // This would normally come from the operator's signed registration
         // For this example, we're converting from the provided signature bytes
-        let signature = self.runtime.block_on(async {
+        let tx_hash = self.runtime.block_on(async {
             self.avs_registry.register_operator(
                 operator_addr, 
                 public_key,
@@ -225,9 +295,80 @@ impl EigenlayerClient {
             ).await
         })?;
         
+        let receipt = self.wait_for_confirmations(tx_hash.as_ref())?;
+        
         info!("Registered operator: {:?}", operator_addr);
         
-        Ok(())
+        Ok(receipt)
+    }
+    
+    /// Registration transaction for an operator into the required quorums, built but not sent
+    fn registration_tx(&self, operator_address: &[u8], bls_public_key: &[u8]) -> Result<TypedTransaction> {
+        let operator_addr = bytes_to_address(operator_address)
+            .context("Invalid operator address")?;
+            
+        let public_key = PublicKey::from_bytes(bls_public_key)
+            .context("Invalid BLS public key")?;
+            
+        self.runtime.block_on(async {
+            self.avs_registry.register_operator_tx(
+                operator_addr,
+                public_key,
+                self.config.avs_config.required_quorums.clone(),
+                [0u8; 64].to_vec()
+            ).await
+        })
+    }
+    
+    /// Estimate the gas used by registering an operator, without sending the transaction
+    pub fn estimate_register_operator_gas(&self, operator_address: &[u8], bls_public_key: &[u8]) -> Result<U256> {
+        let tx = self.registration_tx(operator_address, bls_public_key)?;
+        
+        self.runtime.block_on(self.provider.estimate_gas(&tx, None))
+            .context("Failed to estimate registration gas")
+    }
+    
+    /// Estimate the fee (in wei) of registering an operator at the configured gas price
+    pub fn estimate_register_operator_fee(&self, operator_address: &[u8], bls_public_key: &[u8]) -> Result<U256> {
+        let tx = self.registration_tx(operator_address, bls_public_key)?;
+        
+        self.runtime.block_on(estimate_fee(self.provider.as_ref(), &self.gas_oracle, &tx))
+    }
+    
+    /// Deregister an operator from every quorum it participates in. Its restaked
+    /// funds become withdrawable through Eigenlayer once deregistration completes.
+    pub fn deregister_operator(&self, operator_address: &[u8]) -> Result<TransactionReceipt> {
+        let operator_addr = bytes_to_address(operator_address)
+            .context("Invalid operator address")?;
+            
+        let info = self.get_operator_info(operator_address)?;
+        if info.quorum_ids.is_empty() {
+            return Err(anyhow::anyhow!("Operator {:?} is not registered in any quorum", operator_addr));
+        }
+        
+        let tx_hash = self.runtime.block_on(async {
+            self.avs_registry.deregister_operator(operator_addr, info.quorum_ids.clone()).await
+        })?;
+        
+        let receipt = self.wait_for_confirmations(tx_hash.as_ref())?;
+        
+        info!("Deregistered operator {:?} from quorums {:?}", operator_addr, info.quorum_ids);
+        
+        Ok(receipt)
+    }
+    
+    /// Wait until a submitted transaction has the configured number of confirmations
+    fn wait_for_confirmations(&self, tx_hash: &[u8]) -> Result<TransactionReceipt> {
+        let confirmations = self.config.confirmations;
+        let timeout = Duration::from_secs(self.config.confirmation_timeout_secs);
+        
+        self.runtime.block_on(await_confirmations(
+            self.provider.as_ref(),
+            tx_hash,
+            confirmations,
+            timeout,
+            self.provider.get_interval(),
+        ))
     }
     
     /// Get all active operators and their information
@@ -236,10 +377,15 @@ impl EigenlayerClient {
             // Get all operators registered with the AVS
             let operators = self.avs_registry.get_all_operators().await?;
             
+            if operators.is_empty() {
+                debug!("AVS registry has no operators");
+                return Ok(Vec::new());
+            }
+            
             let mut operator_infos = Vec::new();
             for operator_addr in operators {
                 // Skip invalid operators
-                match self.get_operator_info(&address_to_bytes(&operator_addr)) {
+                match self.fetch_operator_info(operator_addr).await {
                     Ok(info) => operator_infos.push(info),
                     Err(e) => error!("Error getting operator info for {:?}: {:?}", operator_addr, e),
                 }
@@ -255,9 +401,14 @@ impl EigenlayerClient {
             // Get total number of quorums
             let quorum_count = self.avs_registry.get_quorum_count().await?;
             
+            if quorum_count == 0 {
+                debug!("AVS registry has no quorums");
+                return Ok(Vec::new());
+            }
+            
             let mut quorum_infos = Vec::new();
             for i in 0..quorum_count {
-                match self.get_quorum_info(i as u8) {
+                match self.fetch_quorum_info(i as u8).await {
                     Ok(info) => quorum_infos.push(info),
                     Err(e) => error!("Error getting quorum info for {}: {:?}", i, e),
                 }
@@ -277,3 +428,221 @@ impl EigenlayerClient {
         self.config.chain_id
     }
 }
+
+/// Poll `provider` until `tx_hash` is mined with `confirmations` confirmations,
+/// giving up once `timeout` has elapsed
+async fn await_confirmations<M: Middleware>(
+    provider: &M,
+    tx_hash: &[u8],
+    confirmations: u64,
+    timeout: Duration,
+    poll_interval: Duration,
+) -> Result<TransactionReceipt> {
+    if tx_hash.len() != H256::len_bytes() {
+        return Err(anyhow::anyhow!(
+            "Transaction hash must be {} bytes, got {}",
+            H256::len_bytes(),
+            tx_hash.len()
+        ));
+    }
+    let tx_hash = H256::from_slice(tx_hash);
+    
+    debug!("Waiting for {} confirmations of {:?}", confirmations, tx_hash);
+    
+    let poll = async {
+        loop {
+            let receipt = provider.get_transaction_receipt(tx_hash).await
+                .map_err(|e| anyhow::anyhow!("Failed to fetch receipt of {:?}: {}", tx_hash, e))?;
+            
+            if let Some(mined_at) = receipt.as_ref().and_then(|receipt| receipt.block_number) {
+                let current = provider.get_block_number().await
+                    .map_err(|e| anyhow::anyhow!("Failed to fetch block number: {}", e))?;
+                
+                // The block including the transaction is its first confirmation
+                if current.as_u64() + 1 >= mined_at.as_u64() + confirmations {
+                    return Ok(receipt.expect("block number is only read from a receipt; qed"));
+                }
+            }
+            
+            tokio::time::sleep(poll_interval).await;
+        }
+    };
+    
+    match tokio::time::timeout(timeout, poll).await {
+        Ok(result) => result,
+        Err(_) => Err(anyhow::anyhow!(
+            "Timed out after {:?} waiting for {} confirmations of {:?}",
+            timeout, confirmations, tx_hash
+        )),
+    }
+}
+
+/// Fee (in wei) of `tx`: `provider`'s gas estimate at `gas_oracle`'s gas price
+async fn estimate_fee<M: Middleware, O: GasOracle>(provider: &M, gas_oracle: &O, tx: &TypedTransaction) -> Result<U256> {
+    let gas = provider.estimate_gas(tx, None).await
+        .map_err(|e| anyhow::anyhow!("Failed to estimate gas: {}", e))?;
+    let gas_price = gas_oracle.fetch().await
+        .context("Failed to fetch gas price")?;
+    
+    Ok(gas.saturating_mul(gas_price))
+}
+
+/// Per-strategy breakdown of `operator_shares` as (strategy address, shares), with the
+/// total across strategies (saturating rather than overflowing)
+fn share_breakdown(operator_shares: impl IntoIterator<Item = (Address, u128)>) -> (Vec<(Vec<u8>, u128)>, u128) {
+    let breakdown: Vec<(Vec<u8>, u128)> = operator_shares.into_iter()
+        .map(|(strategy, shares)| (address_to_bytes(&strategy), shares))
+        .collect();
+    let total = breakdown.iter().fold(0u128, |total, (_, shares)| total.saturating_add(*shares));
+    
+    (breakdown, total)
+}
+
+/// Fail, listing every quorum in `quorums` whose minimum stake exceeds `restaked_amount`
+fn ensure_quorum_stakes_met(operator_addr: Address, restaked_amount: u128, quorums: &[QuorumInfo]) -> Result<()> {
+    let failing_quorums: Vec<String> = quorums.iter()
+        .filter(|quorum| restaked_amount < quorum.min_stake)
+        .map(|quorum| format!("quorum {} (min stake {} wei)", quorum.quorum_id, quorum.min_stake))
+        .collect();
+    
+    if !failing_quorums.is_empty() {
+        return Err(anyhow::anyhow!(
+            "Operator {:?} restakes {} wei, below the minimum for {}",
+            operator_addr,
+            restaked_amount,
+            failing_quorums.join(", ")
+        ));
+    }
+    
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eigenlayer::gas::ConfiguredGasOracle;
+    use ethers::{
+        middleware::gas_oracle::ProviderOracle,
+        providers::MockProvider,
+        types::{TransactionRequest, U64},
+    };
+    
+    const TX_HASH: [u8; 32] = [0xAB; 32];
+    
+    fn mined_at(block: u64) -> Option<TransactionReceipt> {
+        Some(TransactionReceipt {
+            transaction_hash: H256(TX_HASH),
+            block_number: Some(U64::from(block)),
+            ..Default::default()
+        })
+    }
+    
+    #[tokio::test]
+    async fn waits_for_configured_confirmations() {
+        let (provider, mock) = Provider::mocked();
+        // Responses are served last-pushed first: the receipt is polled twice,
+        // reading blocks 11 and then 12 for a transaction mined in block 10
+        mock.push(U64::from(12)).unwrap();
+        mock.push(mined_at(10)).unwrap();
+        mock.push(U64::from(11)).unwrap();
+        mock.push(mined_at(10)).unwrap();
+        
+        let receipt = await_confirmations(
+            &provider, &TX_HASH, 3, Duration::from_secs(5), Duration::from_millis(1),
+        ).await.unwrap();
+        
+        assert_eq!(receipt.block_number, Some(U64::from(10)));
+    }
+    
+    #[tokio::test]
+    async fn times_out_when_confirmations_do_not_arrive() {
+        let (provider, mock) = Provider::mocked();
+        // The transaction is never mined
+        for _ in 0..1_000 {
+            mock.push(Option::<TransactionReceipt>::None).unwrap();
+        }
+        
+        let err = await_confirmations(
+            &provider, &TX_HASH, 1, Duration::from_millis(50), Duration::from_millis(5),
+        ).await.unwrap_err();
+        
+        assert!(err.to_string().contains("Timed out"), "unexpected error: {}", err);
+    }
+    
+    #[tokio::test]
+    async fn rejects_malformed_transaction_hash() {
+        let (provider, _mock) = Provider::mocked();
+        
+        let err = await_confirmations(
+            &provider, &TX_HASH[..31], 1, Duration::from_secs(5), Duration::from_millis(1),
+        ).await.unwrap_err();
+        
+        assert!(err.to_string().contains("must be 32 bytes"), "unexpected error: {}", err);
+    }
+    
+    fn quorum(quorum_id: u8, min_stake: u128) -> QuorumInfo {
+        QuorumInfo { quorum_id, operator_count: 1, total_stake: min_stake, min_stake }
+    }
+    
+    #[test]
+    fn under_staked_operator_is_rejected_listing_every_failing_quorum() {
+        let quorums = [quorum(0, 2_000), quorum(1, 500), quorum(2, 1_500)];
+        
+        let err = ensure_quorum_stakes_met(Address::repeat_byte(1), 1_000, &quorums).unwrap_err().to_string();
+        
+        assert!(err.contains("restakes 1000 wei"), "unexpected error: {}", err);
+        assert!(err.contains("quorum 0 (min stake 2000 wei)"), "unexpected error: {}", err);
+        assert!(err.contains("quorum 2 (min stake 1500 wei)"), "unexpected error: {}", err);
+        assert!(!err.contains("quorum 1"), "unexpected error: {}", err);
+    }
+    
+    #[test]
+    fn operator_meeting_every_minimum_passes() {
+        let quorums = [quorum(0, 1_000), quorum(1, 500)];
+        
+        assert!(ensure_quorum_stakes_met(Address::repeat_byte(1), 1_000, &quorums).is_ok());
+        assert!(ensure_quorum_stakes_met(Address::repeat_byte(1), 0, &[]).is_ok());
+    }
+    
+    #[test]
+    fn share_breakdown_keeps_each_strategy_and_the_total() {
+        let shares = vec![(Address::repeat_byte(1), 1_000), (Address::repeat_byte(2), 2_500)];
+        
+        let (breakdown, total) = share_breakdown(shares);
+        
+        assert_eq!(breakdown, vec![(vec![1; 20], 1_000), (vec![2; 20], 2_500)]);
+        assert_eq!(total, 3_500);
+    }
+    
+    #[test]
+    fn share_breakdown_total_saturates() {
+        let shares = vec![(Address::repeat_byte(1), u128::MAX), (Address::repeat_byte(2), 1)];
+        
+        assert_eq!(share_breakdown(shares).1, u128::MAX);
+        assert_eq!(share_breakdown(Vec::new()), (Vec::new(), 0));
+    }
+    
+    #[tokio::test]
+    async fn fee_estimate_prices_the_estimated_gas_at_the_configured_price() {
+        let (provider, mock) = Provider::mocked();
+        mock.push(U256::from(150_000)).unwrap();
+        let gas_oracle = ConfiguredGasOracle::with_fees(Some(20), None, None, ProviderOracle::new(provider.clone()));
+        let tx = TypedTransaction::Legacy(TransactionRequest::new());
+        
+        let fee = estimate_fee(&provider, &gas_oracle, &tx).await.unwrap();
+        
+        // 150,000 gas at 20 gwei
+        assert_eq!(fee, U256::from(150_000u64 * 20_000_000_000));
+    }
+    
+    #[tokio::test]
+    async fn fee_estimate_fails_when_gas_cannot_be_estimated() {
+        let (provider, _mock) = Provider::mocked();
+        let gas_oracle = ConfiguredGasOracle::with_fees(Some(20), None, None, ProviderOracle::new(provider.clone()));
+        let tx = TypedTransaction::Legacy(TransactionRequest::new());
+        
+        let err = estimate_fee(&provider, &gas_oracle, &tx).await.unwrap_err();
+        
+        assert!(err.to_string().contains("Failed to estimate gas"));
+    }
+}
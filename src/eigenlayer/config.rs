@@ -1,6 +1,7 @@
 //! Configuration for Eigenlayer integration
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::{collections::HashMap, env, path::PathBuf, str::FromStr};
+use alloy_primitives::Address;
 use eigensdk::eigen_common::getters::{get_signer_and_provider, SignerConfig};
 use anyhow::Result;
 
@@ -25,13 +26,72 @@ pub struct EigenConfig {
     /// Configuration for this AVS
     pub avs_config: AVSConfig,
     
+    /// Underlying token of each strategy, as strategy address -> token address
+    #[serde(default)]
+    pub strategy_tokens: HashMap<String, String>,
+    
     /// Gas price in gwei
     pub gas_price_gwei: Option<u64>,
     
+    /// EIP-1559 max fee per gas in gwei; takes precedence over `gas_price_gwei`
+    #[serde(default)]
+    pub max_fee_per_gas_gwei: Option<u64>,
+    
+    /// EIP-1559 priority fee per gas in gwei, capped at the max fee
+    #[serde(default)]
+    pub max_priority_fee_per_gas_gwei: Option<u64>,
+    
+    /// Block confirmations write calls wait for before returning
+    #[serde(default = "default_confirmations")]
+    pub confirmations: u64,
+    
+    /// Seconds to wait for those confirmations before giving up
+    #[serde(default = "default_confirmation_timeout_secs")]
+    pub confirmation_timeout_secs: u64,
+    
     /// Whether to use mainnet or testnet
     pub is_mainnet: bool,
 }
 
+fn default_confirmations() -> u64 {
+    1
+}
+
+fn default_confirmation_timeout_secs() -> u64 {
+    120
+}
+
+// `0x` followed by exactly 40 hex digits
+fn is_valid_address(address: &str) -> bool {
+    address.strip_prefix("0x")
+        .map_or(false, |hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+// A set, non-empty environment variable
+fn env_var(name: &str) -> Option<String> {
+    env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+fn parse_value<T: FromStr>(name: &str, value: &str) -> Result<T> {
+    value.parse().map_err(|_| anyhow::anyhow!("Invalid value for {}: {}", name, value))
+}
+
+fn parse_optional<T: FromStr>(name: &str) -> Result<Option<T>> {
+    env_var(name).map(|value| parse_value(name, &value)).transpose()
+}
+
+// `strategy=token` pairs separated by commas
+fn parse_strategy_tokens(pairs: &str) -> Result<HashMap<String, String>> {
+    pairs.split(',')
+        .map(|pair| match pair.split_once('=') {
+            Some((strategy, token)) if !strategy.trim().is_empty() && !token.trim().is_empty() => {
+                Ok((strategy.trim().to_string(), token.trim().to_string()))
+            }
+            _ => Err(anyhow::anyhow!("Invalid value for ELXR_STRATEGY_TOKENS: expected strategy=token, got {:?}", pair)),
+        })
+        .collect()
+}
+
 /// Wrapper for Eigensdk's SignerConfig
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct SignerConfigWrapper {
@@ -107,16 +167,162 @@ pub struct AVSConfig {
     
     /// Minimum operator stake (in ETH)
     pub min_operator_stake_eth: f64,
+    
+    /// Skip checking each required quorum's minimum stake before registering
+    #[serde(default)]
+    pub skip_quorum_stake_check: bool,
 }
 
 impl EigenConfig {
     /// Load configuration from a file
+    ///
+    /// The file is validated on load, so files with an empty
+    /// `avs_config.required_quorums`, which older releases accepted even though the
+    /// client then registered into no quorum, are now rejected and must list at
+    /// least one quorum ID.
     pub fn from_file(path: &str) -> Result<Self> {
         let config_str = std::fs::read_to_string(path)?;
         let config: EigenConfig = serde_json::from_str(&config_str)?;
+        config.validate()?;
         Ok(config)
     }
     
+    /// Load configuration from `ELXR_*` environment variables.
+    ///
+    /// Required: `ELXR_ETH_RPC_URL`, `ELXR_CHAIN_ID`, the contract addresses
+    /// (`ELXR_DELEGATION_MANAGER`, `ELXR_AVS_DIRECTORY`, `ELXR_STRATEGY_MANAGER`,
+    /// `ELXR_REGISTRY_COORDINATOR`, `ELXR_BLS_PUBLIC_KEY_COMPENDIUM`, `ELXR_SLASHER`),
+    /// `ELXR_REQUIRED_QUORUMS` as comma-separated quorum IDs, and one signer:
+    /// `ELXR_ECDSA_PRIVATE_KEY`, `ELXR_KEYSTORE_PATH` with `ELXR_KEYSTORE_PASSWORD`,
+    /// or `ELXR_FIREBLOCKS_API_KEY` with `ELXR_FIREBLOCKS_PRIVATE_KEY_PATH` and
+    /// `ELXR_FIREBLOCKS_API_URL`. Every missing required variable is listed in the error.
+    ///
+    /// `ELXR_STRATEGY_TOKENS` optionally maps strategies to their underlying tokens
+    /// as comma-separated `strategy=token` pairs.
+    pub fn from_env() -> Result<Self> {
+        let mut missing = Vec::new();
+        let mut required = |name: &'static str| {
+            env_var(name).unwrap_or_else(|| {
+                missing.push(name);
+                String::new()
+            })
+        };
+        
+        let eth_rpc_url = required("ELXR_ETH_RPC_URL");
+        let chain_id = required("ELXR_CHAIN_ID");
+        let required_quorums = required("ELXR_REQUIRED_QUORUMS");
+        let contract_addresses = ContractAddresses {
+            delegation_manager: required("ELXR_DELEGATION_MANAGER"),
+            avs_directory: required("ELXR_AVS_DIRECTORY"),
+            strategy_manager: required("ELXR_STRATEGY_MANAGER"),
+            elxr_registry_coordinator: required("ELXR_REGISTRY_COORDINATOR"),
+            bls_public_key_compendium: required("ELXR_BLS_PUBLIC_KEY_COMPENDIUM"),
+            slasher: required("ELXR_SLASHER"),
+        };
+        
+        // Keys are taken straight from the environment, never written to disk
+        let ecdsa_config = if let Some(private_key) = env_var("ELXR_ECDSA_PRIVATE_KEY") {
+            SignerConfigWrapper { keystore: None, private_key: Some(private_key), fireblocks: None }
+        } else if let Some(path) = env_var("ELXR_KEYSTORE_PATH") {
+            let keystore = KeystoreConfig {
+                path: PathBuf::from(path),
+                password: required("ELXR_KEYSTORE_PASSWORD"),
+            };
+            SignerConfigWrapper { keystore: Some(keystore), private_key: None, fireblocks: None }
+        } else if let Some(api_key) = env_var("ELXR_FIREBLOCKS_API_KEY") {
+            let fireblocks = FireblocksConfig {
+                api_key,
+                private_key_path: PathBuf::from(required("ELXR_FIREBLOCKS_PRIVATE_KEY_PATH")),
+                api_url: required("ELXR_FIREBLOCKS_API_URL"),
+            };
+            SignerConfigWrapper { keystore: None, private_key: None, fireblocks: Some(fireblocks) }
+        } else {
+            missing.push("ELXR_ECDSA_PRIVATE_KEY (or ELXR_KEYSTORE_PATH / ELXR_FIREBLOCKS_API_KEY)");
+            SignerConfigWrapper { keystore: None, private_key: None, fireblocks: None }
+        };
+        
+        if !missing.is_empty() {
+            return Err(anyhow::anyhow!("Missing required environment variables: {}", missing.join(", ")));
+        }
+        
+        let required_quorums = required_quorums.split(',')
+            .map(|id| id.trim().parse::<u8>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|_| anyhow::anyhow!("Invalid value for ELXR_REQUIRED_QUORUMS: {}", required_quorums))?;
+        
+        let strategy_tokens = match env_var("ELXR_STRATEGY_TOKENS") {
+            Some(pairs) => parse_strategy_tokens(&pairs)?,
+            None => HashMap::new(),
+        };
+        
+        let config = Self {
+            eth_rpc_url,
+            chain_id: parse_value("ELXR_CHAIN_ID", &chain_id)?,
+            contract_addresses,
+            bls_private_key_path: env_var("ELXR_BLS_PRIVATE_KEY_PATH").map(PathBuf::from),
+            ecdsa_config,
+            avs_config: AVSConfig {
+                name: env_var("ELXR_AVS_NAME").unwrap_or_else(|| "elxr".to_string()),
+                version: env_var("ELXR_AVS_VERSION").unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
+                metadata_url: env_var("ELXR_AVS_METADATA_URL"),
+                required_quorums,
+                min_operator_stake_eth: parse_optional("ELXR_MIN_OPERATOR_STAKE_ETH")?.unwrap_or(0.0),
+                skip_quorum_stake_check: parse_optional("ELXR_SKIP_QUORUM_STAKE_CHECK")?.unwrap_or(false),
+            },
+            strategy_tokens,
+            gas_price_gwei: parse_optional("ELXR_GAS_PRICE_GWEI")?,
+            max_fee_per_gas_gwei: parse_optional("ELXR_MAX_FEE_PER_GAS_GWEI")?,
+            max_priority_fee_per_gas_gwei: parse_optional("ELXR_MAX_PRIORITY_FEE_PER_GAS_GWEI")?,
+            confirmations: parse_optional("ELXR_CONFIRMATIONS")?.unwrap_or_else(default_confirmations),
+            confirmation_timeout_secs: parse_optional("ELXR_CONFIRMATION_TIMEOUT_SECS")?
+                .unwrap_or_else(default_confirmation_timeout_secs),
+            is_mainnet: parse_optional("ELXR_MAINNET")?.unwrap_or(false),
+        };
+        config.validate()?;
+        Ok(config)
+    }
+    
+    /// Check every contract address is a 20-byte hex address and at least one quorum
+    /// is required, reporting all problems in a single error
+    pub fn validate(&self) -> Result<()> {
+        let addresses = &self.contract_addresses;
+        let mut problems: Vec<String> = [
+            ("delegation_manager", &addresses.delegation_manager),
+            ("avs_directory", &addresses.avs_directory),
+            ("strategy_manager", &addresses.strategy_manager),
+            ("elxr_registry_coordinator", &addresses.elxr_registry_coordinator),
+            ("bls_public_key_compendium", &addresses.bls_public_key_compendium),
+            ("slasher", &addresses.slasher),
+        ]
+        .iter()
+        .filter(|(_, address)| !is_valid_address(address))
+        .map(|(name, address)| format!("{} is not a 20-byte hex address: {:?}", name, address))
+        .collect();
+        
+        if self.avs_config.required_quorums.is_empty() {
+            problems.push("avs_config.required_quorums is empty".to_string());
+        }
+        
+        if !problems.is_empty() {
+            return Err(anyhow::anyhow!("Invalid Eigenlayer configuration: {}", problems.join("; ")));
+        }
+        
+        Ok(())
+    }
+    
+    /// Strategy-to-token mapping as raw addresses, for `RestakeInfo::stake_amounts`
+    pub fn strategy_token_map(&self) -> Result<HashMap<Vec<u8>, Vec<u8>>> {
+        let parse = |address: &str| {
+            Address::from_str(address)
+                .map(|address| address.to_vec())
+                .map_err(|_| anyhow::anyhow!("Invalid address in strategy_tokens: {}", address))
+        };
+        
+        self.strategy_tokens.iter()
+            .map(|(strategy, token)| Ok((parse(strategy)?, parse(token)?)))
+            .collect()
+    }
+    
     /// Get a signer and provider using this configuration
     pub fn get_signer_and_provider(&self) -> Result<(ethers::signers::Wallet<ethers::signers::LocalWallet>, ethers::providers::Provider<ethers::providers::Http>)> {
         // Convert to the SDK's signer config format
@@ -154,3 +360,148 @@ impl EigenConfig {
         Ok(signer_config)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    
+    // The environment is process-wide, so tests that touch it run one at a time
+    static ENV_LOCK: Mutex<()> = Mutex::new(());
+    
+    const STRATEGY: &str = "0x1111111111111111111111111111111111111111";
+    const TOKEN: &str = "0x2222222222222222222222222222222222222222";
+    
+    fn address(byte: char) -> String {
+        format!("0x{}", byte.to_string().repeat(40))
+    }
+    
+    fn required_vars() -> Vec<(&'static str, String)> {
+        vec![
+            ("ELXR_ETH_RPC_URL", "http://localhost:8545".to_string()),
+            ("ELXR_CHAIN_ID", "17000".to_string()),
+            ("ELXR_DELEGATION_MANAGER", address('a')),
+            ("ELXR_AVS_DIRECTORY", address('b')),
+            ("ELXR_STRATEGY_MANAGER", address('c')),
+            ("ELXR_REGISTRY_COORDINATOR", address('d')),
+            ("ELXR_BLS_PUBLIC_KEY_COMPENDIUM", address('e')),
+            ("ELXR_SLASHER", address('f')),
+            ("ELXR_REQUIRED_QUORUMS", "0, 1".to_string()),
+            ("ELXR_ECDSA_PRIVATE_KEY", format!("0x{}", "1".repeat(64))),
+        ]
+    }
+    
+    /// Run `f` with exactly `vars` set among the `ELXR_*` variables
+    fn with_env<R>(vars: &[(&str, String)], f: impl FnOnce() -> R) -> R {
+        let _guard = ENV_LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        let clear = || {
+            for (name, _) in env::vars().filter(|(name, _)| name.starts_with("ELXR_")) {
+                env::remove_var(name);
+            }
+        };
+        clear();
+        for (name, value) in vars {
+            env::set_var(name, value);
+        }
+        let result = f();
+        clear();
+        result
+    }
+    
+    #[test]
+    fn from_env_builds_a_valid_config() {
+        let mut vars = required_vars();
+        vars.push(("ELXR_STRATEGY_TOKENS", format!("{}={}", STRATEGY, TOKEN)));
+        
+        let config = with_env(&vars, EigenConfig::from_env).unwrap();
+        
+        assert_eq!(config.eth_rpc_url, "http://localhost:8545");
+        assert_eq!(config.chain_id, 17000);
+        assert_eq!(config.contract_addresses.slasher, address('f'));
+        assert_eq!(config.avs_config.required_quorums, vec![0, 1]);
+        assert_eq!(config.ecdsa_config.private_key, Some(format!("0x{}", "1".repeat(64))));
+        assert_eq!(config.strategy_tokens.get(STRATEGY).map(String::as_str), Some(TOKEN));
+        assert_eq!(config.confirmations, default_confirmations());
+    }
+    
+    #[test]
+    fn from_env_lists_every_missing_variable() {
+        let vars: Vec<_> = required_vars()
+            .into_iter()
+            .filter(|(name, _)| !matches!(*name, "ELXR_CHAIN_ID" | "ELXR_SLASHER" | "ELXR_ECDSA_PRIVATE_KEY"))
+            .collect();
+        
+        let error = with_env(&vars, EigenConfig::from_env).unwrap_err().to_string();
+        
+        assert!(error.contains("ELXR_CHAIN_ID"));
+        assert!(error.contains("ELXR_SLASHER"));
+        assert!(error.contains("ELXR_ECDSA_PRIVATE_KEY"));
+        assert!(!error.contains("ELXR_ETH_RPC_URL"));
+    }
+    
+    #[test]
+    fn strategy_token_map_holds_raw_addresses() {
+        let mut vars = required_vars();
+        vars.push(("ELXR_STRATEGY_TOKENS", format!("{}={}", STRATEGY, TOKEN)));
+        let mut config = with_env(&vars, EigenConfig::from_env).unwrap();
+        
+        let map = config.strategy_token_map().unwrap();
+        assert_eq!(map.get(&vec![0x11; 20]), Some(&vec![0x22; 20]));
+        
+        config.strategy_tokens.insert("0x1234".to_string(), TOKEN.to_string());
+        assert!(config.strategy_token_map().is_err());
+    }
+    
+    #[test]
+    fn from_env_rejects_malformed_strategy_tokens() {
+        let mut vars = required_vars();
+        vars.push(("ELXR_STRATEGY_TOKENS", STRATEGY.to_string()));
+        
+        let error = with_env(&vars, EigenConfig::from_env).unwrap_err().to_string();
+        
+        assert!(error.contains("ELXR_STRATEGY_TOKENS"));
+    }
+    
+    #[test]
+    fn from_env_rejects_a_malformed_contract_address() {
+        let vars: Vec<_> = required_vars()
+            .into_iter()
+            .map(|(name, value)| match name {
+                "ELXR_SLASHER" => (name, "0xnot-an-address".to_string()),
+                _ => (name, value),
+            })
+            .collect();
+        
+        let error = with_env(&vars, EigenConfig::from_env).unwrap_err().to_string();
+        
+        assert!(error.contains("slasher is not a 20-byte hex address"));
+        assert!(!error.contains("required_quorums"));
+    }
+    
+    #[test]
+    fn from_file_rejects_an_empty_quorum_list() {
+        let mut config = with_env(&required_vars(), EigenConfig::from_env).unwrap();
+        config.avs_config.required_quorums.clear();
+        let path = env::temp_dir().join(format!("elxr-config-{}.json", std::process::id()));
+        std::fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+        
+        let result = EigenConfig::from_file(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        
+        let error = result.unwrap_err().to_string();
+        assert!(error.contains("avs_config.required_quorums is empty"));
+        assert!(!error.contains("not a 20-byte hex address"));
+    }
+    
+    #[test]
+    fn validation_reports_malformed_addresses_and_empty_quorums_together() {
+        let mut config = with_env(&required_vars(), EigenConfig::from_env).unwrap();
+        config.contract_addresses.avs_directory = "0x1234".to_string();
+        config.avs_config.required_quorums.clear();
+        
+        let error = config.validate().unwrap_err().to_string();
+        
+        assert!(error.contains("avs_directory"));
+        assert!(error.contains("required_quorums"));
+    }
+}
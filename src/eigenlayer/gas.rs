@@ -0,0 +1,217 @@
+//! Gas pricing for Eigenlayer write transactions
+use crate::eigenlayer::config::EigenConfig;
+use async_trait::async_trait;
+use ethers::{
+    middleware::gas_oracle::{GasOracle, GasOracleError, GasOracleMiddleware, ProviderOracle},
+    providers::{Http, Provider},
+    types::U256,
+    utils::parse_units,
+};
+
+/// Provider that fills in gas prices according to the configuration
+pub type GasPricedProvider<P = Http> = GasOracleMiddleware<Provider<P>, ConfiguredGasOracle<ProviderOracle<Provider<P>>>>;
+
+/// Gas oracle applying the configured gas price, falling back to `O`'s
+/// estimates for anything left unset
+#[derive(Debug)]
+pub struct ConfiguredGasOracle<O = ProviderOracle<Provider<Http>>> {
+    /// Legacy gas price (in wei)
+    gas_price: Option<U256>,
+
+    /// EIP-1559 (max fee, priority fee) per gas (in wei)
+    eip1559_fees: Option<(U256, U256)>,
+
+    /// Estimates used when nothing is configured
+    fallback: O,
+}
+
+impl ConfiguredGasOracle {
+    /// Create an oracle for `config`, estimating through `provider` when unset
+    pub fn new(config: &EigenConfig, provider: Provider<Http>) -> Self {
+        Self::with_fees(
+            config.gas_price_gwei,
+            config.max_fee_per_gas_gwei,
+            config.max_priority_fee_per_gas_gwei,
+            ProviderOracle::new(provider),
+        )
+    }
+}
+
+impl<O> ConfiguredGasOracle<O> {
+    /// Create an oracle for fees given in gwei, estimating through `fallback` when unset
+    pub(crate) fn with_fees(
+        gas_price_gwei: Option<u64>,
+        max_fee_per_gas_gwei: Option<u64>,
+        max_priority_fee_per_gas_gwei: Option<u64>,
+        fallback: O,
+    ) -> Self {
+        let gas_price = gas_price_gwei.map(gwei);
+        // A max fee alone tips up to the whole fee, as a legacy price would
+        let eip1559_fees = max_fee_per_gas_gwei.map(|max_fee| {
+            let max_fee = gwei(max_fee);
+            let priority_fee = max_priority_fee_per_gas_gwei.map(gwei).unwrap_or(max_fee);
+            (max_fee, priority_fee.min(max_fee))
+        });
+
+        Self {
+            gas_price,
+            eip1559_fees,
+            fallback,
+        }
+    }
+}
+
+fn gwei(amount: u64) -> U256 {
+    parse_units(amount, "gwei").expect("gwei is a valid unit").into()
+}
+
+#[async_trait]
+impl<O: GasOracle> GasOracle for ConfiguredGasOracle<O> {
+    async fn fetch(&self) -> Result<U256, GasOracleError> {
+        match self.gas_price {
+            Some(gas_price) => Ok(gas_price),
+            None => self.fallback.fetch().await,
+        }
+    }
+
+    async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+        match (self.eip1559_fees, self.gas_price) {
+            (Some(fees), _) => Ok(fees),
+            (None, Some(gas_price)) => Ok((gas_price, gas_price)),
+            (None, None) => self.fallback.estimate_eip1559_fees().await,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        providers::{Middleware, MockProvider},
+        types::{
+            transaction::eip2718::TypedTransaction, Address, Bytes, Eip1559TransactionRequest, TransactionRequest, H256,
+        },
+    };
+
+    /// Provider estimates, distinct from any configured fee
+    const ESTIMATED_PRICE: u64 = 7;
+    const ESTIMATED_FEES: (u64, u64) = (9, 3);
+
+    #[derive(Debug)]
+    struct EstimatingOracle;
+
+    #[async_trait]
+    impl GasOracle for EstimatingOracle {
+        async fn fetch(&self) -> Result<U256, GasOracleError> {
+            Ok(U256::from(ESTIMATED_PRICE))
+        }
+
+        async fn estimate_eip1559_fees(&self) -> Result<(U256, U256), GasOracleError> {
+            Ok((U256::from(ESTIMATED_FEES.0), U256::from(ESTIMATED_FEES.1)))
+        }
+    }
+
+    fn oracle(
+        gas_price: Option<u64>,
+        max_fee: Option<u64>,
+        priority_fee: Option<u64>,
+    ) -> ConfiguredGasOracle<EstimatingOracle> {
+        ConfiguredGasOracle::with_fees(gas_price, max_fee, priority_fee, EstimatingOracle)
+    }
+
+    fn estimated_fees() -> (U256, U256) {
+        (U256::from(ESTIMATED_FEES.0), U256::from(ESTIMATED_FEES.1))
+    }
+
+    #[tokio::test]
+    async fn unconfigured_fees_fall_back_to_estimates() {
+        let oracle = oracle(None, None, None);
+
+        assert_eq!(oracle.fetch().await.unwrap(), U256::from(ESTIMATED_PRICE));
+        assert_eq!(oracle.estimate_eip1559_fees().await.unwrap(), estimated_fees());
+    }
+
+    #[tokio::test]
+    async fn legacy_price_applies_to_both_fee_models() {
+        let oracle = oracle(Some(20), None, None);
+
+        assert_eq!(oracle.fetch().await.unwrap(), gwei(20));
+        assert_eq!(oracle.estimate_eip1559_fees().await.unwrap(), (gwei(20), gwei(20)));
+    }
+
+    #[tokio::test]
+    async fn max_fee_takes_precedence_over_legacy_price() {
+        let oracle = oracle(Some(20), Some(50), Some(2));
+
+        assert_eq!(oracle.estimate_eip1559_fees().await.unwrap(), (gwei(50), gwei(2)));
+        assert_eq!(oracle.fetch().await.unwrap(), gwei(20));
+    }
+
+    #[tokio::test]
+    async fn max_fee_alone_tips_the_whole_fee() {
+        let oracle = oracle(None, Some(50), None);
+
+        assert_eq!(oracle.estimate_eip1559_fees().await.unwrap(), (gwei(50), gwei(50)));
+        // Legacy transactions are still priced by the provider
+        assert_eq!(oracle.fetch().await.unwrap(), U256::from(ESTIMATED_PRICE));
+    }
+
+    #[tokio::test]
+    async fn priority_fee_is_capped_at_max_fee() {
+        let oracle = oracle(None, Some(50), Some(80));
+
+        assert_eq!(oracle.estimate_eip1559_fees().await.unwrap(), (gwei(50), gwei(50)));
+    }
+
+    #[tokio::test]
+    async fn priority_fee_without_max_fee_is_ignored() {
+        let oracle = oracle(None, None, Some(2));
+
+        assert_eq!(oracle.estimate_eip1559_fees().await.unwrap(), estimated_fees());
+    }
+
+    /// Registry coordinator `register_operator` sends its registration to
+    const REGISTRY_COORDINATOR: [u8; 20] = [0xC0; 20];
+
+    /// Gas-priced provider over a mocked RPC, as the client builds it for write calls
+    fn mocked_gas_priced_provider(
+        gas_price: Option<u64>,
+        max_fee: Option<u64>,
+        priority_fee: Option<u64>,
+    ) -> (GasPricedProvider<MockProvider>, MockProvider) {
+        let (provider, mock) = Provider::mocked();
+        let oracle = ConfiguredGasOracle::with_fees(gas_price, max_fee, priority_fee, ProviderOracle::new(provider.clone()));
+        // The only RPC call left once gas and fees are filled in
+        mock.push(H256::repeat_byte(0xAB)).unwrap();
+        (GasOracleMiddleware::new(provider, oracle), mock)
+    }
+
+    /// A registration call from the operator to the registry coordinator, gas already estimated
+    fn registration_call() -> (Address, Address, Bytes) {
+        (Address::repeat_byte(1), Address::from(REGISTRY_COORDINATOR), Bytes::from(vec![0xA5; 68]))
+    }
+
+    #[tokio::test]
+    async fn registration_is_sent_with_the_configured_eip1559_fees() {
+        let (provider, mock) = mocked_gas_priced_provider(Some(20), Some(50), Some(2));
+        let (from, to, data) = registration_call();
+        let tx = Eip1559TransactionRequest::new().from(from).to(to).data(data).gas(300_000);
+
+        provider.send_transaction(tx.clone(), None).await.unwrap();
+
+        let sent = TypedTransaction::Eip1559(tx.max_fee_per_gas(gwei(50)).max_priority_fee_per_gas(gwei(2)));
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn legacy_registration_is_sent_at_the_configured_gas_price() {
+        let (provider, mock) = mocked_gas_priced_provider(Some(20), None, None);
+        let (from, to, data) = registration_call();
+        let tx = TransactionRequest::new().from(from).to(to).data(data).gas(300_000);
+
+        provider.send_transaction(tx.clone(), None).await.unwrap();
+
+        let sent = TypedTransaction::Legacy(tx.gas_price(gwei(20)));
+        mock.assert_request("eth_sendTransaction", [sent]).unwrap();
+    }
+}
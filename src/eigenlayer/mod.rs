@@ -5,14 +5,19 @@
 //! 
 //! It leverages the official eigensdk-rs library to interact with Eigenlayer contracts.
 
+mod backend;
 mod client;
 mod config;
+mod gas;
 mod operator;
+mod oracle_integration;
 mod service;
 mod types;
 
+pub use backend::{EigenBackend, MockEigenBackend};
 pub use client::EigenlayerClient;
 pub use config::EigenConfig;
-pub use operator::{OperatorInfo, OperatorManager};
+pub use operator::{OperatorChange, OperatorEvent, OperatorInfo, OperatorManager};
+pub use oracle_integration::{EigenlayerOracleService, PriceSource, RuntimeApiPriceSource};
 pub use service::EigenlayerService;
 pub use types::{RestakeInfo, QuorumInfo, StakeAmount, AVSIdentifier};
@@ -1,16 +1,18 @@
 //! Operator management for Eigenlayer integration
 use crate::eigenlayer::{
+    backend::EigenBackend,
     client::EigenlayerClient,
     types::{RestakeInfo, QuorumInfo, OperatorStatus},
 };
 use anyhow::{Result, Context};
+use ethers::types::U256;
 use log::{info, error, warn, debug};
 use parity_scale_codec::{Decode, Encode};
 use scale_info::TypeInfo;
 use serde::{Deserialize, Serialize};
 use sp_runtime::RuntimeDebug;
 use std::{collections::HashMap, sync::{Arc, Mutex, RwLock}};
-use tokio::runtime::Runtime;
+use tokio::{runtime::Runtime, sync::broadcast};
 
 /// Information about an operator in the Eigenlayer ecosystem
 #[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode, TypeInfo, PartialEq, Eq)]
@@ -21,6 +23,9 @@ pub struct OperatorInfo {
     /// Last time this operator was updated
     pub last_updated: u64,
     
+    /// Last time the restaking information was fetched from Eigenlayer
+    pub fetched_at: u64,
+    
     /// Reliability score (0-100)
     pub reliability_score: u8,
     
@@ -29,12 +34,63 @@ pub struct OperatorInfo {
     
     /// Number of slashes received
     pub slashes: u32,
+    
+    /// Most recent slashes as (timestamp, severity), oldest first
+    pub slash_history: Vec<(u64, u8)>,
+    
+    /// Onboarding phase: newly registered or discovered operators stay `Pending` until they have
+    /// enough successful validations to be promoted to `Active`
+    pub onboarding_status: OperatorStatus,
+}
+
+/// Number of slashes kept in an operator's history
+pub const MAX_SLASH_HISTORY: usize = 32;
+
+/// Notifications published by the operator manager for monitoring
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperatorEvent {
+    /// A quorum's stake or operator count moved by more than the configured threshold
+    QuorumChanged {
+        quorum_id: u8,
+        old_stake: u128,
+        new_stake: u128,
+    },
+    /// An operator's stake came within the configured buffer of a quorum's minimum
+    StakeMarginLow {
+        operator_address: Vec<u8>,
+        quorum_id: u8,
+        margin: i128,
+    },
+    /// A quorum dropped below the configured minimum operator count or total stake
+    QuorumUnhealthy {
+        quorum_id: u8,
+        operator_count: u32,
+        total_stake: u128,
+    },
+    /// A refresh changed a cached operator's restaking information
+    OperatorUpdated {
+        operator_address: Vec<u8>,
+        changes: Vec<OperatorChange>,
+    },
+}
+
+/// A change to an operator's restaking information observed on refresh
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OperatorChange {
+    /// Restaked amount changed (in wei)
+    RestakedAmount { old: u128, new: u128 },
+    /// Operator joined a quorum
+    QuorumJoined(u8),
+    /// Operator left a quorum
+    QuorumLeft(u8),
+    /// Eigenlayer status changed
+    Status { old: OperatorStatus, new: OperatorStatus },
 }
 
 /// Manages a set of operators for a specific chain
-pub struct OperatorManager {
+pub struct OperatorManager<B: EigenBackend = EigenlayerClient> {
     /// Client for interacting with Eigenlayer contracts
-    client: Arc<EigenlayerClient>,
+    client: Arc<B>,
     
     /// Cache of operator information
     operators: RwLock<HashMap<Vec<u8>, OperatorInfo>>,
@@ -50,13 +106,36 @@ pub struct OperatorManager {
     
     /// Maximum age of operator information before refresh (in seconds)
     max_cache_age: u64,
+    
+    /// Relative change (in percent) in a quorum's stake or operator count that is reported
+    quorum_change_threshold: u8,
+    
+    /// Minimum (operator count, total stake) below which refreshed quorums are reported
+    quorum_health_thresholds: Option<(u32, u128)>,
+    
+    /// Stake above a quorum's minimum below which operators are warned
+    stake_margin_buffer: Option<u128>,
+    
+    /// Successful validations a newly registered operator needs before activation
+    min_validations_to_activate: u64,
+    
+    /// Channel monitoring subscribers receive operator events on
+    events: broadcast::Sender<OperatorEvent>,
 }
 
-impl OperatorManager {
+impl<B: EigenBackend> OperatorManager<B> {
     /// Create a new operator manager
-    pub fn new(client: Arc<EigenlayerClient>) -> Result<Self> {
+    pub fn new(client: Arc<B>) -> Result<Self> {
         // Default cache age - 5 minutes
         const DEFAULT_CACHE_AGE: u64 = 300;
+        // Default probation for newly seen operators. Each successful validation
+        // also raises the reliability score by one point, so 100 validations is
+        // the run that takes an operator from a score of 0 back to the full 100;
+        // requiring the same from newcomers means no operator becomes active with
+        // less track record than a fully recovered one.
+        const DEFAULT_MIN_VALIDATIONS_TO_ACTIVATE: u64 = 100;
+        // Default quorum change worth reporting - 10%
+        const DEFAULT_QUORUM_CHANGE_THRESHOLD: u8 = 10;
         
         // Create runtime for async operations
         let runtime = Arc::new(Runtime::new()?);
@@ -68,6 +147,11 @@ impl OperatorManager {
             runtime,
             last_refresh: Mutex::new(0),
             max_cache_age: DEFAULT_CACHE_AGE,
+            quorum_change_threshold: DEFAULT_QUORUM_CHANGE_THRESHOLD,
+            quorum_health_thresholds: None,
+            stake_margin_buffer: None,
+            min_validations_to_activate: DEFAULT_MIN_VALIDATIONS_TO_ACTIVATE,
+            events: broadcast::channel(100).0,
         })
     }
     
@@ -76,6 +160,31 @@ impl OperatorManager {
         self.max_cache_age = age_seconds;
     }
     
+    /// Set the relative quorum change (in percent) that triggers a `QuorumChanged` event
+    pub fn set_quorum_change_threshold(&mut self, percent: u8) {
+        self.quorum_change_threshold = percent;
+    }
+    
+    /// Set how many successful validations a newly registered operator needs before activation
+    pub fn set_min_validations_to_activate(&mut self, validations: u64) {
+        self.min_validations_to_activate = validations;
+    }
+    
+    /// Warn when an operator's stake comes within `buffer` of a quorum's minimum
+    pub fn set_stake_margin_buffer(&mut self, buffer: u128) {
+        self.stake_margin_buffer = Some(buffer);
+    }
+    
+    /// Report quorums with fewer operators or less total stake than these on refresh
+    pub fn set_quorum_health_thresholds(&mut self, min_operators: u32, min_total_stake: u128) {
+        self.quorum_health_thresholds = Some((min_operators, min_total_stake));
+    }
+    
+    /// Sender side of the event channel, for handing out subscriptions
+    pub fn event_sender(&self) -> broadcast::Sender<OperatorEvent> {
+        self.events.clone()
+    }
+    
     /// Get the current timestamp
     fn current_time() -> u64 {
         std::time::SystemTime::now()
@@ -104,40 +213,151 @@ impl OperatorManager {
         // Get all operators from the client
         let operators = self.client.get_all_operators()?;
         
+        // Snapshot the quorums before taking the operators lock, so this never holds
+        // both locks at once and cannot deadlock against a quorum refresh
+        let quorums = self.quorums.read().unwrap().clone();
+        
         // Update the cache
         let mut cache = self.operators.write().unwrap();
         for op_info in operators {
-            let operator_id = op_info.operator_address.clone();
+            let previous = cache.get(&op_info.operator_address).map(|op| op.restake_info.clone());
+            let info = self.cache_operator(&mut cache, op_info);
+            self.check_stake_margins(&quorums, &info, previous.as_ref().map(|prev| prev.restaked_amount));
             
-            // If we already have this operator, update it while preserving stats
-            if let Some(existing) = cache.get(&operator_id) {
-                cache.insert(operator_id, OperatorInfo {
-                    restake_info: op_info,
-                    last_updated: Self::current_time(),
-                    reliability_score: existing.reliability_score,
-                    blocks_validated: existing.blocks_validated,
-                    slashes: existing.slashes,
-                });
-            } else {
-                // New operator
-                cache.insert(operator_id, OperatorInfo {
-                    restake_info: op_info,
-                    last_updated: Self::current_time(),
-                    reliability_score: 100, // Start with perfect score
-                    blocks_validated: 0,
-                    slashes: 0,
-                });
+            if let Some(previous) = previous {
+                let changes = Self::diff_restake_info(&previous, &info.restake_info);
+                if !changes.is_empty() {
+                    let _ = self.events.send(OperatorEvent::OperatorUpdated {
+                        operator_address: info.restake_info.operator_address.clone(),
+                        changes,
+                    });
+                }
             }
         }
         
         // Update last refresh time
         *self.last_refresh.lock().unwrap() = Self::current_time();
         
-        info!("Cached {} operators", cache.len());
+        info!(operator_count = cache.len(); "Cached operators");
         
         Ok(())
     }
     
+    /// Changes from `old` to `new` in restaked amount, quorum membership and status
+    pub fn diff_restake_info(old: &RestakeInfo, new: &RestakeInfo) -> Vec<OperatorChange> {
+        let mut changes = Vec::new();
+        
+        if old.restaked_amount != new.restaked_amount {
+            changes.push(OperatorChange::RestakedAmount {
+                old: old.restaked_amount,
+                new: new.restaked_amount,
+            });
+        }
+        
+        changes.extend(new.quorum_ids.iter()
+            .filter(|id| !old.quorum_ids.contains(id))
+            .map(|id| OperatorChange::QuorumJoined(*id)));
+        changes.extend(old.quorum_ids.iter()
+            .filter(|id| !new.quorum_ids.contains(id))
+            .map(|id| OperatorChange::QuorumLeft(*id)));
+        
+        if old.status != new.status {
+            changes.push(OperatorChange::Status {
+                old: old.status.clone(),
+                new: new.status.clone(),
+            });
+        }
+        
+        changes
+    }
+    
+    /// Signed difference between an operator's restaked amount and a quorum's minimum stake
+    pub fn stake_margin(&self, operator_address: &[u8], quorum_id: u8) -> Result<i128> {
+        let operator = self.get_operator(operator_address)?;
+        let quorum = self.get_quorum(quorum_id)?;
+        Ok(Self::margin(operator.restake_info.restaked_amount, quorum.min_stake))
+    }
+    
+    fn margin(stake: u128, min_stake: u128) -> i128 {
+        let to_i128 = |v: u128| i128::try_from(v).unwrap_or(i128::MAX);
+        to_i128(stake) - to_i128(min_stake)
+    }
+    
+    /// Warn about each quorum in `quorums` where the operator's margin newly fell below the buffer
+    fn check_stake_margins(&self, quorums: &HashMap<u8, QuorumInfo>, info: &OperatorInfo, previous_stake: Option<u128>) {
+        let buffer = match self.stake_margin_buffer {
+            Some(buffer) => i128::try_from(buffer).unwrap_or(i128::MAX),
+            None => return,
+        };
+        
+        for quorum_id in &info.restake_info.quorum_ids {
+            let quorum = match quorums.get(quorum_id) {
+                Some(quorum) => quorum,
+                None => continue,
+            };
+            
+            let margin = Self::margin(info.restake_info.restaked_amount, quorum.min_stake);
+            let was_low = previous_stake.map_or(false, |stake| Self::margin(stake, quorum.min_stake) < buffer);
+            if margin < buffer && !was_low {
+                warn!(
+                    operator = hex::encode(&info.restake_info.operator_address),
+                    quorum_id = *quorum_id,
+                    margin = margin,
+                    buffer = buffer;
+                    "Operator stake is close to the quorum minimum"
+                );
+                let _ = self.events.send(OperatorEvent::StakeMarginLow {
+                    operator_address: info.restake_info.operator_address.clone(),
+                    quorum_id: *quorum_id,
+                    margin,
+                });
+            }
+        }
+    }
+    
+    /// Onboarding status given to an operator seen for the first time
+    fn initial_onboarding_status(&self) -> OperatorStatus {
+        if self.min_validations_to_activate > 0 {
+            OperatorStatus::Pending
+        } else {
+            OperatorStatus::Active
+        }
+    }
+    
+    /// Insert fresh restaking information into the cache, preserving existing stats
+    fn cache_operator(&self, cache: &mut HashMap<Vec<u8>, OperatorInfo>, op_info: RestakeInfo) -> OperatorInfo {
+        let operator_id = op_info.operator_address.clone();
+        
+        let info = match cache.get(&operator_id) {
+            // If we already have this operator, update it while preserving stats
+            Some(existing) => OperatorInfo {
+                restake_info: op_info,
+                last_updated: Self::current_time(),
+                fetched_at: Self::current_time(),
+                reliability_score: existing.reliability_score,
+                blocks_validated: existing.blocks_validated,
+                slashes: existing.slashes,
+                slash_history: existing.slash_history.clone(),
+                onboarding_status: existing.onboarding_status.clone(),
+            },
+            // New operator
+            None => OperatorInfo {
+                restake_info: op_info,
+                last_updated: Self::current_time(),
+                fetched_at: Self::current_time(),
+                reliability_score: 100, // Start with perfect score
+                blocks_validated: 0,
+                slashes: 0,
+                slash_history: Vec::new(),
+                // Discovered operators serve the same probation as registered ones
+                onboarding_status: self.initial_onboarding_status(),
+            },
+        };
+        
+        cache.insert(operator_id, info.clone());
+        info
+    }
+    
     /// Update the cached quorum information
     pub fn refresh_quorums(&self) -> Result<()> {
         if !self.needs_refresh() {
@@ -153,17 +373,94 @@ impl OperatorManager {
         // Update the cache
         let mut cache = self.quorums.write().unwrap();
         for quorum_info in quorums {
+            if let Some((min_operators, min_total_stake)) = self.quorum_health_thresholds {
+                // Only report the transition into an unhealthy state, not every refresh
+                let was_healthy = cache
+                    .get(&quorum_info.quorum_id)
+                    .map_or(true, |old| Self::meets_thresholds(old, min_operators, min_total_stake));
+                if was_healthy && !Self::meets_thresholds(&quorum_info, min_operators, min_total_stake) {
+                    warn!(
+                        quorum_id = quorum_info.quorum_id,
+                        operator_count = quorum_info.operator_count,
+                        total_stake = quorum_info.total_stake,
+                        min_operators = min_operators,
+                        min_total_stake = min_total_stake;
+                        "Quorum is unhealthy"
+                    );
+                    let _ = self.events.send(OperatorEvent::QuorumUnhealthy {
+                        quorum_id: quorum_info.quorum_id,
+                        operator_count: quorum_info.operator_count,
+                        total_stake: quorum_info.total_stake,
+                    });
+                }
+            }
+            
+            if let Some(old) = cache.get(&quorum_info.quorum_id) {
+                if self.is_material_change(old, &quorum_info) {
+                    info!(
+                        quorum_id = quorum_info.quorum_id,
+                        old_stake = old.total_stake,
+                        new_stake = quorum_info.total_stake,
+                        old_operator_count = old.operator_count,
+                        new_operator_count = quorum_info.operator_count;
+                        "Quorum changed"
+                    );
+                    // No subscribers is not an error
+                    let _ = self.events.send(OperatorEvent::QuorumChanged {
+                        quorum_id: quorum_info.quorum_id,
+                        old_stake: old.total_stake,
+                        new_stake: quorum_info.total_stake,
+                    });
+                }
+            }
             cache.insert(quorum_info.quorum_id, quorum_info);
         }
         
-        info!("Cached {} quorums", cache.len());
+        info!(quorum_count = cache.len(); "Cached quorums");
         
         Ok(())
     }
     
+    /// Whether a quorum has enough operators and stake to be trusted; unknown quorums are unhealthy
+    pub fn is_quorum_healthy(&self, quorum_id: u8, min_operators: u32, min_total_stake: u128) -> bool {
+        match self.get_quorum(quorum_id) {
+            Ok(quorum) => Self::meets_thresholds(&quorum, min_operators, min_total_stake),
+            Err(e) => {
+                debug!("Treating quorum {} as unhealthy: {:?}", quorum_id, e);
+                false
+            }
+        }
+    }
+    
+    fn meets_thresholds(quorum: &QuorumInfo, min_operators: u32, min_total_stake: u128) -> bool {
+        quorum.operator_count >= min_operators && quorum.total_stake >= min_total_stake
+    }
+    
+    /// Whether the stake or operator count moved by at least the change threshold
+    fn is_material_change(&self, old: &QuorumInfo, new: &QuorumInfo) -> bool {
+        let exceeds = |old: u128, new: u128| {
+            let delta = old.abs_diff(new);
+            delta > 0 && delta.saturating_mul(100) >= old.saturating_mul(self.quorum_change_threshold as u128)
+        };
+        
+        exceeds(old.total_stake, new.total_stake)
+            || exceeds(old.operator_count as u128, new.operator_count as u128)
+    }
+    
     /// Get information about a specific operator
     pub fn get_operator(&self, operator_address: &[u8]) -> Result<OperatorInfo> {
-        // Refresh if needed
+        // A fresh cached entry needs no refresh. Recording stats bumps `last_updated`
+        // but not `fetched_at`, so only a fetch keeps the entry fresh
+        {
+            let cache = self.operators.read().unwrap();
+            if let Some(info) = cache.get(operator_address) {
+                if Self::current_time().saturating_sub(info.fetched_at) <= self.max_cache_age {
+                    return Ok(info.clone());
+                }
+            }
+        }
+        
+        // Missing or stale: refresh if needed
         self.refresh_operators()?;
         
         // Try to get from cache first
@@ -177,20 +474,15 @@ impl OperatorManager {
         // Not in cache, try to get directly
         let op_info = self.client.get_operator_info(operator_address)?;
         
-        // Create and cache a new operator info
-        let info = OperatorInfo {
-            restake_info: op_info,
-            last_updated: Self::current_time(),
-            reliability_score: 100, // Start with perfect score
-            blocks_validated: 0,
-            slashes: 0,
-        };
-        
-        // Update cache
+        // Another caller may have cached the operator and recorded stats while the
+        // lock was released, so merge rather than overwrite
         let mut cache = self.operators.write().unwrap();
-        cache.insert(operator_address.to_vec(), info.clone());
-        
-        Ok(info)
+        Ok(self.cache_operator(&mut cache, op_info))
+    }
+    
+    /// Get the recorded slashes of an operator as (timestamp, severity), oldest first
+    pub fn get_slash_history(&self, operator_address: &[u8]) -> Result<Vec<(u64, u8)>> {
+        Ok(self.get_operator(operator_address)?.slash_history)
     }
     
     /// Get all operators
@@ -198,9 +490,10 @@ impl OperatorManager {
         // Refresh if needed
         self.refresh_operators()?;
         
-        // Get from cache
+        // Get from cache, ordered by address so responses are stable across calls
         let cache = self.operators.read().unwrap();
-        let operators: Vec<OperatorInfo> = cache.values().cloned().collect();
+        let mut operators: Vec<OperatorInfo> = cache.values().cloned().collect();
+        operators.sort_by(|a, b| a.restake_info.operator_address.cmp(&b.restake_info.operator_address));
         
         Ok(operators)
     }
@@ -213,13 +506,58 @@ impl OperatorManager {
         let active_ops = all_ops
             .into_iter()
             .filter(|op| op.restake_info.status == OperatorStatus::Active)
+            .filter(|op| op.onboarding_status == OperatorStatus::Active)
+            .collect();
+            
+        Ok(active_ops)
+    }
+    
+    /// Get active operators with at least `min_score` reliability
+    ///
+    /// Yields an empty list, not an error, when no operators are known.
+    pub fn get_reliable_operators(&self, min_score: u8) -> Result<Vec<OperatorInfo>> {
+        let active_ops = self.get_active_operators()?;
+        
+        let reliable_ops = active_ops
+            .into_iter()
+            .filter(|op| op.reliability_score >= min_score)
             .collect();
             
+        Ok(reliable_ops)
+    }
+    
+    /// Get up to `n` active operators, most reliable first
+    ///
+    /// Returns `min(n, active operators)` entries, so an empty operator set yields an empty list.
+    pub fn get_best_operators(&self, n: usize) -> Result<Vec<OperatorInfo>> {
+        let mut active_ops = self.get_active_operators()?;
+        
+        active_ops.sort_by(|a, b| b.reliability_score.cmp(&a.reliability_score));
+        active_ops.truncate(n);
+        
         Ok(active_ops)
     }
     
     /// Get operators for a specific quorum
+    ///
+    /// When the cache is cold only this quorum's operators are fetched from the
+    /// client, instead of pulling every operator and filtering in memory.
     pub fn get_operators_in_quorum(&self, quorum_id: u8) -> Result<Vec<OperatorInfo>> {
+        if self.needs_refresh() {
+            match self.client.get_operators_in_quorum(quorum_id) {
+                Ok(operators) => {
+                    let mut cache = self.operators.write().unwrap();
+                    let quorum_ops = operators
+                        .into_iter()
+                        .map(|op_info| self.cache_operator(&mut cache, op_info))
+                        .collect();
+                    
+                    return Ok(quorum_ops);
+                }
+                Err(e) => warn!("Direct quorum {} operator query failed, falling back to full refresh: {:?}", quorum_id, e),
+            }
+        }
+        
         let all_ops = self.get_all_operators()?;
         
         // Filter to operators in this quorum
@@ -259,13 +597,19 @@ impl OperatorManager {
         // Refresh if needed
         self.refresh_quorums()?;
         
-        // Get from cache
+        // Get from cache, ordered by quorum ID so responses are stable across calls
         let cache = self.quorums.read().unwrap();
-        let quorums: Vec<QuorumInfo> = cache.values().cloned().collect();
+        let mut quorums: Vec<QuorumInfo> = cache.values().cloned().collect();
+        quorums.sort_by_key(|quorum| quorum.quorum_id);
         
         Ok(quorums)
     }
     
+    /// Estimate the fee (in wei) of registering an operator at the configured gas price
+    pub fn estimate_registration_fee(&self, operator_address: &[u8], bls_public_key: &[u8]) -> Result<U256> {
+        self.client.estimate_register_operator_fee(operator_address, bls_public_key)
+    }
+    
     /// Register a new operator
     pub fn register_operator(&self, 
                             operator_address: &[u8], 
@@ -278,24 +622,91 @@ impl OperatorManager {
         *self.last_refresh.lock().unwrap() = 0;
         self.refresh_operators()?;
         
+        // Re-registered operators restart probation, like newly discovered ones
+        let status = self.initial_onboarding_status();
+        self.with_operator_mut(operator_address, |info| {
+            info.onboarding_status = status;
+        });
+        
+        Ok(())
+    }
+    
+    /// Deregister an operator from its quorums and drop it from the cache
+    pub fn deregister_operator(&self, operator_address: &[u8]) -> Result<()> {
+        self.client.deregister_operator(operator_address)?;
+        
+        self.operators.write().unwrap().remove(operator_address);
+        
         Ok(())
     }
     
+    /// Promote a pending operator to active once it has enough successful validations.
+    /// Returns whether the operator was promoted.
+    pub fn promote_if_eligible(&self, operator_address: &[u8]) -> bool {
+        let min_validations = self.min_validations_to_activate;
+        self.with_operator_mut(operator_address, |info| {
+            let eligible = info.onboarding_status == OperatorStatus::Pending
+                && info.blocks_validated >= min_validations;
+            if eligible {
+                info.onboarding_status = OperatorStatus::Active;
+            }
+            eligible
+        })
+        .unwrap_or(false)
+    }
+    
+    /// Seconds elapsed since the operator cache was last refreshed
+    pub fn last_refresh_age(&self) -> u64 {
+        let last = *self.last_refresh.lock().unwrap();
+        Self::current_time().saturating_sub(last)
+    }
+    
+    /// Render cached operator metrics in the Prometheus text exposition format
+    ///
+    /// Only reads the cache, so scraping never triggers a refresh.
+    pub fn export_metrics(&self) -> String {
+        let cache = self.operators.read().unwrap();
+        
+        let operator_count = cache.len();
+        let total_stake: u128 = cache.values().map(|op| op.restake_info.restaked_amount).sum();
+        let total_slashes: u64 = cache.values().map(|op| op.slashes as u64).sum();
+        let average_reliability = if operator_count == 0 {
+            0.0
+        } else {
+            cache.values().map(|op| op.reliability_score as f64).sum::<f64>() / operator_count as f64
+        };
+        
+        let metrics = [
+            ("elxr_eigenlayer_operators", "gauge", "Number of cached operators", operator_count.to_string()),
+            ("elxr_eigenlayer_total_stake_wei", "gauge", "Total restaked amount across operators in wei", total_stake.to_string()),
+            ("elxr_eigenlayer_average_reliability", "gauge", "Average operator reliability score (0-100)", format!("{:.2}", average_reliability)),
+            ("elxr_eigenlayer_slashes_total", "counter", "Total slashes recorded across operators", total_slashes.to_string()),
+            ("elxr_eigenlayer_last_refresh_age_seconds", "gauge", "Seconds since the operator cache was refreshed", self.last_refresh_age().to_string()),
+        ];
+        
+        let mut output = String::new();
+        for (name, kind, help, value) in metrics.iter() {
+            output.push_str(&format!("# HELP {} {}\n# TYPE {} {}\n{} {}\n", name, help, name, kind, name, value));
+        }
+        
+        output
+    }
+    
     /// Record a successful block validation by an operator
     pub fn record_successful_validation(&self, operator_address: &[u8]) -> Result<()> {
-        let mut cache = self.operators.write().unwrap();
-        
-        if let Some(mut info) = cache.get_mut(operator_address) {
+        let updated = self.with_operator_mut(operator_address, |info| {
             info.blocks_validated += 1;
             
             // Increase reliability score if it's not already perfect
             if info.reliability_score < 100 {
                 info.reliability_score = (info.reliability_score + 1).min(100);
             }
-            
-            info.last_updated = Self::current_time();
-        } else {
+        });
+        
+        if updated.is_none() {
             warn!("Tried to record validation for unknown operator: {:?}", operator_address);
+        } else if self.promote_if_eligible(operator_address) {
+            info!("Operator {:?} completed onboarding and is now active", operator_address);
         }
         
         Ok(())
@@ -303,20 +714,551 @@ impl OperatorManager {
     
     /// Record a slash event for an operator
     pub fn record_slash(&self, operator_address: &[u8], severity: u8) -> Result<()> {
-        let mut cache = self.operators.write().unwrap();
-        
-        if let Some(mut info) = cache.get_mut(operator_address) {
+        let operator = hex::encode(operator_address);
+        let updated = self.with_operator_mut(operator_address, |info| {
             info.slashes += 1;
             
             // Decrease reliability score based on severity (1-100)
             let decrease = severity.min(100);
             info.reliability_score = info.reliability_score.saturating_sub(decrease);
             
-            info.last_updated = Self::current_time();
-        } else {
-            warn!("Tried to record slash for unknown operator: {:?}", operator_address);
+            // Keep a bounded audit trail, dropping the oldest entries
+            info.slash_history.push((Self::current_time(), severity));
+            if info.slash_history.len() > MAX_SLASH_HISTORY {
+                let excess = info.slash_history.len() - MAX_SLASH_HISTORY;
+                info.slash_history.drain(..excess);
+            }
+            
+            (info.slashes, info.reliability_score)
+        });
+        
+        match updated {
+            Some((slashes, reliability_score)) => warn!(
+                operator = operator,
+                severity = severity,
+                slashes = slashes,
+                reliability_score = reliability_score;
+                "Recorded operator slash"
+            ),
+            None => warn!(operator = operator, severity = severity; "Tried to record slash for unknown operator"),
         }
         
         Ok(())
     }
+    
+    /// Apply `f` to a cached operator while holding the write lock for the whole
+    /// read-modify-write, so concurrent updates are never lost. Returns `None` if
+    /// the operator is not cached.
+    pub fn with_operator_mut<R>(
+        &self,
+        operator_address: &[u8],
+        f: impl FnOnce(&mut OperatorInfo) -> R,
+    ) -> Option<R> {
+        let mut cache = self.operators.write().unwrap();
+        let info = cache.get_mut(operator_address)?;
+        let result = f(info);
+        info.last_updated = Self::current_time();
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eigenlayer::backend::MockEigenBackend;
+    
+    fn restake_info(address: u8, restaked_amount: u128, quorum_ids: Vec<u8>) -> RestakeInfo {
+        RestakeInfo {
+            operator_address: vec![address; 20],
+            restaked_amount,
+            strategy_shares: Vec::new(),
+            public_key: vec![address; 32],
+            quorum_ids,
+            status: OperatorStatus::Active,
+        }
+    }
+    
+    #[test]
+    fn cold_cache_quorum_lookup_queries_only_that_quorum() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        backend.set_operator(restake_info(2, 2_000, vec![0, 1]));
+        backend.set_operator(restake_info(3, 3_000, vec![1]));
+        let manager = OperatorManager::new(backend.clone()).unwrap();
+        
+        let mut quorum_ops: Vec<Vec<u8>> = manager.get_operators_in_quorum(0).unwrap()
+            .into_iter()
+            .map(|op| op.restake_info.operator_address)
+            .collect();
+        quorum_ops.sort();
+        
+        assert_eq!(quorum_ops, vec![vec![1; 20], vec![2; 20]]);
+        assert_eq!(backend.get_all_operators_calls(), 0);
+    }
+    
+    fn quorum_info(quorum_id: u8, min_stake: u128) -> QuorumInfo {
+        QuorumInfo { quorum_id, operator_count: 1, total_stake: 1_000, min_stake }
+    }
+    
+    #[test]
+    fn refresh_warns_operators_close_to_cached_quorum_minimum() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_quorum(quorum_info(0, 900));
+        backend.set_quorum(quorum_info(1, 100));
+        backend.set_operator(restake_info(1, 1_000, vec![0, 1]));
+        let mut manager = OperatorManager::new(backend).unwrap();
+        manager.set_stake_margin_buffer(200);
+        let mut events = manager.event_sender().subscribe();
+        
+        manager.get_all_quorums().unwrap();
+        manager.refresh_operators().unwrap();
+        
+        assert_eq!(events.try_recv().unwrap(), OperatorEvent::StakeMarginLow {
+            operator_address: vec![1; 20],
+            quorum_id: 0,
+            margin: 100,
+        });
+        assert!(events.try_recv().is_err());
+    }
+    
+    #[test]
+    fn discovered_operators_stay_pending_until_probation_ends() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        let mut manager = OperatorManager::new(backend).unwrap();
+        manager.set_min_validations_to_activate(3);
+        
+        let discovered = manager.get_operator(&[1; 20]).unwrap();
+        assert_eq!(discovered.onboarding_status, OperatorStatus::Pending);
+        assert!(manager.get_active_operators().unwrap().is_empty());
+        
+        for _ in 0..2 {
+            manager.record_successful_validation(&[1; 20]).unwrap();
+        }
+        assert!(manager.get_active_operators().unwrap().is_empty());
+        
+        manager.record_successful_validation(&[1; 20]).unwrap();
+        let active = manager.get_active_operators().unwrap();
+        assert_eq!(active.len(), 1);
+        assert_eq!(active[0].onboarding_status, OperatorStatus::Active);
+    }
+    
+    #[test]
+    fn discovered_operators_are_active_without_probation() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        let mut manager = OperatorManager::new(backend).unwrap();
+        manager.set_min_validations_to_activate(0);
+        
+        assert_eq!(manager.get_active_operators().unwrap().len(), 1);
+    }
+    
+    #[test]
+    fn concurrent_updates_to_one_operator_are_not_lost() {
+        const THREADS: usize = 8;
+        const UPDATES: usize = 250;
+        
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        let manager = OperatorManager::new(backend).unwrap();
+        manager.get_operator(&[1; 20]).unwrap();
+        
+        // Validations and slashes race on the same entry; a read-then-write
+        // update would drop some of them
+        std::thread::scope(|scope| {
+            for thread in 0..THREADS {
+                let manager = &manager;
+                scope.spawn(move || {
+                    for _ in 0..UPDATES {
+                        if thread % 2 == 0 {
+                            manager.record_successful_validation(&[1; 20]).unwrap();
+                        } else {
+                            manager.record_slash(&[1; 20], 0).unwrap();
+                            manager.with_operator_mut(&[1; 20], |info| info.blocks_validated += 1).unwrap();
+                        }
+                    }
+                });
+            }
+        });
+        
+        let operator = manager.get_operator(&[1; 20]).unwrap();
+        assert_eq!(operator.blocks_validated, (THREADS * UPDATES) as u64);
+        assert_eq!(operator.slashes as usize, THREADS / 2 * UPDATES);
+    }
+    
+    #[test]
+    fn metrics_report_cached_operators_in_prometheus_format() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        backend.set_operator(restake_info(2, 2_500, vec![0]));
+        let manager = OperatorManager::new(backend).unwrap();
+        manager.refresh_operators().unwrap();
+        manager.record_slash(&[2; 20], 10).unwrap();
+        
+        let metrics = manager.export_metrics();
+        
+        for name in [
+            "elxr_eigenlayer_operators",
+            "elxr_eigenlayer_total_stake_wei",
+            "elxr_eigenlayer_average_reliability",
+            "elxr_eigenlayer_slashes_total",
+            "elxr_eigenlayer_last_refresh_age_seconds",
+        ] {
+            assert!(metrics.contains(&format!("# TYPE {} ", name)), "missing {}:\n{}", name, metrics);
+        }
+        let sample = |name: &str| -> String {
+            metrics.lines()
+                .find_map(|line| line.strip_prefix(&format!("{} ", name)))
+                .unwrap_or_else(|| panic!("no sample for {}", name))
+                .to_string()
+        };
+        assert_eq!(sample("elxr_eigenlayer_operators").parse::<usize>().unwrap(), 2);
+        assert_eq!(sample("elxr_eigenlayer_total_stake_wei"), "3500");
+        assert_eq!(sample("elxr_eigenlayer_slashes_total"), "1");
+    }
+    
+    #[test]
+    fn empty_backend_yields_empty_selections() {
+        let manager = OperatorManager::new(Arc::new(MockEigenBackend::new())).unwrap();
+        
+        assert!(manager.get_all_operators().unwrap().is_empty());
+        assert!(manager.get_all_quorums().unwrap().is_empty());
+        assert!(manager.get_operators_in_quorum(0).unwrap().is_empty());
+        assert!(manager.get_reliable_operators(0).unwrap().is_empty());
+        assert!(manager.get_best_operators(5).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn best_operators_are_capped_at_the_operator_count() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        backend.set_operator(restake_info(2, 1_000, vec![0]));
+        let mut manager = OperatorManager::new(backend).unwrap();
+        manager.set_min_validations_to_activate(0);
+        manager.refresh_operators().unwrap();
+        manager.with_operator_mut(&[1; 20], |info| info.reliability_score = 40).unwrap();
+        manager.with_operator_mut(&[2; 20], |info| info.reliability_score = 90).unwrap();
+        
+        let best: Vec<_> = manager.get_best_operators(5).unwrap()
+            .into_iter()
+            .map(|op| op.restake_info.operator_address)
+            .collect();
+        assert_eq!(best, vec![vec![2; 20], vec![1; 20]]);
+        assert_eq!(manager.get_best_operators(1).unwrap().len(), 1);
+        assert!(manager.get_best_operators(0).unwrap().is_empty());
+        assert!(manager.get_reliable_operators(95).unwrap().is_empty());
+    }
+    
+    #[test]
+    fn operators_and_quorums_are_listed_in_a_stable_order() {
+        let backend = Arc::new(MockEigenBackend::new());
+        for id in [7, 3, 9, 1, 5] {
+            backend.set_operator(restake_info(id, 1_000, vec![id]));
+            backend.set_quorum(quorum_info(id, 100));
+        }
+        let manager = OperatorManager::new(backend).unwrap();
+        let operator_addresses = || -> Vec<Vec<u8>> {
+            manager.get_all_operators().unwrap().into_iter().map(|op| op.restake_info.operator_address).collect()
+        };
+        let quorum_ids = || -> Vec<u8> {
+            manager.get_all_quorums().unwrap().into_iter().map(|quorum| quorum.quorum_id).collect()
+        };
+        
+        let first = operator_addresses();
+        assert_eq!(first, [1, 3, 5, 7, 9].iter().map(|id| vec![*id; 20]).collect::<Vec<_>>());
+        assert_eq!(operator_addresses(), first);
+        
+        assert_eq!(quorum_ids(), vec![1, 3, 5, 7, 9]);
+        assert_eq!(quorum_ids(), vec![1, 3, 5, 7, 9]);
+    }
+    
+    /// Let the next refresh hit the backend regardless of cache age
+    fn expire_cache<B: EigenBackend>(manager: &OperatorManager<B>) {
+        *manager.last_refresh.lock().unwrap() = 0;
+    }
+    
+    fn quorum_with_stake(quorum_id: u8, operator_count: u32, total_stake: u128) -> QuorumInfo {
+        QuorumInfo { quorum_id, operator_count, total_stake, min_stake: 0 }
+    }
+    
+    #[test]
+    fn material_quorum_stake_drop_is_broadcast() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_quorum(quorum_with_stake(0, 4, 1_000));
+        backend.set_quorum(quorum_with_stake(1, 4, 1_000));
+        let mut manager = OperatorManager::new(backend.clone()).unwrap();
+        manager.set_quorum_change_threshold(10);
+        let mut events = manager.event_sender().subscribe();
+        manager.refresh_quorums().unwrap();
+        
+        // A large operator leaves quorum 0; quorum 1 drifts below the threshold
+        backend.set_quorum(quorum_with_stake(0, 3, 800));
+        backend.set_quorum(quorum_with_stake(1, 4, 950));
+        expire_cache(&manager);
+        manager.refresh_quorums().unwrap();
+        
+        assert_eq!(events.try_recv().unwrap(), OperatorEvent::QuorumChanged {
+            quorum_id: 0,
+            old_stake: 1_000,
+            new_stake: 800,
+        });
+        assert!(events.try_recv().is_err());
+    }
+    
+    #[test]
+    fn operator_count_change_alone_is_material() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_quorum(quorum_with_stake(0, 10, 1_000));
+        let manager = OperatorManager::new(backend.clone()).unwrap();
+        let mut events = manager.event_sender().subscribe();
+        manager.refresh_quorums().unwrap();
+        
+        backend.set_quorum(quorum_with_stake(0, 5, 1_000));
+        expire_cache(&manager);
+        manager.refresh_quorums().unwrap();
+        
+        assert_eq!(events.try_recv().unwrap(), OperatorEvent::QuorumChanged {
+            quorum_id: 0,
+            old_stake: 1_000,
+            new_stake: 1_000,
+        });
+    }
+    
+    #[test]
+    fn slash_history_keeps_only_the_latest_entries() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        let manager = OperatorManager::new(backend).unwrap();
+        manager.refresh_operators().unwrap();
+        
+        for severity in 0..(MAX_SLASH_HISTORY + 5) as u8 {
+            manager.record_slash(&[1; 20], severity).unwrap();
+        }
+        
+        let operator = manager.get_operator(&[1; 20]).unwrap();
+        let severities: Vec<u8> = operator.slash_history.iter().map(|(_, severity)| *severity).collect();
+        assert_eq!(severities, (5..(MAX_SLASH_HISTORY + 5) as u8).collect::<Vec<_>>());
+        assert_eq!(operator.slashes as usize, MAX_SLASH_HISTORY + 5);
+    }
+    
+    #[test]
+    fn recording_stats_does_not_keep_a_stale_entry_fresh() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        let manager = OperatorManager::new(backend.clone()).unwrap();
+        manager.refresh_operators().unwrap();
+        manager.operators.write().unwrap().get_mut(&vec![1; 20]).unwrap().fetched_at = 0;
+        
+        manager.record_successful_validation(&[1; 20]).unwrap();
+        manager.record_slash(&[1; 20], 1).unwrap();
+        
+        backend.set_operator(restake_info(1, 2_000, vec![0]));
+        expire_cache(&manager);
+        let operator = manager.get_operator(&[1; 20]).unwrap();
+        assert_eq!(operator.restake_info.restaked_amount, 2_000);
+        assert_eq!(operator.blocks_validated, 1);
+        assert_eq!(operator.slashes, 1);
+    }
+    
+    #[test]
+    fn deregistration_calls_the_backend_and_drops_the_cache_entry() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0, 1]));
+        let manager = OperatorManager::new(backend.clone()).unwrap();
+        manager.refresh_operators().unwrap();
+        
+        manager.deregister_operator(&[1; 20]).unwrap();
+        
+        let deregistered = backend.get_operator_info(&[1; 20]).unwrap();
+        assert!(deregistered.quorum_ids.is_empty());
+        assert_eq!(deregistered.status, OperatorStatus::Removed);
+        assert!(!manager.operators.read().unwrap().contains_key(&vec![1; 20]));
+    }
+    
+    #[test]
+    fn failed_deregistration_keeps_the_cache_entry() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, Vec::new()));
+        let manager = OperatorManager::new(backend).unwrap();
+        manager.refresh_operators().unwrap();
+        
+        assert!(manager.deregister_operator(&[1; 20]).is_err());
+        
+        assert!(manager.operators.read().unwrap().contains_key(&vec![1; 20]));
+    }
+    
+    #[test]
+    fn stake_margin_is_signed_difference_to_quorum_minimum() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_quorum(quorum_info(0, 600));
+        backend.set_quorum(quorum_info(1, 1_500));
+        backend.set_operator(restake_info(1, 1_000, vec![0, 1]));
+        let manager = OperatorManager::new(backend).unwrap();
+        
+        assert_eq!(manager.stake_margin(&[1; 20], 0).unwrap(), 400);
+        assert_eq!(manager.stake_margin(&[1; 20], 1).unwrap(), -500);
+        assert!(manager.stake_margin(&[2; 20], 0).is_err());
+    }
+    
+    #[test]
+    fn low_margin_alert_fires_once_until_stake_recovers() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_quorum(quorum_info(0, 900));
+        backend.set_operator(restake_info(1, 2_000, vec![0]));
+        let mut manager = OperatorManager::new(backend.clone()).unwrap();
+        manager.set_stake_margin_buffer(200);
+        let mut events = manager.event_sender().subscribe();
+        manager.get_all_quorums().unwrap();
+        manager.refresh_operators().unwrap();
+        assert!(events.try_recv().is_err());
+        
+        // Falling below the quorum minimum is a (negative) low margin too
+        backend.set_operator(restake_info(1, 800, vec![0]));
+        expire_cache(&manager);
+        manager.refresh_operators().unwrap();
+        assert_eq!(events.try_recv().unwrap(), OperatorEvent::StakeMarginLow {
+            operator_address: vec![1; 20],
+            quorum_id: 0,
+            margin: -100,
+        });
+        
+        // Still low: no repeat alert
+        expire_cache(&manager);
+        manager.refresh_operators().unwrap();
+        assert!(events.try_recv().is_err());
+        
+        // Recovering re-arms the alert
+        backend.set_operator(restake_info(1, 2_000, vec![0]));
+        expire_cache(&manager);
+        manager.refresh_operators().unwrap();
+        backend.set_operator(restake_info(1, 950, vec![0]));
+        expire_cache(&manager);
+        manager.refresh_operators().unwrap();
+        assert_eq!(events.try_recv().unwrap(), OperatorEvent::StakeMarginLow {
+            operator_address: vec![1; 20],
+            quorum_id: 0,
+            margin: 50,
+        });
+        assert!(events.try_recv().is_err());
+    }
+    
+    #[test]
+    fn refresh_publishes_what_changed_per_operator() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(1, 1_000, vec![0]));
+        backend.set_operator(restake_info(2, 2_000, vec![0]));
+        let manager = OperatorManager::new(backend.clone()).unwrap();
+        let mut events = manager.event_sender().subscribe();
+        
+        // Discovery is not a change
+        manager.refresh_operators().unwrap();
+        assert!(events.try_recv().is_err());
+        
+        backend.set_operator(restake_info(1, 1_500, vec![0]));
+        backend.set_operator(restake_info(2, 2_000, vec![1]));
+        expire_cache(&manager);
+        manager.refresh_operators().unwrap();
+        
+        let mut updates = vec![events.try_recv().unwrap(), events.try_recv().unwrap()];
+        updates.sort_by_key(|event| match event {
+            OperatorEvent::OperatorUpdated { operator_address, .. } => operator_address.clone(),
+            other => panic!("unexpected event {:?}", other),
+        });
+        assert_eq!(updates, vec![
+            OperatorEvent::OperatorUpdated {
+                operator_address: vec![1; 20],
+                changes: vec![OperatorChange::RestakedAmount { old: 1_000, new: 1_500 }],
+            },
+            OperatorEvent::OperatorUpdated {
+                operator_address: vec![2; 20],
+                changes: vec![OperatorChange::QuorumJoined(1), OperatorChange::QuorumLeft(0)],
+            },
+        ]);
+        
+        // Nothing changed since
+        expire_cache(&manager);
+        manager.refresh_operators().unwrap();
+        assert!(events.try_recv().is_err());
+    }
+    
+    #[test]
+    fn status_change_is_diffed() {
+        let old = restake_info(1, 1_000, vec![0]);
+        let new = RestakeInfo { status: OperatorStatus::Removed, ..old.clone() };
+        
+        assert_eq!(
+            OperatorManager::<MockEigenBackend>::diff_restake_info(&old, &new),
+            vec![OperatorChange::Status { old: OperatorStatus::Active, new: OperatorStatus::Removed }]
+        );
+        assert!(OperatorManager::<MockEigenBackend>::diff_restake_info(&old, &old).is_empty());
+    }
+    
+    /// Log records captured by `CapturingLogger`, as (message, key-value fields)
+    static CAPTURED_LOGS: Mutex<Vec<(String, Vec<(String, String)>)>> = Mutex::new(Vec::new());
+    
+    struct CapturingLogger;
+    
+    impl log::Log for CapturingLogger {
+        fn enabled(&self, _metadata: &log::Metadata) -> bool {
+            true
+        }
+        
+        fn log(&self, record: &log::Record) {
+            struct Fields(Vec<(String, String)>);
+            
+            impl<'kvs> log::kv::VisitSource<'kvs> for Fields {
+                fn visit_pair(&mut self, key: log::kv::Key<'kvs>, value: log::kv::Value<'kvs>) -> Result<(), log::kv::Error> {
+                    self.0.push((key.to_string(), value.to_string()));
+                    Ok(())
+                }
+            }
+            
+            let mut fields = Fields(Vec::new());
+            let _ = record.key_values().visit(&mut fields);
+            CAPTURED_LOGS.lock().unwrap().push((record.args().to_string(), fields.0));
+        }
+        
+        fn flush(&self) {}
+    }
+    
+    /// Route every log record into `CAPTURED_LOGS`; the logger is process-wide, so
+    /// tests must pick out their own records
+    fn capture_logs() {
+        static INSTALL: std::sync::Once = std::sync::Once::new();
+        INSTALL.call_once(|| {
+            log::set_logger(&CapturingLogger).unwrap();
+            log::set_max_level(log::LevelFilter::Trace);
+        });
+    }
+    
+    /// Fields of the captured records with `message` whose `operator` field is `operator`
+    fn captured_fields(message: &str, operator: &str) -> Vec<Vec<(String, String)>> {
+        CAPTURED_LOGS.lock().unwrap().iter()
+            .filter(|(msg, fields)| msg == message && fields.iter().any(|(k, v)| k == "operator" && v == operator))
+            .map(|(_, fields)| fields.clone())
+            .collect()
+    }
+    
+    #[test]
+    fn slash_is_logged_with_structured_fields() {
+        capture_logs();
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(restake_info(0xAB, 1_000, vec![0]));
+        let manager = OperatorManager::new(backend).unwrap();
+        manager.refresh_operators().unwrap();
+        
+        manager.record_slash(&[0xAB; 20], 7).unwrap();
+        manager.record_slash(&[0xCD; 20], 9).unwrap();
+        
+        let recorded = captured_fields("Recorded operator slash", &hex::encode([0xAB; 20]));
+        assert_eq!(recorded.len(), 1);
+        let field = |name: &str| recorded[0].iter().find(|(k, _)| k == name).map(|(_, v)| v.clone());
+        assert_eq!(field("severity").as_deref(), Some("7"));
+        assert_eq!(field("slashes").as_deref(), Some("1"));
+        assert!(field("reliability_score").is_some());
+        
+        let unknown = captured_fields("Tried to record slash for unknown operator", &hex::encode([0xCD; 20]));
+        assert_eq!(unknown.len(), 1);
+        assert!(unknown[0].contains(&("severity".to_string(), "9".to_string())));
+    }
 }
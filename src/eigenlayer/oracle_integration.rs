@@ -3,15 +3,105 @@
 //! Connects the daemonless oracle with Eigenlayer components,
 //! enabling quantum-resistant security for staked assets.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
+use std::marker::PhantomData;
 use std::sync::{Arc, Mutex};
 
-use crate::pallet::oracle::error_correction;
-use crate::pallet::types::{ElixirAsset, VerificationStatus};
+use elixir_pallet::oracle::{error_correction, AssetId};
+use elixir_pallet::{OracleRuntimeApi, PriceQuote};
+use log::warn;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 
 // Import from Eigenlayer namespace
-use super::{config, operator};
-use shared::liquidity::types::{AssetId, PoolId};
+use super::types::AVSIdentifier;
+
+/// Which AVS secures each asset's price feed
+pub type AssetAvsMapping = HashMap<AssetId, AVSIdentifier>;
+
+/// NRSH asset identifier
+pub const NRSH_ASSET_ID: AssetId = AssetId(1);
+
+/// ELXR asset identifier
+pub const ELXR_ASSET_ID: AssetId = AssetId(2);
+
+/// Assets whose prices the service exposes
+pub const SUPPORTED_ASSETS: [AssetId; 2] = [NRSH_ASSET_ID, ELXR_ASSET_ID];
+
+/// Source of finalized oracle prices (with 6 decimals)
+pub trait PriceSource: Send + Sync {
+    /// Latest finalized price of an asset, if the oracle has one
+    fn price(&self, asset_id: AssetId) -> Option<u64>;
+}
+
+/// Prices finalized by the on-chain oracle, read through `OracleRuntimeApi` at the best block
+pub struct RuntimeApiPriceSource<Block, Client, Balance, BlockNumber> {
+    client: Arc<Client>,
+    _marker: PhantomData<fn() -> (Block, Balance, BlockNumber)>,
+}
+
+impl<Block, Client, Balance, BlockNumber> RuntimeApiPriceSource<Block, Client, Balance, BlockNumber> {
+    /// Read prices through `client`
+    pub fn new(client: Arc<Client>) -> Self {
+        Self { client, _marker: PhantomData }
+    }
+}
+
+impl<Block, Client, Balance, BlockNumber> PriceSource for RuntimeApiPriceSource<Block, Client, Balance, BlockNumber>
+where
+    Block: BlockT,
+    Client: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync,
+    Client::Api: OracleRuntimeApi<Block, Balance, BlockNumber>,
+    Balance: parity_scale_codec::Codec + TryInto<u64>,
+    BlockNumber: parity_scale_codec::Codec,
+{
+    fn price(&self, asset_id: AssetId) -> Option<u64> {
+        let at = BlockId::Hash(self.client.info().best_hash);
+        match self.client.runtime_api().prices(&at, vec![asset_id]) {
+            Ok(quotes) => quoted_price(asset_id, quotes),
+            Err(e) => {
+                warn!(asset_id = asset_id.0, error = format!("{:?}", e); "Oracle runtime API price query failed");
+                None
+            }
+        }
+    }
+}
+
+/// Price of `asset_id` among `quotes`, if quoted and representable as `u64`
+fn quoted_price<Balance: TryInto<u64>, BlockNumber>(
+    asset_id: AssetId,
+    quotes: Vec<(AssetId, Option<PriceQuote<Balance, BlockNumber>>)>,
+) -> Option<u64> {
+    quotes
+        .into_iter()
+        .find(|(id, _)| *id == asset_id)
+        .and_then(|(_, quote)| quote)
+        .and_then(|quote| quote.price.try_into().ok())
+}
+
+/// Fixed price table for tests
+#[cfg(test)]
+#[derive(Default, Clone)]
+pub struct StaticPriceSource {
+    prices: HashMap<AssetId, u64>,
+}
+
+#[cfg(test)]
+impl StaticPriceSource {
+    /// Set the price returned for an asset
+    pub fn with_price(mut self, asset_id: AssetId, price: u64) -> Self {
+        self.prices.insert(asset_id, price);
+        self
+    }
+}
+
+#[cfg(test)]
+impl PriceSource for StaticPriceSource {
+    fn price(&self, asset_id: AssetId) -> Option<u64> {
+        self.prices.get(&asset_id).copied()
+    }
+}
 
 /// Oracle verification context for Eigenlayer integration
 pub struct OracleVerificationContext {
@@ -31,9 +121,13 @@ pub struct OracleVerificationContext {
     quantum_code_distance: u8,
     
     // Metrics for operator evaluation
-    verification_metrics: VerificationMetrics,
+    verification_metrics: Mutex<VerificationMetrics>,
+    metrics_window: usize,
 }
 
+/// Default number of recent verifications the average verification time covers
+pub const DEFAULT_METRICS_WINDOW: usize = 100;
+
 /// Performance metrics for verification operations
 #[derive(Default, Clone)]
 pub struct VerificationMetrics {
@@ -41,13 +135,53 @@ pub struct VerificationMetrics {
     pub successful_verifications: u64,
     pub failed_verifications: u64,
     pub correction_applied: u64,
+    /// Average over the most recent verifications in the retention window
     pub avg_verification_time_ms: f64,
+    /// Average over every verification since the context was created
+    pub lifetime_avg_verification_time_ms: f64,
     pub last_verification_timestamp: u64,
+    recent_verification_times_ms: VecDeque<f64>,
+}
+
+impl VerificationMetrics {
+    /// Record a completed verification, keeping at most `window` recent timings
+    fn record_verification(&mut self, success: bool, elapsed_ms: f64, window: usize) {
+        self.total_verifications += 1;
+        if success {
+            self.successful_verifications += 1;
+        } else {
+            self.failed_verifications += 1;
+        }
+        
+        // Update lifetime average verification time
+        let total_verifications = self.total_verifications as f64;
+        self.lifetime_avg_verification_time_ms = 
+            ((self.lifetime_avg_verification_time_ms * (total_verifications - 1.0)) + elapsed_ms) / total_verifications;
+        
+        // Update windowed average verification time
+        self.recent_verification_times_ms.push_back(elapsed_ms);
+        while self.recent_verification_times_ms.len() > window.max(1) {
+            self.recent_verification_times_ms.pop_front();
+        }
+        self.avg_verification_time_ms = self.recent_verification_times_ms.iter().sum::<f64>()
+            / self.recent_verification_times_ms.len() as f64;
+        
+        // Update timestamp
+        self.last_verification_timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+    }
 }
 
 impl OracleVerificationContext {
     /// Create a new verification context
     pub fn new(component_id: &str, profile_url: &str) -> Self {
+        Self::with_metrics_window(component_id, profile_url, DEFAULT_METRICS_WINDOW)
+    }
+    
+    /// Create a new verification context averaging verification time over the last `metrics_window` verifications
+    pub fn with_metrics_window(component_id: &str, profile_url: &str, metrics_window: usize) -> Self {
         // In a real implementation, these would be generated securely
         let kyber_keys = generate_kyber_keypair();
         let dilithium_keys = generate_dilithium_keypair();
@@ -63,6 +197,7 @@ impl OracleVerificationContext {
             bridge_redundancy: 4,
             quantum_code_distance: 5,
             verification_metrics: Default::default(),
+            metrics_window,
         }
     }
     
@@ -74,16 +209,15 @@ impl OracleVerificationContext {
     /// Verify a signed message with comprehensive error correction
     pub fn verify_signature(&self, message: &[u8], signature: &[u8], public_key: &[u8]) -> Result<bool, String> {
         let start_time = std::time::Instant::now();
-        let mut metrics = self.verification_metrics.clone();
         
         // Apply multi-level error correction
         let corrected_message = match apply_error_correction(message, self) {
             Ok(corrected) => {
-                metrics.correction_applied += 1;
+                self.verification_metrics.lock().unwrap().correction_applied += 1;
                 corrected
             },
             Err(e) => {
-                metrics.failed_verifications += 1;
+                self.verification_metrics.lock().unwrap().failed_verifications += 1;
                 return Err(format!("Error correction failed: {}", e));
             }
         };
@@ -92,30 +226,14 @@ impl OracleVerificationContext {
         // For now, we'll use a mock verification that always succeeds
         let verification_result = true;
         
-        // Update metrics
-        metrics.total_verifications += 1;
-        if verification_result {
-            metrics.successful_verifications += 1;
-        } else {
-            metrics.failed_verifications += 1;
-        }
-        
         let elapsed = start_time.elapsed();
         let elapsed_ms = elapsed.as_secs() as f64 * 1000.0 + elapsed.subsec_nanos() as f64 / 1_000_000.0;
         
-        // Update average verification time
-        let total_verifications = metrics.total_verifications as f64;
-        metrics.avg_verification_time_ms = 
-            ((metrics.avg_verification_time_ms * (total_verifications - 1.0)) + elapsed_ms) / total_verifications;
-        
-        // Update timestamp
-        metrics.last_verification_timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs();
-        
-        // In a production environment, this would be atomic
-        // self.verification_metrics = metrics;
+        // Update metrics
+        self.verification_metrics
+            .lock()
+            .unwrap()
+            .record_verification(verification_result, elapsed_ms, self.metrics_window);
         
         Ok(verification_result)
     }
@@ -157,23 +275,27 @@ impl OracleVerificationContext {
     }
     
     /// Get verification metrics for this context
-    pub fn metrics(&self) -> &VerificationMetrics {
-        &self.verification_metrics
+    pub fn metrics(&self) -> VerificationMetrics {
+        self.verification_metrics.lock().unwrap().clone()
     }
 }
 
 /// Eigenlayer Oracle Service
 pub struct EigenlayerOracleService {
     verification_contexts: HashMap<String, OracleVerificationContext>,
-    config: config::EigenlayerConfig,
+    asset_avs: AssetAvsMapping,
+    operator_avs: HashMap<String, AVSIdentifier>,
+    price_source: Arc<dyn PriceSource>,
 }
 
 impl EigenlayerOracleService {
-    /// Create a new Eigenlayer Oracle Service
-    pub fn new(config: config::EigenlayerConfig) -> Self {
+    /// Create a new Eigenlayer Oracle Service reading prices from `price_source`
+    pub fn new(price_source: Arc<dyn PriceSource>) -> Self {
         Self {
             verification_contexts: HashMap::new(),
-            config,
+            asset_avs: HashMap::new(),
+            operator_avs: HashMap::new(),
+            price_source,
         }
     }
     
@@ -189,13 +311,43 @@ impl EigenlayerOracleService {
         self.verification_contexts.get(component_id).cloned()
     }
     
-    /// Verify an operator's data with the oracle
+    /// Set the AVS securing an asset's price feed
+    pub fn set_asset_avs(&mut self, asset_id: AssetId, avs: AVSIdentifier) {
+        self.asset_avs.insert(asset_id, avs);
+    }
+    
+    /// Get the AVS securing an asset's price feed
+    pub fn get_asset_avs(&self, asset_id: &AssetId) -> Option<&AVSIdentifier> {
+        self.asset_avs.get(asset_id)
+    }
+    
+    /// Record which AVS an operator is registered with
+    pub fn set_operator_avs(&mut self, operator_id: &str, avs: AVSIdentifier) {
+        self.operator_avs.insert(operator_id.to_string(), avs);
+    }
+    
+    /// Verify an operator's data attesting to an asset's price
+    ///
+    /// The operator must belong to the AVS mapped to the asset.
     pub fn verify_operator_data(
         &self, 
         operator_id: &str, 
+        asset_id: AssetId,
         data: &[u8], 
         signature: &[u8]
     ) -> Result<bool, String> {
+        let asset_avs = self.asset_avs.get(&asset_id)
+            .ok_or_else(|| format!("No AVS mapped to asset {}", asset_id.0))?;
+        let operator_avs = self.operator_avs.get(operator_id)
+            .ok_or_else(|| format!("Operator {} is not registered with any AVS", operator_id))?;
+        
+        if operator_avs != asset_avs {
+            return Err(format!(
+                "Operator {} belongs to AVS {} but asset {} is secured by AVS {}",
+                operator_id, operator_avs.name, asset_id.0, asset_avs.name
+            ));
+        }
+        
         // Get the oracle context for the Eigenlayer component
         let context = self.verification_contexts.get("eigenlayer").ok_or("Eigenlayer context not found")?;
         
@@ -205,21 +357,19 @@ impl EigenlayerOracleService {
     
     /// Get price data from the oracle with error correction
     pub fn get_asset_price(&self, asset_id: AssetId) -> Result<u64, String> {
-        // In a real implementation, this would query the oracle pallet
-        // For now, we'll return mock prices
-        let price = match asset_id.0 {
-            1 => 120_000_000, // NRSH price (with 6 decimals)
-            2 => 250_000_000, // ELXR price (with 6 decimals)
-            _ => return Err(format!("Price not available for asset {}", asset_id.0)),
-        };
+        if !SUPPORTED_ASSETS.contains(&asset_id) {
+            return Err(format!("Asset {} is not supported", asset_id.0));
+        }
         
-        Ok(price)
+        self.price_source
+            .price(asset_id)
+            .ok_or_else(|| format!("Price not available for asset {}", asset_id.0))
     }
     
     /// Get the performance metrics for all verification contexts
     pub fn get_performance_summary(&self) -> HashMap<String, VerificationMetrics> {
         self.verification_contexts.iter()
-            .map(|(id, context)| (id.clone(), context.metrics().clone()))
+            .map(|(id, context)| (id.clone(), context.metrics()))
             .collect()
     }
 }
@@ -243,8 +393,8 @@ fn apply_error_correction(message: &[u8], context: &OracleVerificationContext) -
     // Apply classical error correction (Reed-Solomon)
     let classical_encoded = error_correction::classical::encode(message, context.classical_redundancy);
     
-    // Apply bridge error correction (redundancy)
-    let bridge_encoded = error_correction::bridge::encode(&classical_encoded, context.bridge_redundancy);
+    // Apply bridge error correction (every byte duplicated)
+    let bridge_encoded = error_correction::bridge::encode(&classical_encoded);
     
     // Apply quantum error correction (surface codes)
     let quantum_protected = error_correction::quantum::protect(&bridge_encoded);
@@ -263,7 +413,7 @@ fn recover_from_errors(protected_message: &[u8]) -> Result<Vec<u8>, String> {
         .ok_or("Bridge error correction recovery failed")?;
     
     // Apply classical error correction recovery
-    let classical_recovered = error_correction::classical::decode(&bridge_recovered)
+    let (classical_recovered, _) = error_correction::classical::decode(&bridge_recovered)
         .ok_or("Classical error correction recovery failed")?;
     
     Ok(classical_recovered)
@@ -282,7 +432,125 @@ impl Clone for OracleVerificationContext {
             classical_redundancy: self.classical_redundancy,
             bridge_redundancy: self.bridge_redundancy,
             quantum_code_distance: self.quantum_code_distance,
-            verification_metrics: self.verification_metrics.clone(),
+            verification_metrics: Mutex::new(self.metrics()),
+            metrics_window: self.metrics_window,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sp_runtime::Percent;
+    
+    fn quote(price: u128) -> Option<PriceQuote<u128, u32>> {
+        Some(PriceQuote { price, updated_at: 1, confidence: Percent::from_percent(90) })
+    }
+    
+    #[test]
+    fn quoted_price_picks_the_requested_asset() {
+        let quotes = vec![(NRSH_ASSET_ID, quote(5)), (ELXR_ASSET_ID, quote(1_500_000))];
+        
+        assert_eq!(quoted_price(ELXR_ASSET_ID, quotes), Some(1_500_000));
+    }
+    
+    #[test]
+    fn quoted_price_is_none_without_a_feed_or_when_out_of_range() {
+        assert_eq!(quoted_price(ELXR_ASSET_ID, vec![(ELXR_ASSET_ID, None)]), None);
+        assert_eq!(quoted_price::<u128, u32>(ELXR_ASSET_ID, Vec::new()), None);
+        assert_eq!(quoted_price(ELXR_ASSET_ID, vec![(ELXR_ASSET_ID, quote(u64::MAX as u128 + 1))]), None);
+    }
+    
+    #[test]
+    fn service_serves_prices_from_its_source() {
+        let source = StaticPriceSource::default().with_price(ELXR_ASSET_ID, 2_000_000);
+        let service = EigenlayerOracleService::new(Arc::new(source));
+        
+        assert_eq!(service.get_asset_price(ELXR_ASSET_ID), Ok(2_000_000));
+        assert!(service.get_asset_price(NRSH_ASSET_ID).is_err());
+        assert!(service.get_asset_price(AssetId(9)).is_err());
+    }
+    
+    fn avs(service_id: u32, name: &str) -> AVSIdentifier {
+        AVSIdentifier { registry_address: vec![service_id as u8; 20], service_id, name: name.to_string() }
+    }
+    
+    /// Service with ELXR secured by the "elxr" AVS and operators registered with either AVS
+    fn service_with_avs_mapping() -> EigenlayerOracleService {
+        let mut service = EigenlayerOracleService::new(Arc::new(StaticPriceSource::default()));
+        service.register_component("eigenlayer", "https://example.com/eigenlayer");
+        service.set_asset_avs(ELXR_ASSET_ID, avs(1, "elxr"));
+        service.set_operator_avs("elxr-operator", avs(1, "elxr"));
+        service.set_operator_avs("nrsh-operator", avs(2, "nrsh"));
+        service
+    }
+    
+    #[test]
+    fn operator_of_the_mapped_avs_may_attest_an_asset() {
+        let service = service_with_avs_mapping();
+        
+        assert_eq!(service.verify_operator_data("elxr-operator", ELXR_ASSET_ID, b"price", &[0; 64]), Ok(true));
+        assert_eq!(service.get_asset_avs(&ELXR_ASSET_ID), Some(&avs(1, "elxr")));
+    }
+    
+    #[test]
+    fn operator_from_another_avs_is_rejected_for_an_asset() {
+        let service = service_with_avs_mapping();
+        
+        let err = service.verify_operator_data("nrsh-operator", ELXR_ASSET_ID, b"price", &[0; 64]).unwrap_err();
+        
+        assert!(err.contains("belongs to AVS nrsh but asset 2 is secured by AVS elxr"), "unexpected error: {}", err);
+    }
+    
+    #[test]
+    fn unmapped_assets_and_unknown_operators_are_rejected() {
+        let service = service_with_avs_mapping();
+        
+        assert!(service.verify_operator_data("elxr-operator", NRSH_ASSET_ID, b"price", &[0; 64]).is_err());
+        assert!(service.verify_operator_data("unknown-operator", ELXR_ASSET_ID, b"price", &[0; 64]).is_err());
+    }
+    
+    #[test]
+    fn windowed_average_tracks_recent_verifications() {
+        let mut metrics = VerificationMetrics::default();
+        for _ in 0..1_000 {
+            metrics.record_verification(true, 1.0, 10);
+        }
+        for _ in 0..10 {
+            metrics.record_verification(false, 50.0, 10);
         }
+        
+        // The window holds only the slow batch
+        assert_eq!(metrics.avg_verification_time_ms, 50.0);
+        assert_eq!(metrics.recent_verification_times_ms.len(), 10);
+        
+        // Lifetime figures still cover every verification
+        assert_eq!(metrics.total_verifications, 1_010);
+        assert_eq!((metrics.successful_verifications, metrics.failed_verifications), (1_000, 10));
+        assert!((metrics.lifetime_avg_verification_time_ms - 1_500.0 / 1_010.0).abs() < 1e-9);
+    }
+    
+    #[test]
+    fn partially_filled_window_averages_what_it_holds() {
+        let mut metrics = VerificationMetrics::default();
+        metrics.record_verification(true, 2.0, 10);
+        metrics.record_verification(true, 4.0, 10);
+        assert_eq!(metrics.avg_verification_time_ms, 3.0);
+        
+        // A zero window still keeps the latest verification
+        metrics.record_verification(true, 8.0, 0);
+        assert_eq!(metrics.avg_verification_time_ms, 8.0);
+    }
+    
+    #[test]
+    fn context_records_verifications_in_its_metrics() {
+        let context = OracleVerificationContext::with_metrics_window("eigenlayer", "https://example.com", 2);
+        for _ in 0..3 {
+            assert_eq!(context.verify_signature(b"price", &[0; 64], &[]), Ok(true));
+        }
+        
+        let metrics = context.metrics();
+        assert_eq!(metrics.total_verifications, 3);
+        assert_eq!(metrics.recent_verification_times_ms.len(), 2);
     }
 }
@@ -1,17 +1,18 @@
 //! Service implementation for Eigenlayer integration
 use crate::eigenlayer::{
+    backend::EigenBackend,
     client::EigenlayerClient,
     config::EigenConfig,
-    operator::{OperatorManager, OperatorInfo},
+    operator::{OperatorManager, OperatorInfo, OperatorEvent},
     types::{RestakeInfo, QuorumInfo, StakeAmount},
 };
 use anyhow::{Result, Context};
-use eigensdk::eigen_services::types::EigenMetrics;
+use ethers::types::U256;
 use log::{info, error, warn, debug};
 use std::{sync::{Arc, Mutex}, time::Duration};
 use tokio::{
     runtime::Runtime,
-    sync::mpsc::{self, Receiver, Sender},
+    sync::{broadcast, mpsc::{self, Receiver, Sender}},
     task::JoinHandle,
     time,
 };
@@ -34,18 +35,30 @@ enum ServiceMessage {
     /// Register a new operator
     RegisterOperator(Vec<u8>, Vec<u8>, Vec<u8>, Sender<Result<()>>),
     
+    /// Estimate the fee of registering an operator
+    EstimateRegistrationFee(Vec<u8>, Vec<u8>, Sender<Result<U256>>),
+    
+    /// Deregister an operator from its quorums
+    DeregisterOperator(Vec<u8>, Sender<Result<()>>),
+    
     /// Record a successful validation by an operator
     RecordValidation(Vec<u8>, Sender<Result<()>>),
     
     /// Record a slash event for an operator
     RecordSlash(Vec<u8>, u8, Sender<Result<()>>),
     
+    /// Request the slash history of an operator
+    GetSlashHistory(Vec<u8>, Sender<Result<Vec<(u64, u8)>>>),
+    
+    /// Render operator metrics in Prometheus text format
+    ExportMetrics(Sender<Result<String>>),
+    
     /// Stop the service
     Stop,
 }
 
 /// Eigenlayer integration service
-pub struct EigenlayerService {
+pub struct EigenlayerService<B: EigenBackend + 'static = EigenlayerClient> {
     /// Sender for the service message channel
     tx: Mutex<Option<Sender<ServiceMessage>>>,
     
@@ -54,57 +67,64 @@ pub struct EigenlayerService {
     
     /// Runtime for async operations
     runtime: Arc<Runtime>,
+    
+    /// Operator manager shared with the service task; the service keeps the last
+    /// reference so the manager's own runtime is never dropped inside a task
+    manager: Arc<OperatorManager<B>>,
 }
 
 impl EigenlayerService {
-    /// Create a new Eigenlayer service
+    /// Create a new Eigenlayer service backed by the Eigenlayer contracts
     pub fn new(config: EigenConfig) -> Result<Self> {
+        let client = EigenlayerClient::new(config)
+            .context("Failed to create Eigenlayer client")?;
+        
+        Self::with_backend(Arc::new(client))
+    }
+}
+
+impl<B: EigenBackend + 'static> EigenlayerService<B> {
+    /// Create a new Eigenlayer service reading operator and quorum state from `backend`
+    pub fn with_backend(backend: Arc<B>) -> Result<Self> {
         // Create runtime for async operations
         let runtime = Arc::new(Runtime::new()?);
         
-        // Create Eigenlayer client and operator manager inside the runtime
-        let (client, operator_manager) = runtime.block_on(async {
-            let client = EigenlayerClient::new(config)
-                .context("Failed to create Eigenlayer client")?;
-            
-            let client_arc = Arc::new(client);
-            
-            let operator_manager = OperatorManager::new(client_arc)
-                .context("Failed to create operator manager")?;
-                
-            Result::<_, anyhow::Error>::Ok((client_arc, operator_manager))
-        })?;
+        let manager = Arc::new(OperatorManager::new(backend)
+            .context("Failed to create operator manager")?);
         
         // Create channel for communicating with the service
         let (tx, rx) = mpsc::channel::<ServiceMessage>(100);
         
         // Create and start the service task
-        let task_handle = runtime.spawn(Self::run_service(rx, operator_manager));
+        let task_handle = runtime.spawn(Self::run_service(rx, manager.clone()));
         
         Ok(Self {
             tx: Mutex::new(Some(tx)),
             task_handle: Mutex::new(Some(task_handle)),
             runtime,
+            manager,
         })
     }
     
     /// Main service loop
-    async fn run_service(mut rx: Receiver<ServiceMessage>, manager: OperatorManager) {
+    async fn run_service(mut rx: Receiver<ServiceMessage>, manager: Arc<OperatorManager<B>>) {
         info!("Eigenlayer service started");
         
         // Periodic refresh task
+        let refresher = manager.clone();
         let refresh_handle = tokio::spawn(async move {
+            let manager = refresher;
             let refresh_interval = Duration::from_secs(60); // Refresh every minute
             
             loop {
                 time::sleep(refresh_interval).await;
                 
                 if let Err(e) = manager.refresh_operators() {
-                    error!("Failed to refresh operators: {:?}", e);
+                    error!(error:? = e; "Failed to refresh operators");
                 }
                 
                 if let Err(e) = manager.refresh_quorums() {
-                    error!("Failed to refresh quorums: {:?}", e);
+                    error!(error:? = e; "Failed to refresh quorums");
                 }
             }
         });
@@ -137,6 +157,16 @@ impl EigenlayerService {
                     let _ = reply.send(result).await;
                 }
                 
+                ServiceMessage::EstimateRegistrationFee(address, pubkey, reply) => {
+                    let result = manager.estimate_registration_fee(&address, &pubkey);
+                    let _ = reply.send(result).await;
+                }
+                
+                ServiceMessage::DeregisterOperator(address, reply) => {
+                    let result = manager.deregister_operator(&address);
+                    let _ = reply.send(result).await;
+                }
+                
                 ServiceMessage::RecordValidation(address, reply) => {
                     let result = manager.record_successful_validation(&address);
                     let _ = reply.send(result).await;
@@ -144,27 +174,48 @@ impl EigenlayerService {
                 
                 ServiceMessage::RecordSlash(address, severity, reply) => {
                     let result = manager.record_slash(&address, severity);
+                    if let Err(e) = &result {
+                        error!(operator = hex::encode(&address), severity = severity, error:? = e;
+                            "Failed to record slash");
+                    }
                     let _ = reply.send(result).await;
                 }
                 
+                ServiceMessage::GetSlashHistory(address, reply) => {
+                    let result = manager.get_slash_history(&address);
+                    let _ = reply.send(result).await;
+                }
+                
+                ServiceMessage::ExportMetrics(reply) => {
+                    let _ = reply.send(Ok(manager.export_metrics())).await;
+                }
+                
                 ServiceMessage::Stop => {
                     info!("Eigenlayer service stopping");
-                    refresh_handle.abort();
                     break;
                 }
             }
         }
         
+        // Wait for the refresh task to let go of the manager
+        refresh_handle.abort();
+        let _ = refresh_handle.await;
+        
         info!("Eigenlayer service stopped");
     }
     
+    /// Subscribe to operator events such as material quorum changes
+    pub fn subscribe(&self) -> broadcast::Receiver<OperatorEvent> {
+        self.manager.event_sender().subscribe()
+    }
+    
     /// Get information about a specific operator
     pub fn get_operator(&self, operator_address: &[u8]) -> Result<OperatorInfo> {
         let tx = self.tx.lock().unwrap();
         let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
         
         // Create a channel for the reply
-        let (reply_tx, reply_rx) = mpsc::channel(1);
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
         
         // Send the request
         self.runtime.block_on(async {
@@ -189,7 +240,7 @@ impl EigenlayerService {
         let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
         
         // Create a channel for the reply
-        let (reply_tx, reply_rx) = mpsc::channel(1);
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
         
         // Send the request
         self.runtime.block_on(async {
@@ -211,7 +262,7 @@ impl EigenlayerService {
         let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
         
         // Create a channel for the reply
-        let (reply_tx, reply_rx) = mpsc::channel(1);
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
         
         // Send the request
         self.runtime.block_on(async {
@@ -233,7 +284,7 @@ impl EigenlayerService {
         let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
         
         // Create a channel for the reply
-        let (reply_tx, reply_rx) = mpsc::channel(1);
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
         
         // Send the request
         self.runtime.block_on(async {
@@ -258,7 +309,7 @@ impl EigenlayerService {
         let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
         
         // Create a channel for the reply
-        let (reply_tx, reply_rx) = mpsc::channel(1);
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
         
         // Send the request
         self.runtime.block_on(async {
@@ -279,13 +330,61 @@ impl EigenlayerService {
         })
     }
     
+    /// Estimate the fee (in wei) of registering an operator, without registering it
+    pub fn estimate_registration_fee(&self, operator_address: &[u8], bls_public_key: &[u8]) -> Result<U256> {
+        let tx = self.tx.lock().unwrap();
+        let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
+        
+        // Create a channel for the reply
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
+        
+        // Send the request
+        self.runtime.block_on(async {
+            tx.send(ServiceMessage::EstimateRegistrationFee(
+                operator_address.to_vec(),
+                bls_public_key.to_vec(),
+                reply_tx,
+            )).await
+        })?;
+        
+        // Wait for the reply
+        self.runtime.block_on(async {
+            match reply_rx.recv().await {
+                Some(result) => result,
+                None => Err(anyhow::anyhow!("Failed to estimate registration fee")),
+            }
+        })
+    }
+    
+    /// Deregister an operator from its quorums
+    pub fn deregister_operator(&self, operator_address: &[u8]) -> Result<()> {
+        let tx = self.tx.lock().unwrap();
+        let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
+        
+        // Create a channel for the reply
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
+        
+        // Send the request
+        self.runtime.block_on(async {
+            tx.send(ServiceMessage::DeregisterOperator(operator_address.to_vec(), reply_tx)).await
+        })?;
+        
+        // Wait for the reply
+        self.runtime.block_on(async {
+            match reply_rx.recv().await {
+                Some(result) => result,
+                None => Err(anyhow::anyhow!("Failed to deregister operator")),
+            }
+        })
+    }
+    
     /// Record a successful validation by an operator
     pub fn record_successful_validation(&self, operator_address: &[u8]) -> Result<()> {
         let tx = self.tx.lock().unwrap();
         let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
         
         // Create a channel for the reply
-        let (reply_tx, reply_rx) = mpsc::channel(1);
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
         
         // Send the request
         self.runtime.block_on(async {
@@ -310,7 +409,7 @@ impl EigenlayerService {
         let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
         
         // Create a channel for the reply
-        let (reply_tx, reply_rx) = mpsc::channel(1);
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
         
         // Send the request
         self.runtime.block_on(async {
@@ -330,6 +429,50 @@ impl EigenlayerService {
         })
     }
     
+    /// Get the slash history of an operator as (timestamp, severity), oldest first
+    pub fn get_slash_history(&self, operator_address: &[u8]) -> Result<Vec<(u64, u8)>> {
+        let tx = self.tx.lock().unwrap();
+        let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
+        
+        // Create a channel for the reply
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
+        
+        // Send the request
+        self.runtime.block_on(async {
+            tx.send(ServiceMessage::GetSlashHistory(operator_address.to_vec(), reply_tx)).await
+        })?;
+        
+        // Wait for the reply
+        self.runtime.block_on(async {
+            match reply_rx.recv().await {
+                Some(result) => result,
+                None => Err(anyhow::anyhow!("Failed to get slash history")),
+            }
+        })
+    }
+    
+    /// Render operator metrics in Prometheus text format, for serving from a `/metrics` endpoint
+    pub fn export_metrics(&self) -> Result<String> {
+        let tx = self.tx.lock().unwrap();
+        let tx = tx.as_ref().ok_or_else(|| anyhow::anyhow!("Service not running"))?;
+        
+        // Create a channel for the reply
+        let (reply_tx, mut reply_rx) = mpsc::channel(1);
+        
+        // Send the request
+        self.runtime.block_on(async {
+            tx.send(ServiceMessage::ExportMetrics(reply_tx)).await
+        })?;
+        
+        // Wait for the reply
+        self.runtime.block_on(async {
+            match reply_rx.recv().await {
+                Some(result) => result,
+                None => Err(anyhow::anyhow!("Failed to export metrics")),
+            }
+        })
+    }
+    
     /// Stop the service
     pub fn stop(&self) -> Result<()> {
         let mut tx_guard = self.tx.lock().unwrap();
@@ -355,8 +498,76 @@ impl EigenlayerService {
     }
 }
 
-impl Drop for EigenlayerService {
+impl<B: EigenBackend + 'static> Drop for EigenlayerService<B> {
     fn drop(&mut self) {
         let _ = self.stop();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eigenlayer::{backend::MockEigenBackend, types::OperatorStatus};
+    
+    fn operator(address: u8, restaked_amount: u128) -> RestakeInfo {
+        RestakeInfo {
+            operator_address: vec![address; 20],
+            restaked_amount,
+            strategy_shares: Vec::new(),
+            public_key: vec![address; 32],
+            quorum_ids: vec![0],
+            status: OperatorStatus::Active,
+        }
+    }
+    
+    #[test]
+    fn service_answers_from_a_mock_backend() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(operator(1, 1_000));
+        backend.set_quorum(QuorumInfo { quorum_id: 0, operator_count: 1, total_stake: 1_000, min_stake: 100 });
+        let service = EigenlayerService::with_backend(backend).unwrap();
+        
+        assert_eq!(service.get_operator(&[1; 20]).unwrap().restake_info.restaked_amount, 1_000);
+        assert_eq!(service.get_quorum(0).unwrap().min_stake, 100);
+        
+        service.record_slash(&[1; 20], 10).unwrap();
+        assert_eq!(service.get_slash_history(&[1; 20]).unwrap().len(), 1);
+        
+        service.stop().unwrap();
+        assert!(service.get_all_operators().is_err());
+    }
+    
+    #[test]
+    fn slash_history_lists_severities_oldest_first() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_operator(operator(1, 1_000));
+        let service = EigenlayerService::with_backend(backend).unwrap();
+        service.get_operator(&[1; 20]).unwrap();
+        let start = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+        
+        for severity in [5, 40, 20] {
+            service.record_slash(&[1; 20], severity).unwrap();
+        }
+        
+        let history = service.get_slash_history(&[1; 20]).unwrap();
+        let end = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+        assert_eq!(history.iter().map(|(_, severity)| *severity).collect::<Vec<_>>(), vec![5, 40, 20]);
+        assert!(history.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+        assert!(history.iter().all(|(at, _)| (start..=end).contains(at)));
+        assert!(service.get_slash_history(&[2; 20]).is_err());
+        
+        service.stop().unwrap();
+    }
+    
+    #[test]
+    fn registration_fee_is_estimated_through_the_service() {
+        let backend = Arc::new(MockEigenBackend::new());
+        backend.set_registration_fee(U256::from(3_000_000_000_000_000u64));
+        let service = EigenlayerService::with_backend(backend).unwrap();
+        
+        let fee = service.estimate_registration_fee(&[1; 20], &[1; 48]).unwrap();
+        
+        assert_eq!(fee, U256::from(3_000_000_000_000_000u64));
+        service.stop().unwrap();
+    }
+}
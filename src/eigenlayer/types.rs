@@ -27,8 +27,11 @@ pub struct AVSIdentifier {
 pub struct RestakeInfo {
     /// Operator Ethereum address
     pub operator_address: Vec<u8>,
-    /// Total ETH restaked (in wei)
+    /// Total ETH restaked (in wei), summed over `strategy_shares`
     pub restaked_amount: u128,
+    /// Shares held in each strategy as (strategy address, shares)
+    #[serde(default)]
+    pub strategy_shares: Vec<(Vec<u8>, u128)>,
     /// BLS Public Key of the operator
     pub public_key: Vec<u8>,
     /// Quorums the operator is participating in
@@ -37,6 +40,32 @@ pub struct RestakeInfo {
     pub status: OperatorStatus,
 }
 
+impl RestakeInfo {
+    /// Stake per underlying token, merging strategies backed by the same token.
+    /// Strategies missing from `strategy_tokens` are left out.
+    pub fn stake_amounts(&self, strategy_tokens: &HashMap<Vec<u8>, Vec<u8>>) -> Vec<StakeAmount> {
+        let mut amounts: Vec<StakeAmount> = Vec::new();
+        for (strategy, shares) in &self.strategy_shares {
+            let token = match strategy_tokens.get(strategy) {
+                Some(token) => token,
+                None => continue,
+            };
+            match amounts.iter_mut().find(|stake| &stake.token_address == token) {
+                Some(stake) => stake.amount = stake.amount.saturating_add(*shares),
+                None => amounts.push(StakeAmount { token_address: token.clone(), amount: *shares }),
+            }
+        }
+        amounts
+    }
+    
+    /// Total stake backed by `token_address` across all strategies
+    pub fn total_in_token(&self, strategy_tokens: &HashMap<Vec<u8>, Vec<u8>>, token_address: &[u8]) -> u128 {
+        self.strategy_shares.iter()
+            .filter(|(strategy, _)| strategy_tokens.get(strategy).map_or(false, |token| token == token_address))
+            .fold(0u128, |total, (_, shares)| total.saturating_add(*shares))
+    }
+}
+
 /// The status of an operator in the Eigenlayer system
 #[derive(Clone, Debug, Serialize, Deserialize, Encode, Decode, TypeInfo, PartialEq, Eq)]
 pub enum OperatorStatus {
@@ -107,3 +136,58 @@ impl EigenlayerConversions for RestakeInfo {
         self.clone()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    const STRATEGY_A: [u8; 20] = [0xA1; 20];
+    const STRATEGY_B: [u8; 20] = [0xB2; 20];
+    const STRATEGY_C: [u8; 20] = [0xC3; 20];
+    const UNMAPPED_STRATEGY: [u8; 20] = [0xD4; 20];
+    const TOKEN_X: [u8; 20] = [0x11; 20];
+    const TOKEN_Y: [u8; 20] = [0x22; 20];
+    
+    /// Strategies A and C hold token X; strategy B holds token Y
+    fn strategy_tokens() -> HashMap<Vec<u8>, Vec<u8>> {
+        HashMap::from([
+            (STRATEGY_A.to_vec(), TOKEN_X.to_vec()),
+            (STRATEGY_B.to_vec(), TOKEN_Y.to_vec()),
+            (STRATEGY_C.to_vec(), TOKEN_X.to_vec()),
+        ])
+    }
+    
+    fn restake_info(strategy_shares: Vec<([u8; 20], u128)>) -> RestakeInfo {
+        RestakeInfo {
+            operator_address: vec![1; 20],
+            restaked_amount: strategy_shares.iter().map(|(_, shares)| shares).sum(),
+            strategy_shares: strategy_shares.into_iter().map(|(strategy, shares)| (strategy.to_vec(), shares)).collect(),
+            public_key: vec![1; 32],
+            quorum_ids: vec![0],
+            status: OperatorStatus::Active,
+        }
+    }
+    
+    #[test]
+    fn two_strategies_value_stake_in_their_own_tokens() {
+        let info = restake_info(vec![(STRATEGY_A, 1_000), (STRATEGY_B, 250)]);
+        
+        assert_eq!(info.stake_amounts(&strategy_tokens()), vec![
+            StakeAmount { token_address: TOKEN_X.to_vec(), amount: 1_000 },
+            StakeAmount { token_address: TOKEN_Y.to_vec(), amount: 250 },
+        ]);
+        assert_eq!(info.total_in_token(&strategy_tokens(), &TOKEN_X), 1_000);
+        assert_eq!(info.total_in_token(&strategy_tokens(), &TOKEN_Y), 250);
+    }
+    
+    #[test]
+    fn strategies_sharing_a_token_are_summed_and_unmapped_ones_skipped() {
+        let info = restake_info(vec![(STRATEGY_A, 1_000), (STRATEGY_C, 500), (UNMAPPED_STRATEGY, 9_999)]);
+        
+        assert_eq!(info.stake_amounts(&strategy_tokens()), vec![
+            StakeAmount { token_address: TOKEN_X.to_vec(), amount: 1_500 },
+        ]);
+        assert_eq!(info.total_in_token(&strategy_tokens(), &TOKEN_X), 1_500);
+        assert_eq!(info.total_in_token(&strategy_tokens(), &TOKEN_Y), 0);
+    }
+}
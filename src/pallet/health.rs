@@ -0,0 +1,186 @@
+//! SCOBY health scoring derived from fermentation telemetry.
+
+use codec::{Decode, Encode};
+use frame_support::RuntimeDebug;
+
+use crate::types::TelemetrySample;
+
+/// Maximum number of most recent samples considered when scoring
+pub const HEALTH_WINDOW: usize = 24;
+
+/// Share of the total score contributed by each component (sums to 100)
+const PH_WEIGHT: u32 = 40;
+const TEMPERATURE_WEIGHT: u32 = 30;
+const STAGE_TIME_WEIGHT: u32 = 30;
+
+/// Target ranges a healthy SCOBY is expected to stay within
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct ScobyHealthTargets {
+    /// Acceptable pH band (scaled by 10, min, max)
+    pub ph_range: (u8, u8),
+    /// Largest acceptable temperature swing across the window (tenths of a degree Celsius)
+    pub max_temperature_spread: u16,
+    /// Expected time spent in the current stage in milliseconds
+    pub expected_stage_duration: u64,
+}
+
+impl Default for ScobyHealthTargets {
+    fn default() -> Self {
+        Self {
+            ph_range: (25, 35),
+            max_temperature_spread: 30,
+            expected_stage_duration: 14 * 24 * 60 * 60 * 1000,
+        }
+    }
+}
+
+/// Coarse health band shown to users
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub enum HealthBand {
+    Excellent,
+    Good,
+    Fair,
+    Poor,
+}
+
+/// SCOBY health score (0-100) with the contribution of each component
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug)]
+pub struct HealthScore {
+    /// Overall score (0-100)
+    pub score: u8,
+    /// Points from samples within the target pH band (0-40)
+    pub ph: u8,
+    /// Points from temperature stability (0-30)
+    pub temperature: u8,
+    /// Points from elapsed time relative to the expected stage duration (0-30)
+    pub stage_time: u8,
+}
+
+impl HealthScore {
+    pub fn band(&self) -> HealthBand {
+        match self.score {
+            85..=100 => HealthBand::Excellent,
+            65..=84 => HealthBand::Good,
+            40..=64 => HealthBand::Fair,
+            _ => HealthBand::Poor,
+        }
+    }
+}
+
+/// Score the most recent samples (oldest first) against `targets`.
+/// Returns `None` when there is no telemetry to score.
+pub fn score(samples: &[TelemetrySample], targets: &ScobyHealthTargets) -> Option<HealthScore> {
+    let window = &samples[samples.len().saturating_sub(HEALTH_WINDOW)..];
+    let (first, last) = (window.first()?, window.last()?);
+    let total = window.len() as u32;
+
+    // pH: proportion of samples inside the target band
+    let (ph_min, ph_max) = targets.ph_range;
+    let in_band = window
+        .iter()
+        .filter(|s| s.ph_level >= ph_min && s.ph_level <= ph_max)
+        .count() as u32;
+    let ph = PH_WEIGHT * in_band / total;
+
+    // Temperature: full marks within the allowed spread, nothing at twice the spread
+    let t_min = window.iter().map(|s| s.temperature).min().unwrap_or_default();
+    let t_max = window.iter().map(|s| s.temperature).max().unwrap_or_default();
+    let spread = u32::from(t_max.saturating_sub(t_min));
+    let allowed = u32::from(targets.max_temperature_spread).max(1);
+    let temperature = if spread <= allowed {
+        TEMPERATURE_WEIGHT
+    } else {
+        TEMPERATURE_WEIGHT * (2 * allowed).saturating_sub(spread) / allowed
+    };
+
+    // Stage time: full marks until the expected duration, nothing at twice the duration
+    let elapsed = last.timestamp.saturating_sub(first.timestamp);
+    let expected = targets.expected_stage_duration.max(1);
+    let stage_time = if elapsed <= expected {
+        STAGE_TIME_WEIGHT
+    } else {
+        (u64::from(STAGE_TIME_WEIGHT) * (2 * expected).saturating_sub(elapsed) / expected) as u32
+    };
+
+    Some(HealthScore {
+        score: (ph + temperature + stage_time) as u8,
+        ph: ph as u8,
+        temperature: temperature as u8,
+        stage_time: stage_time as u8,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(timestamp: u64, ph_level: u8, temperature: u16) -> TelemetrySample {
+        TelemetrySample { timestamp, ph_level, temperature }
+    }
+
+    fn score_of(samples: &[TelemetrySample]) -> HealthScore {
+        score(samples, &ScobyHealthTargets::default()).unwrap()
+    }
+
+    fn with_score(score: u8) -> HealthScore {
+        HealthScore { score, ph: 0, temperature: 0, stage_time: 0 }
+    }
+
+    #[test]
+    fn no_telemetry_has_no_score() {
+        assert_eq!(score(&[], &ScobyHealthTargets::default()), None);
+    }
+
+    #[test]
+    fn steady_in_band_fermentation_scores_full_marks() {
+        let health = score_of(&[sample(0, 30, 240), sample(1_000, 32, 250)]);
+
+        assert_eq!(health, HealthScore { score: 100, ph: 40, temperature: 30, stage_time: 30 });
+        assert_eq!(health.band(), HealthBand::Excellent);
+    }
+
+    #[test]
+    fn ph_points_follow_the_share_of_samples_in_band() {
+        let health = score_of(&[sample(0, 30, 240), sample(1_000, 40, 240)]);
+
+        assert_eq!(health.ph, 20);
+        assert_eq!(health.score, 80);
+        assert_eq!(health.band(), HealthBand::Good);
+    }
+
+    #[test]
+    fn temperature_points_fall_to_zero_at_twice_the_allowed_spread() {
+        assert_eq!(score_of(&[sample(0, 30, 240), sample(1_000, 30, 270)]).temperature, 30);
+        assert_eq!(score_of(&[sample(0, 30, 240), sample(1_000, 30, 285)]).temperature, 15);
+        assert_eq!(score_of(&[sample(0, 30, 240), sample(1_000, 30, 300)]).temperature, 0);
+        assert_eq!(score_of(&[sample(0, 30, 200), sample(1_000, 30, 400)]).temperature, 0);
+    }
+
+    #[test]
+    fn stage_time_points_fall_to_zero_at_twice_the_expected_duration() {
+        let expected = ScobyHealthTargets::default().expected_stage_duration;
+
+        assert_eq!(score_of(&[sample(0, 30, 240), sample(expected, 30, 240)]).stage_time, 30);
+        assert_eq!(score_of(&[sample(0, 30, 240), sample(expected * 3 / 2, 30, 240)]).stage_time, 15);
+        assert_eq!(score_of(&[sample(0, 30, 240), sample(expected * 2, 30, 240)]).stage_time, 0);
+    }
+
+    #[test]
+    fn only_the_most_recent_window_is_scored() {
+        let samples: Vec<_> = (0..HEALTH_WINDOW as u64 + 10)
+            .map(|i| sample(i * 1_000, if i < 10 { 60 } else { 30 }, 240))
+            .collect();
+
+        assert_eq!(score_of(&samples).ph, 40);
+    }
+
+    #[test]
+    fn bands_split_at_their_thresholds() {
+        assert_eq!(with_score(85).band(), HealthBand::Excellent);
+        assert_eq!(with_score(84).band(), HealthBand::Good);
+        assert_eq!(with_score(65).band(), HealthBand::Good);
+        assert_eq!(with_score(64).band(), HealthBand::Fair);
+        assert_eq!(with_score(40).band(), HealthBand::Fair);
+        assert_eq!(with_score(39).band(), HealthBand::Poor);
+    }
+}
@@ -4,25 +4,36 @@
 
 use frame_support::{
     decl_error, decl_event, decl_module, decl_storage,
-    dispatch::DispatchResult,
+    dispatch::{DispatchResult, DispatchResultWithPostInfo, PostDispatchInfo},
     ensure,
     traits::{Currency, ExistenceRequirement, Get, ReservableCurrency},
+    weights::Pays,
+    BoundedVec,
 };
-use frame_system::{ensure_signed, pallet_prelude::*};
-use sp_runtime::traits::StaticLookup;
+use frame_system::{ensure_root, ensure_signed, pallet_prelude::*};
+use codec::Encode;
+use sp_runtime::{traits::{Hash as HashT, StaticLookup}, Percent};
 use sp_std::prelude::*;
 
 mod types;
 pub use types::*;
 
+pub mod health;
+pub use health::{HealthBand, HealthScore, ScobyHealthTargets};
+
 // Import oracle and oracle-liquidity integration modules
 pub mod oracle;
 pub mod oracle_liquidity;
 
+#[cfg(feature = "runtime-benchmarks")]
+mod oracle_liquidity_benchmarking;
+
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod oracle_tests;
 
 pub mod weights;
 pub use weights::*;
@@ -30,6 +41,12 @@ pub use weights::*;
 pub mod runtime_api;
 pub use runtime_api::*;
 
+pub mod runtime_interface;
+pub use runtime_interface::*;
+
+/// Redundancy of the parity stored alongside verification evidence
+pub const EVIDENCE_REDUNDANCY: u8 = 1;
+
 type BalanceOf<T> =
     <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
@@ -50,6 +67,33 @@ pub trait Config: frame_system::Config {
     /// The maximum number of verifiers per batch.
     type MaxVerifiers: Get<u32>;
 
+    /// The number of passed or failed votes that decides a batch's verification.
+    type VerificationQuorum: Get<u32>;
+
+    /// The number of inconclusive votes after which a batch's verification restarts.
+    type InconclusiveRetryThreshold: Get<u32>;
+
+    /// The maximum length of the evidence a verifier may attach to a vote.
+    type MaxVerificationDataLength: Get<u32>;
+
+    /// Target ranges used when scoring SCOBY health from telemetry.
+    type ScobyHealthTargets: Get<ScobyHealthTargets>;
+
+    /// The maximum number of telemetry samples accepted in a single call.
+    type MaxSamplesPerCall: Get<u32>;
+
+    /// The maximum number of telemetry samples stored for a batch.
+    type MaxTelemetrySamples: Get<u32>;
+
+    /// The number of telemetry samples a batch needs before it can be voted as passed.
+    type MinTelemetrySamplesForVerification: Get<u32>;
+
+    /// The maximum number of endpoints allowed to submit telemetry.
+    type MaxAuthorizedEndpoints: Get<u32>;
+
+    /// The value of a batch with a perfect SCOBY health score, used to derive prices.
+    type HealthyBatchValue: Get<BalanceOf<Self>>;
+
     /// The oracle pallet configuration
     type OracleConfig: oracle::Config;
 
@@ -60,4 +104,375 @@ pub trait Config: frame_system::Config {
     type WeightInfo: WeightInfo;
 }
 
-// The main implementation will go here
+decl_storage! {
+    trait Store for Module<T: Config> as Elixir {
+        /// Telemetry samples recorded for each batch, oldest first.
+        pub Telemetry get(fn telemetry):
+            map hasher(blake2_128_concat) BatchId => BoundedVec<TelemetrySample, T::MaxTelemetrySamples>;
+
+        /// Verification votes cast on each batch in the current round.
+        pub VerificationVotes get(fn verification_votes):
+            map hasher(blake2_128_concat) BatchId => Vec<(T::AccountId, VerificationResult)>;
+
+        /// Verification status of each batch that has received votes.
+        pub BatchVerificationStatus get(fn batch_verification_status):
+            map hasher(blake2_128_concat) BatchId => Option<VerificationStatus>;
+
+        /// Digest of the evidence each verifier attached to their vote on a batch. The
+        /// evidence itself, bounded by `MaxVerificationDataLength`, is only kept in the
+        /// off-chain index with parity, under `evidence_key`.
+        pub VerificationEvidence get(fn verification_evidence):
+            double_map hasher(blake2_128_concat) BatchId, hasher(blake2_128_concat) T::AccountId
+                => Option<VerificationInfo<T::Hash>>;
+
+        /// Sensor accounts whose telemetry is recorded without fees. Trust only waives
+        /// the fee; a sensor must also be in `AuthorizedEndpoints` to submit at all.
+        pub TrustedSensors get(fn is_trusted_sensor): map hasher(blake2_128_concat) T::AccountId => bool;
+
+        /// Digest over the evidence digests of each verified batch, ordered by verifier,
+        /// taken when it passed verification.
+        pub VerificationDigests get(fn verification_digest): map hasher(blake2_128_concat) BatchId => Option<T::Hash>;
+
+        /// Completion certificate of each verified batch, see `issue_completion_certificate`.
+        pub CompletionCertificates get(fn certificate_of):
+            map hasher(blake2_128_concat) BatchId => Option<T::Hash>;
+
+        /// Bridge endpoints whose telemetry is accepted at all.
+        pub AuthorizedEndpoints get(fn authorized_endpoints): BoundedVec<T::AccountId, T::MaxAuthorizedEndpoints>;
+    }
+}
+
+decl_event! {
+    pub enum Event<T> where
+        AccountId = <T as frame_system::Config>::AccountId,
+        Hash = <T as frame_system::Config>::Hash,
+    {
+        /// A batch of telemetry samples was recorded. \[batch_id, sample_count\]
+        TelemetryBatchRecorded(BatchId, u32),
+        /// A verifier voted on a batch. \[batch_id, result\]
+        VerificationRecorded(BatchId, VerificationResult),
+        /// A verifier attached evidence to their vote. \[batch_id, verifier, evidence_hash\]
+        VerificationEvidenceStored(BatchId, AccountId, Hash),
+        /// A batch passed verification. \[batch_id, evidence_digest\]
+        BatchVerified(BatchId, Hash),
+        /// A completion certificate was issued for a batch. \[batch_id, certificate\]
+        CompletionCertificateIssued(BatchId, Hash),
+        /// A batch failed verification. \[batch_id\]
+        BatchVerificationFailed(BatchId),
+        /// Too many inconclusive votes; verification of the batch restarts. \[batch_id\]
+        VerificationInconclusive(BatchId),
+        /// A sensor account was allow-listed for fee-free telemetry. \[sensor\]
+        TrustedSensorAdded(AccountId),
+        /// A sensor account was removed from the allow-list. \[sensor\]
+        TrustedSensorRemoved(AccountId),
+        /// An endpoint was authorized to submit telemetry. \[endpoint\]
+        EndpointAuthorized(AccountId),
+        /// An endpoint's authorization was revoked. \[endpoint\]
+        EndpointRevoked(AccountId),
+    }
+}
+
+decl_error! {
+    pub enum Error for Module<T: Config> {
+        /// A telemetry sample lies outside the physically valid sensor range.
+        SampleOutOfRange,
+        /// Telemetry timestamps are not strictly ascending.
+        NonMonotonicTimestamps,
+        /// A verification vote must be passed, failed or inconclusive.
+        InvalidVerificationResult,
+        /// The verifier already voted on this batch.
+        AlreadyVoted,
+        /// The batch already has the maximum number of verifiers.
+        TooManyVerifiers,
+        /// The batch's verification has already been decided.
+        VerificationClosed,
+        /// The attached verification evidence exceeds the maximum length.
+        VerificationDataTooLong,
+        /// The batch has too little telemetry to be voted as passed.
+        InsufficientTelemetry,
+        /// The batch has not passed verification.
+        BatchNotCompleted,
+        /// A completion certificate was already issued for the batch.
+        CertificateAlreadyIssued,
+        /// The caller is not an authorized endpoint.
+        EndpointNotAuthorized,
+        /// The endpoint is already authorized.
+        EndpointAlreadyAuthorized,
+        /// The maximum number of authorized endpoints has been reached.
+        TooManyEndpoints,
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Config> for enum Call where origin: T::Origin {
+        type Error = Error<T>;
+
+        fn deposit_event() = default;
+
+        const ScobyHealthTargets: ScobyHealthTargets = T::ScobyHealthTargets::get();
+        const MaxSamplesPerCall: u32 = T::MaxSamplesPerCall::get();
+        const MaxTelemetrySamples: u32 = T::MaxTelemetrySamples::get();
+        const MaxAuthorizedEndpoints: u32 = T::MaxAuthorizedEndpoints::get();
+        const MinTelemetrySamplesForVerification: u32 = T::MinTelemetrySamplesForVerification::get();
+
+        /// Record buffered telemetry samples for a batch in a single call.
+        ///
+        /// Samples must be in range and strictly ascending in time, both within the
+        /// call and relative to the last stored sample; otherwise nothing is stored.
+        /// Only authorized endpoints may submit; trusted sensors are refunded the fee.
+        /// A batch holds at most `MaxTelemetrySamples` samples; the weight charged up
+        /// front assumes a full batch and is refunded down to the samples stored.
+        #[weight = T::WeightInfo::record_telemetry_batch(samples.len() as u32, T::MaxTelemetrySamples::get())]
+        pub fn record_telemetry_batch(
+            origin,
+            batch_id: BatchId,
+            samples: BoundedVec<TelemetrySample, T::MaxSamplesPerCall>,
+        ) -> DispatchResultWithPostInfo {
+            let who = ensure_signed(origin)?;
+
+            ensure!(Self::authorized_endpoints().contains(&who), Error::<T>::EndpointNotAuthorized);
+            ensure!(samples.iter().all(TelemetrySample::is_in_range), Error::<T>::SampleOutOfRange);
+
+            let mut telemetry = Telemetry::<T>::get(&batch_id);
+            let stored = telemetry.len() as u32;
+            let mut last_timestamp = telemetry.last().map(|s| s.timestamp);
+            for sample in samples.iter() {
+                ensure!(
+                    last_timestamp.map_or(true, |last| sample.timestamp > last),
+                    Error::<T>::NonMonotonicTimestamps
+                );
+                last_timestamp = Some(sample.timestamp);
+            }
+
+            let count = samples.len() as u32;
+            for sample in samples.into_inner() {
+                telemetry.try_push(sample).map_err(|_| Error::<T>::TooManyTelemetrySamples)?;
+            }
+            Telemetry::<T>::insert(&batch_id, telemetry);
+
+            Self::deposit_event(RawEvent::TelemetryBatchRecorded(batch_id, count));
+
+            let pays_fee = if Self::is_trusted_sensor(&who) { Pays::No } else { Pays::Yes };
+            Ok(PostDispatchInfo {
+                actual_weight: Some(T::WeightInfo::record_telemetry_batch(count, stored)),
+                pays_fee,
+            })
+        }
+
+        /// Allow-list a sensor account so its telemetry is recorded without fees.
+        #[weight = T::WeightInfo::update_trusted_sensors()]
+        pub fn add_trusted_sensor(origin, sensor: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+
+            TrustedSensors::<T>::insert(&sensor, true);
+
+            Self::deposit_event(RawEvent::TrustedSensorAdded(sensor));
+            Ok(())
+        }
+
+        /// Remove a sensor account from the fee-free allow-list.
+        #[weight = T::WeightInfo::update_trusted_sensors()]
+        pub fn remove_trusted_sensor(origin, sensor: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+
+            TrustedSensors::<T>::remove(&sensor);
+
+            Self::deposit_event(RawEvent::TrustedSensorRemoved(sensor));
+            Ok(())
+        }
+
+        /// Authorize an endpoint to submit telemetry.
+        #[weight = T::WeightInfo::update_authorized_endpoints()]
+        pub fn add_authorized_endpoint(origin, endpoint: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let mut endpoints = Self::authorized_endpoints();
+            ensure!(!endpoints.contains(&endpoint), Error::<T>::EndpointAlreadyAuthorized);
+            endpoints.try_push(endpoint.clone()).map_err(|_| Error::<T>::TooManyEndpoints)?;
+            AuthorizedEndpoints::<T>::put(endpoints);
+
+            Self::deposit_event(RawEvent::EndpointAuthorized(endpoint));
+            Ok(())
+        }
+
+        /// Revoke an endpoint's authorization to submit telemetry.
+        #[weight = T::WeightInfo::update_authorized_endpoints()]
+        pub fn remove_authorized_endpoint(origin, endpoint: T::AccountId) -> DispatchResult {
+            ensure_root(origin)?;
+
+            let mut endpoints = Self::authorized_endpoints();
+            ensure!(endpoints.contains(&endpoint), Error::<T>::EndpointNotAuthorized);
+            endpoints.retain(|e| e != &endpoint);
+            AuthorizedEndpoints::<T>::put(endpoints);
+
+            Self::deposit_event(RawEvent::EndpointRevoked(endpoint));
+            Ok(())
+        }
+
+        /// Record a verifier's assessment of a batch.
+        ///
+        /// Passed and failed votes decide the batch once `VerificationQuorum` of them
+        /// are cast. A passed vote needs `MinTelemetrySamplesForVerification` samples.
+        /// Inconclusive votes decide nothing; once `InconclusiveRetryThreshold` of them
+        /// accumulate, the round's votes are cleared so verification can restart.
+        ///
+        /// Optional `data` (e.g. a lab report) is indexed off-chain with parity under a
+        /// digest of the raw payload; only the digest is stored on-chain.
+        #[weight = T::WeightInfo::verify_batch()]
+        pub fn record_verification(
+            origin,
+            batch_id: BatchId,
+            result: VerificationResult,
+            data: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+
+            ensure!(result != VerificationResult::Pending, Error::<T>::InvalidVerificationResult);
+            if result == VerificationResult::Passed {
+                ensure!(
+                    Self::telemetry(&batch_id).len() >= T::MinTelemetrySamplesForVerification::get() as usize,
+                    Error::<T>::InsufficientTelemetry
+                );
+            }
+            ensure!(
+                data.len() <= T::MaxVerificationDataLength::get() as usize,
+                Error::<T>::VerificationDataTooLong
+            );
+            ensure!(
+                matches!(
+                    Self::batch_verification_status(&batch_id),
+                    None | Some(VerificationStatus::Pending) | Some(VerificationStatus::InProgress)
+                ),
+                Error::<T>::VerificationClosed
+            );
+
+            let mut votes = Self::verification_votes(&batch_id);
+            ensure!(!votes.iter().any(|(verifier, _)| verifier == &who), Error::<T>::AlreadyVoted);
+            ensure!((votes.len() as u32) < T::MaxVerifiers::get(), Error::<T>::TooManyVerifiers);
+
+            if !data.is_empty() {
+                let data_digest = T::Hashing::hash(&data);
+                let protected = oracle::error_correction::classical::encode(&data, EVIDENCE_REDUNDANCY);
+                sp_io::offchain_index::set(&Self::evidence_key(&data_digest), &protected);
+                VerificationEvidence::<T>::insert(&batch_id, &who, VerificationInfo { data_digest });
+                Self::deposit_event(RawEvent::VerificationEvidenceStored(batch_id.clone(), who.clone(), data_digest));
+            }
+
+            votes.push((who, result));
+            Self::deposit_event(RawEvent::VerificationRecorded(batch_id.clone(), result));
+
+            let count = |wanted: VerificationResult| votes.iter().filter(|(_, r)| *r == wanted).count() as u32;
+            let (passed, failed, inconclusive) = (
+                count(VerificationResult::Passed),
+                count(VerificationResult::Failed),
+                count(VerificationResult::Inconclusive),
+            );
+
+            if passed + failed >= T::VerificationQuorum::get() {
+                VerificationVotes::<T>::remove(&batch_id);
+                if passed > failed {
+                    BatchVerificationStatus::insert(&batch_id, VerificationStatus::Verified);
+                    let digest = Self::evidence_digest(&batch_id);
+                    VerificationDigests::<T>::insert(&batch_id, digest);
+                    Self::deposit_event_with_topic(&batch_id, RawEvent::BatchVerified(batch_id.clone(), digest));
+                } else {
+                    BatchVerificationStatus::insert(&batch_id, VerificationStatus::Failed);
+                    Self::deposit_event(RawEvent::BatchVerificationFailed(batch_id));
+                }
+            } else if inconclusive >= T::InconclusiveRetryThreshold::get() {
+                VerificationVotes::<T>::remove(&batch_id);
+                BatchVerificationStatus::insert(&batch_id, VerificationStatus::Pending);
+                Self::deposit_event(RawEvent::VerificationInconclusive(batch_id));
+            } else {
+                VerificationVotes::<T>::insert(&batch_id, votes);
+                BatchVerificationStatus::insert(&batch_id, VerificationStatus::InProgress);
+            }
+
+            Ok(())
+        }
+
+        /// Attest that a batch completed fermentation and passed verification.
+        ///
+        /// The certificate is a hash over the batch ID, its verification status and all
+        /// telemetry recorded for it, fixed at the time of issue.
+        #[weight = T::WeightInfo::issue_completion_certificate()]
+        pub fn issue_completion_certificate(origin, batch_id: BatchId) -> DispatchResult {
+            ensure_signed(origin)?;
+
+            let status = Self::batch_verification_status(&batch_id);
+            ensure!(status == Some(VerificationStatus::Verified), Error::<T>::BatchNotCompleted);
+            ensure!(Self::certificate_of(&batch_id).is_none(), Error::<T>::CertificateAlreadyIssued);
+
+            let certificate = T::Hashing::hash_of(&(&batch_id, status, Self::telemetry(&batch_id)));
+            CompletionCertificates::<T>::insert(&batch_id, certificate);
+
+            Self::deposit_event_with_topic(&batch_id, RawEvent::CompletionCertificateIssued(batch_id.clone(), certificate));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Config> Module<T> {
+    /// Off-chain index key under which verification evidence with `hash` is stored.
+    pub fn evidence_key(hash: &T::Hash) -> Vec<u8> {
+        (b"elixir/evidence", hash).encode()
+    }
+
+    /// Hash over every verifier's evidence digest for a batch, ordered by verifier.
+    fn evidence_digest(batch_id: &BatchId) -> T::Hash {
+        let mut digests: Vec<(T::AccountId, T::Hash)> = VerificationEvidence::<T>::iter_prefix(batch_id)
+            .map(|(verifier, info)| (verifier, info.data_digest))
+            .collect();
+        digests.sort();
+        T::Hashing::hash_of(&digests)
+    }
+
+    /// Whether the batch's evidence digests still hash to the digest recorded when
+    /// it was verified. `false` for batches without a digest.
+    pub fn verify_digest(batch_id: &BatchId) -> bool {
+        Self::verification_digest(batch_id) == Some(Self::evidence_digest(batch_id))
+    }
+
+    /// Whether `indexed`, evidence read from the off-chain index, error-corrects to
+    /// the evidence whose digest is `data_digest`.
+    pub fn evidence_matches(data_digest: &T::Hash, indexed: &[u8]) -> bool {
+        oracle::error_correction::classical::decode(indexed)
+            .map_or(false, |(data, _)| T::Hashing::hash(&data) == *data_digest)
+    }
+
+    /// Deposit an event with the hash of `subject`'s encoding as its topic, so
+    /// indexers can filter by batch without decoding every event.
+    fn deposit_event_with_topic<S: Encode>(subject: &S, event: Event<T>) {
+        let topic = T::Hashing::hash_of(subject);
+        <frame_system::Pallet<T>>::deposit_event_indexed(&[topic], <T as Config>::Event::from(event).into());
+    }
+
+    /// SCOBY health of a batch derived from its recent telemetry, if any was recorded.
+    pub fn scoby_health(batch_id: Vec<u8>) -> Option<HealthScore> {
+        health::score(&Self::telemetry(batch_id), &T::ScobyHealthTargets::get())
+    }
+}
+
+impl<T: Config> pallet_kombucha_registry::FermentationSource<T::Hash> for Module<T> {
+    /// Batches are complete once they pass verification.
+    fn is_completed(batch_id: &BatchId) -> bool {
+        Self::batch_verification_status(batch_id) == Some(VerificationStatus::Verified)
+    }
+
+    fn completion_certificate(batch_id: &BatchId) -> Option<T::Hash> {
+        Self::certificate_of(batch_id)
+    }
+}
+
+impl<T: Config> oracle::FermentationValuation<BalanceOf<T>> for Module<T> {
+    /// `HealthyBatchValue` scaled by the batch's health score, for batches rated good or better.
+    fn healthy_batch_valuation(batch_id: &BatchId) -> Option<BalanceOf<T>> {
+        let health = Self::scoby_health(batch_id.clone())?;
+        match health.band() {
+            HealthBand::Excellent | HealthBand::Good => {
+                Some(Percent::from_percent(health.score) * T::HealthyBatchValue::get())
+            }
+            HealthBand::Fair | HealthBand::Poor => None,
+        }
+    }
+}
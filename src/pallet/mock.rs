@@ -0,0 +1,266 @@
+//! Test runtime for the Elixir pallet and its oracle integrations.
+
+use crate as elixir_pallet;
+use crate::{oracle, oracle_liquidity, BatchId};
+use frame_support::{
+    parameter_types,
+    traits::{ConstU128, ConstU32, ConstU64, Contains, Everything, Get, Hooks},
+    PalletId,
+};
+use shared::liquidity::types::AssetId;
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup},
+    Percent,
+};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Balance = u128;
+
+/// NRSH asset, priced only by signed submissions
+pub const NRSH: AssetId = AssetId(1);
+/// ELXR asset, the only asset whose price may be derived from fermentation telemetry
+pub const ELXR: AssetId = AssetId(2);
+
+/// Milliseconds between consecutive blocks unless a test spaces them differently
+pub const BLOCK_TIME_MILLIS: u64 = 6_000;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Timestamp: pallet_timestamp,
+        Balances: pallet_balances,
+        Elixir: elixir_pallet,
+        Oracle: oracle,
+        OracleLiquidity: oracle_liquidity,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type Origin = Origin;
+    type Call = Call;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type Event = Event;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_timestamp::Config for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+    type MinimumPeriod = ConstU64<1>;
+    type WeightInfo = ();
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ConstU32<2>;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type Event = Event;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const ScobyHealthTargets: elixir_pallet::ScobyHealthTargets = elixir_pallet::ScobyHealthTargets {
+        ph_range: (25, 35),
+        max_temperature_spread: 30,
+        expected_stage_duration: 14 * 24 * 60 * 60 * 1000,
+    };
+}
+
+impl elixir_pallet::Config for Test {
+    type Event = Event;
+    type Currency = Balances;
+    type MinBrewerStake = ConstU128<100>;
+    type FermentationPeriod = ConstU64<100>;
+    type MaxVerifiers = ConstU32<5>;
+    type VerificationQuorum = ConstU32<2>;
+    type InconclusiveRetryThreshold = ConstU32<2>;
+    type MaxVerificationDataLength = ConstU32<64>;
+    type ScobyHealthTargets = ScobyHealthTargets;
+    type MaxSamplesPerCall = ConstU32<16>;
+    type MaxTelemetrySamples = ConstU32<4>;
+    type MinTelemetrySamplesForVerification = ConstU32<2>;
+    type MaxAuthorizedEndpoints = ConstU32<4>;
+    type HealthyBatchValue = ConstU128<1_000>;
+    type OracleConfig = Test;
+    type OracleLiquidityConfig = Test;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const OraclePalletId: PalletId = PalletId(*b"elx/orcl");
+}
+
+thread_local! {
+    static BATCH_VALUATIONS: RefCell<BTreeMap<BatchId, Balance>> = RefCell::new(BTreeMap::new());
+    static CONSENSUS_THRESHOLD: RefCell<Percent> = RefCell::new(Percent::from_percent(60));
+}
+
+/// Oracle consensus threshold, 60% unless a test sets another
+pub struct ConsensusThreshold;
+
+impl ConsensusThreshold {
+    /// Use `threshold` for the rest of the test
+    pub fn set(threshold: Percent) {
+        CONSENSUS_THRESHOLD.with(|t| *t.borrow_mut() = threshold);
+    }
+}
+
+impl Get<Percent> for ConsensusThreshold {
+    fn get() -> Percent {
+        CONSENSUS_THRESHOLD.with(|t| *t.borrow())
+    }
+}
+
+/// Fermentation valuations programmed by each test
+pub struct MockFermentation;
+
+impl MockFermentation {
+    /// Value `batch_id` as a healthy batch worth `value`
+    pub fn set_healthy(batch_id: &[u8], value: Balance) {
+        BATCH_VALUATIONS.with(|v| v.borrow_mut().insert(batch_id.to_vec(), value));
+    }
+}
+
+impl oracle::FermentationValuation<Balance> for MockFermentation {
+    fn healthy_batch_valuation(batch_id: &BatchId) -> Option<Balance> {
+        BATCH_VALUATIONS.with(|v| v.borrow().get(batch_id).copied())
+    }
+}
+
+/// Only ELXR prices may be derived from fermentation telemetry
+pub struct DerivedPriceAssets;
+
+impl Contains<AssetId> for DerivedPriceAssets {
+    fn contains(asset_id: &AssetId) -> bool {
+        *asset_id == ELXR
+    }
+}
+
+impl oracle::Config for Test {
+    type RuntimeEvent = Event;
+    type Currency = Balances;
+    type MinValidators = ConstU32<3>;
+    type ConsensusThreshold = ConsensusThreshold;
+    type MinStake = ConstU128<100>;
+    type FullConfidenceStakeMultiple = ConstU32<10>;
+    type MaxStakePerValidator = ConstU128<10_000>;
+    type FeedConsumers = OracleLiquidity;
+    type Fermentation = MockFermentation;
+    type MaxEvidenceBatches = ConstU32<4>;
+    type DerivedPriceAssets = DerivedPriceAssets;
+    type MaxPriceAge = ConstU64<100>;
+    type TwapWindow = ConstU64<10>;
+    type ConfidenceDecayBlocks = ConstU64<100>;
+    type MinBlocksBetweenSubmissions = ConstU64<5>;
+    type BlockTimeMillis = ConstU64<BLOCK_TIME_MILLIS>;
+    type UnixTime = Timestamp;
+    type PalletId = OraclePalletId;
+    type RewardPerFinalization = ConstU128<30>;
+    type AllowedSignatureSchemes = Everything;
+    type WeightInfo = ();
+}
+
+parameter_types! {
+    pub const MinOverrideConfidence: Percent = Percent::from_percent(50);
+}
+
+impl oracle_liquidity::Config for Test {
+    type AmmHandler = ();
+    type RuntimeEvent = Event;
+    type MinOverrideConfidence = MinOverrideConfidence;
+    type WeightInfo = ();
+}
+
+/// Accounts endowed at genesis
+pub const ENDOWED: [AccountId; 6] = [1, 2, 3, 4, 5, 6];
+
+/// Balance of each endowed account
+pub const ENDOWMENT: Balance = 100_000;
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: ENDOWED.iter().map(|who| (*who, ENDOWMENT)).collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| {
+        // Both test assets start out trusted, so their feeds accept submissions
+        for asset_id in [NRSH, ELXR] {
+            oracle::VerificationStatuses::<Test>::insert(
+                oracle::VerificationSubject::Asset(asset_id),
+                crate::types::VerificationStatus::Verified,
+            );
+        }
+        run_to_block(1)
+    });
+    ext
+}
+
+/// Advance to block `n`, each block `BLOCK_TIME_MILLIS` after the last
+pub fn run_to_block(n: u64) {
+    while System::block_number() < n {
+        advance_block(BLOCK_TIME_MILLIS);
+    }
+}
+
+/// Move to the next block, `millis` after the current one
+pub fn advance_block(millis: u64) {
+    Oracle::on_finalize(System::block_number());
+    System::set_block_number(System::block_number() + 1);
+    Timestamp::set_timestamp(Timestamp::now() + millis);
+    Oracle::on_initialize(System::block_number());
+}
+
+/// Register each of `validators` with `stake` reserved
+pub fn register_validators(validators: &[AccountId], stake: Balance) {
+    for who in validators {
+        frame_support::assert_ok!(Oracle::register_validator(Origin::signed(*who), stake));
+    }
+}
+
+/// Events deposited by the oracle pallet, oldest first
+pub fn oracle_events() -> Vec<oracle::Event<Test>> {
+    System::events()
+        .into_iter()
+        .filter_map(|record| match record.event {
+            Event::Oracle(event) => Some(event),
+            _ => None,
+        })
+        .collect()
+}
@@ -10,27 +10,43 @@ use frame_support::{
     dispatch::DispatchResult,
     ensure,
     pallet_prelude::*,
-    traits::{Currency, ExistenceRequirement, Get, ReservableCurrency},
+    traits::{Contains, Currency, ExistenceRequirement, Get, ReservableCurrency, UnixTime},
     weights::Weight,
+    PalletId,
 };
 use frame_system::pallet_prelude::*;
-use sp_runtime::{traits::Zero, DispatchError, Percent};
+use sp_runtime::{
+    traits::{AccountIdConversion, CheckedAdd, Hash as HashT, SaturatedConversion, Saturating, Zero},
+    DispatchError, Perbill, Percent,
+};
 use sp_std::prelude::*;
 
 // Integrations
-use crate::pallet::types::{ElixirAsset, VerificationStatus};
-use shared::liquidity::types::{AddLiquidityParams, AssetId, PoolId, PriceCalculator, SwapParams};
+use crate::pallet::types::{BatchId, ElixirAsset, VerificationStatus};
+use crate::runtime_api::PriceQuote;
+use shared::liquidity::types::{AddLiquidityParams, PoolId, PriceCalculator, SwapParams};
+pub use shared::liquidity::types::AssetId;
 
 // Re-use quantum cryptography from the daemonless oracle
-mod crypto {
-    // Mock interfaces for the quantum-resistant cryptography
-    // In production, these would be linked to the actual implementations
+pub(crate) mod crypto {
+    // Key generation is still mocked: the keys below are placeholders that no
+    // signer accepts, so validators register their real Dilithium3 public key with
+    // `set_dilithium_key`. Signing, verification and decapsulation go through the
+    // `pq_crypto` host functions. Native builds and runtimes linked
+    // against the node's host functions call them directly, everything else falls
+    // back to the slow in-Wasm implementation.
+    #[cfg(any(feature = "std", feature = "pq-host-functions"))]
+    use crate::pq_crypto as pq;
+    #[cfg(not(any(feature = "std", feature = "pq-host-functions")))]
+    use crate::runtime_interface::fallback as pq;
     
     pub struct KyberPublicKey(pub Vec<u8>);
     pub struct KyberPrivateKey(pub Vec<u8>);
     pub struct DilithiumPublicKey(pub Vec<u8>);
     pub struct DilithiumPrivateKey(pub Vec<u8>);
     pub struct DilithiumSignature(pub Vec<u8>);
+    pub struct FalconPublicKey(pub Vec<u8>);
+    pub struct FalconSignature(pub Vec<u8>);
     
     pub fn kyber_keygen() -> (KyberPublicKey, KyberPrivateKey) {
         // In production, this would call the actual Kyber key generation
@@ -42,9 +58,9 @@ mod crypto {
         (DilithiumPublicKey(vec![0; 32]), DilithiumPrivateKey(vec![0; 32]))
     }
     
-    pub fn dilithium_sign(private_key: &DilithiumPrivateKey, message: &[u8]) -> DilithiumSignature {
-        // In production, this would call the actual Dilithium signing function
-        DilithiumSignature(vec![0; 64])
+    /// `None` if `private_key` is not a Dilithium3 secret key or signing is unavailable
+    pub fn dilithium_sign(private_key: &DilithiumPrivateKey, message: &[u8]) -> Option<DilithiumSignature> {
+        pq::dilithium_sign(&private_key.0, message).map(DilithiumSignature)
     }
     
     pub fn dilithium_verify(
@@ -52,76 +68,26 @@ mod crypto {
         message: &[u8], 
         signature: &DilithiumSignature
     ) -> bool {
-        // In production, this would call the actual Dilithium verification function
-        true
-    }
-}
-
-// Error correction modules at multiple levels
-mod error_correction {
-    pub mod classical {
-        // Reed-Solomon error correction for classical data
-        pub fn encode(data: &[u8], redundancy: u8) -> Vec<u8> {
-            // Mock implementation
-            let mut encoded = data.to_vec();
-            encoded.extend_from_slice(&[redundancy; 16]);
-            encoded
-        }
-        
-        pub fn decode(data: &[u8]) -> Option<Vec<u8>> {
-            // Mock implementation
-            if data.len() < 16 {
-                return None;
-            }
-            Some(data[..data.len() - 16].to_vec())
-        }
+        pq::dilithium_verify(&public_key.0, message, &signature.0)
     }
     
-    pub mod bridge {
-        // Bridge error correction for classical-quantum interface
-        pub fn encode(data: &[u8], redundancy_level: u8) -> Vec<u8> {
-            // Mock implementation
-            let mut encoded = Vec::with_capacity(data.len() * 2);
-            for &byte in data {
-                encoded.push(byte);
-                encoded.push(byte); // Simple duplication for redundancy
-            }
-            encoded
-        }
-        
-        pub fn decode(data: &[u8]) -> Option<Vec<u8>> {
-            // Mock implementation
-            if data.len() % 2 != 0 {
-                return None;
-            }
-            
-            let mut decoded = Vec::with_capacity(data.len() / 2);
-            for i in (0..data.len()).step_by(2) {
-                decoded.push(data[i]);
-            }
-            Some(decoded)
-        }
+    pub fn falcon_verify(
+        public_key: &FalconPublicKey,
+        message: &[u8],
+        signature: &FalconSignature
+    ) -> bool {
+        pq::falcon_verify(&public_key.0, message, &signature.0)
     }
     
-    pub mod quantum {
-        // Surface code error correction for quantum data
-        pub fn protect(data: &[u8]) -> Vec<u8> {
-            // Mock implementation of surface code protection
-            let mut protected = data.to_vec();
-            protected.extend_from_slice(&[0xEC; 32]); // Error correction metadata
-            protected
-        }
-        
-        pub fn recover(data: &[u8]) -> Option<Vec<u8>> {
-            // Mock implementation
-            if data.len() < 32 {
-                return None;
-            }
-            Some(data[..data.len() - 32].to_vec())
-        }
+    pub fn kyber_decapsulate(private_key: &KyberPrivateKey, ciphertext: &[u8]) -> Option<Vec<u8>> {
+        pq::kyber_decapsulate(&private_key.0, ciphertext)
     }
 }
 
+// Error correction at the classical, bridge and quantum levels, shared with the
+// registry and daemonless oracle pallets
+pub use elixir_error_correction as error_correction;
+
 // Define the pallet configuration trait
 pub trait Config: frame_system::Config {
     /// The overarching event type
@@ -133,20 +99,191 @@ pub trait Config: frame_system::Config {
     /// Minimum number of validators required for consensus
     type MinValidators: Get<u32>;
     
-    /// Consensus threshold percentage
+    /// Consensus threshold percentage; must be at least `MIN_CONSENSUS_THRESHOLD`
+    #[pallet::constant]
     type ConsensusThreshold: Get<Percent>;
     
     /// Minimum stake amount for validators
     type MinStake: Get<BalanceOf<Self>>;
     
+    /// Multiple of `MinStake` a validator needs to submit at full confidence; below
+    /// it, accepted confidence is capped in proportion to stake
+    #[pallet::constant]
+    type FullConfidenceStakeMultiple: Get<u32>;
+    
+    /// Maximum stake a single validator may accumulate
+    #[pallet::constant]
+    type MaxStakePerValidator: Get<BalanceOf<Self>>;
+    
+    /// Pallets relying on this oracle's price feeds
+    type FeedConsumers: PriceFeedConsumers;
+    
+    /// Telemetry-derived valuation of fermentation batches
+    type Fermentation: FermentationValuation<BalanceOf<Self>>;
+    
+    /// Maximum number of evidence batches per derived price submission
+    #[pallet::constant]
+    type MaxEvidenceBatches: Get<u32>;
+    
+    /// Assets whose price may be derived from fermentation telemetry (ELXR)
+    type DerivedPriceAssets: Contains<AssetId>;
+    
+    /// Blocks after which a finalized price is considered stale
+    #[pallet::constant]
+    type MaxPriceAge: Get<Self::BlockNumber>;
+    
+    /// Number of blocks of finalized prices averaged into the TWAP
+    #[pallet::constant]
+    type TwapWindow: Get<Self::BlockNumber>;
+    
+    /// Blocks over which a finalized price's confidence decays linearly to zero;
+    /// zero disables decay
+    #[pallet::constant]
+    type ConfidenceDecayBlocks: Get<Self::BlockNumber>;
+    
+    /// Blocks a validator must wait between submissions for the same asset
+    #[pallet::constant]
+    type MinBlocksBetweenSubmissions: Get<Self::BlockNumber>;
+    
+    /// Expected block time, converting the block-denominated `TwapWindow` into a
+    /// wall-clock span
+    #[pallet::constant]
+    type BlockTimeMillis: Get<u64>;
+    
+    /// Wall-clock time (`pallet_timestamp`) each TWAP sample is stamped with
+    type UnixTime: UnixTime;
+    
+    /// Identifier deriving the reward pool account
+    #[pallet::constant]
+    type PalletId: Get<PalletId>;
+    
+    /// Reward shared, by stake, among the validators behind each finalized price. Capped
+    /// at what the reward pool holds beyond the rewards already accrued.
+    #[pallet::constant]
+    type RewardPerFinalization: Get<BalanceOf<Self>>;
+    
+    /// Signature schemes validators may sign price submissions with
+    type AllowedSignatureSchemes: Contains<SignatureScheme>;
+    
     /// Weight information for extrinsics
     type WeightInfo: WeightInfo;
 }
 
+/// Implemented by pallets that depend on price feeds, so a feed still in use is never cleared
+pub trait PriceFeedConsumers {
+    /// Whether anything still depends on the asset's price feed
+    fn is_asset_in_use(asset_id: AssetId) -> bool;
+}
+
+impl PriceFeedConsumers for () {
+    fn is_asset_in_use(_asset_id: AssetId) -> bool {
+        false
+    }
+}
+
+/// Provides telemetry-derived valuations of fermentation batches
+pub trait FermentationValuation<Balance> {
+    /// Valuation of a batch, if its telemetry shows it is healthy
+    fn healthy_batch_valuation(batch_id: &BatchId) -> Option<Balance>;
+}
+
 #[pallet::pallet]
 #[pallet::without_storage_info]
 pub struct Pallet<T>(_);
 
+/// Lowest consensus threshold accepted; anything less lets a minority finalize prices
+pub const MIN_CONSENSUS_THRESHOLD: Percent = Percent::from_percent(50);
+
+/// Storage layout `on_runtime_upgrade` migrates `OracleVersion` to
+pub const ORACLE_VERSION: u32 = 1;
+
+/// Length of a Falcon-512 public key
+pub const FALCON_PUBLIC_KEY_LEN: usize = 897;
+
+/// Length of a Dilithium3 public key
+pub const DILITHIUM_PUBLIC_KEY_LEN: usize = 1952;
+
+#[pallet::hooks]
+impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_runtime_upgrade() -> Weight {
+        let version = OracleVersion::<T>::get();
+        let mut weight = T::DbWeight::get().reads(1);
+        if version >= ORACLE_VERSION {
+            return weight;
+        }
+        
+        if version < 1 {
+            weight = weight.saturating_add(Self::migrate_to_v1());
+        }
+        weight
+    }
+    
+    fn integrity_test() {
+        assert!(
+            T::ConsensusThreshold::get() >= MIN_CONSENSUS_THRESHOLD,
+            "ConsensusThreshold must be at least 50%"
+        );
+    }
+}
+
+#[pallet::genesis_config]
+pub struct GenesisConfig<T: Config> {
+    /// Validators registered at genesis with their stake, reserved from their endowment
+    pub validators: Vec<(T::AccountId, BalanceOf<T>)>,
+    /// Assets whose feeds start out verified
+    pub assets: Vec<AssetId>,
+}
+
+#[cfg(feature = "std")]
+impl<T: Config> Default for GenesisConfig<T> {
+    fn default() -> Self {
+        Self { validators: Vec::new(), assets: Vec::new() }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T: Config> GenesisConfig<T> {
+    /// Check every genesis validator can be registered: listed once, with a stake
+    /// within bounds that its endowment covers
+    pub fn validate(&self) -> Result<(), &'static str> {
+        for (i, (who, stake)) in self.validators.iter().enumerate() {
+            if self.validators[..i].iter().any(|(other, _)| other == who) {
+                return Err("validator listed more than once");
+            }
+            if *stake < T::MinStake::get() {
+                return Err("validator stake below MinStake");
+            }
+            if *stake > T::MaxStakePerValidator::get() {
+                return Err("validator stake above MaxStakePerValidator");
+            }
+            if !T::Currency::can_reserve(who, *stake) {
+                return Err("validator endowment does not cover its stake");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[pallet::genesis_build]
+impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+    fn build(&self) {
+        if let Err(reason) = self.validate() {
+            panic!("Invalid oracle genesis config: {}", reason);
+        }
+        
+        // A new chain starts on the current layout; there is nothing to migrate
+        OracleVersion::<T>::put(ORACLE_VERSION);
+        for (who, stake) in &self.validators {
+            if let Err(e) = Pallet::<T>::do_register_validator(who.clone(), *stake) {
+                panic!("Genesis validator {:?} could not be registered: {:?}", who, e);
+            }
+        }
+        for asset_id in &self.assets {
+            VerificationStatuses::<T>::insert(VerificationSubject::Asset(*asset_id), VerificationStatus::Verified);
+        }
+    }
+}
+
 // Storage items
 #[pallet::storage]
 pub type PriceFeeds<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, PriceFeed<T>>;
@@ -154,9 +291,15 @@ pub type PriceFeeds<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, PriceF
 #[pallet::storage]
 pub type Validators<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ValidatorInfo<T>>;
 
+#[pallet::storage]
+pub type ValidatorCount<T: Config> = StorageValue<_, u32, ValueQuery>;
+
 #[pallet::storage]
 pub type ValidatorStakes<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, BalanceOf<T>>;
 
+#[pallet::storage]
+pub type FalconKeys<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Vec<u8>>;
+
 #[pallet::storage]
 pub type QuantumKeys<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, (Vec<u8>, Vec<u8>)>;
 
@@ -168,9 +311,62 @@ pub type LiquidityOraclePrices<T: Config> = StorageDoubleMap<
     Balance<T>,
 >;
 
+#[pallet::storage]
+pub type Paused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
 #[pallet::storage]
 pub type OracleVersion<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+/// Governance-assigned trust in each feed and validator. Only `Verified` feeds
+/// accept submissions and resolve to a price; `Failed` validators cannot submit.
+#[pallet::storage]
+pub type VerificationStatuses<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat, VerificationSubject<T::AccountId>,
+    VerificationStatus,
+    OptionQuery,
+>;
+
+#[pallet::storage]
+pub type PriceSamples<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat, AssetId,
+    Vec<(T::BlockNumber, u64, Balance<T>)>,
+    ValueQuery,
+>;
+
+#[pallet::storage]
+pub type LastSubmission<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat, T::AccountId,
+    Blake2_128Concat, AssetId,
+    T::BlockNumber,
+>;
+
+#[pallet::storage]
+pub type PendingRewards<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, Balance<T>, ValueQuery>;
+
+/// Sum of `PendingRewards`, the part of the reward pool already owed to validators
+#[pallet::storage]
+pub type TotalPendingRewards<T: Config> = StorageValue<_, Balance<T>, ValueQuery>;
+
+/// Latest finalized price of each asset with its block, written on every consensus
+/// finalization; the restore point of `restore_feed` and the last fallback source
+#[pallet::storage]
+pub type LastKnownGood<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, (Balance<T>, T::BlockNumber)>;
+
+/// Hash of the last quantum proof reported as mismatching, per asset; a repeat
+/// challenge of the same proof is charged
+#[pallet::storage]
+pub type ReportedProofMismatches<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, [u8; 32]>;
+
+#[pallet::storage]
+pub type FallbackSources<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, Vec<PriceSource>>;
+
+/// Resolution order used for assets without configured fallback sources
+pub const DEFAULT_PRICE_SOURCES: [PriceSource; 3] =
+    [PriceSource::PrimaryFeed, PriceSource::Twap, PriceSource::LastKnownGood];
+
 // Define types
 type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 type Balance<T> = BalanceOf<T>;
@@ -181,11 +377,36 @@ pub struct PriceFeed<T: Config> {
     pub asset_id: AssetId,
     pub price: Balance<T>,
     pub timestamp: T::BlockNumber,
-    pub confidence: u8,
-    pub signatures: Vec<(T::AccountId, Vec<u8>)>,
+    /// Lowest confidence among the submissions behind the price
+    pub confidence: Percent,
+    /// Validators signing the round in progress, with the scheme, signature and
+    /// confidence they submitted; cleared once the round's price is finalized
+    pub signatures: Vec<(T::AccountId, SignatureScheme, Vec<u8>, Percent)>,
     pub quantum_proof: Vec<u8>,
 }
 
+// `PriceFeed` layout at `OracleVersion` 0: a raw u8 confidence and untagged signatures
+#[derive(Encode, Decode)]
+struct PriceFeedV0<T: Config> {
+    asset_id: AssetId,
+    price: Balance<T>,
+    timestamp: T::BlockNumber,
+    confidence: u8,
+    signatures: Vec<(T::AccountId, Vec<u8>)>,
+    quantum_proof: Vec<u8>,
+}
+
+// Scheme a price submission was signed with
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+pub enum SignatureScheme {
+    /// CRYSTALS-Dilithium3 over the key the validator registered with `set_dilithium_key`
+    Dilithium3,
+    /// No off-chain signature; the price was derived from on-chain evidence
+    Unsigned,
+    /// Falcon-512 over the key the validator registered with `set_falcon_key`
+    Falcon,
+}
+
 // Validator information
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
 pub struct ValidatorInfo<T: Config> {
@@ -196,6 +417,37 @@ pub struct ValidatorInfo<T: Config> {
     pub dilithium_public_key: Vec<u8>,
 }
 
+// Something governance decides whether to trust
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum VerificationSubject<AccountId> {
+    Asset(AssetId),
+    Validator(AccountId),
+}
+
+/// Whether governance may move a subject from `current` to `next`
+///
+/// Subjects without a status are unverified. Unverified → `Pending` →
+/// `Verified`/`Failed`; verified subjects may later fail and failed ones be
+/// resubmitted for review. `InProgress` and `Disputed` only apply to batches.
+pub fn can_transition_verification(current: Option<VerificationStatus>, next: VerificationStatus) -> bool {
+    use VerificationStatus::*;
+    matches!(
+        (current, next),
+        (None, Pending) | (Some(Pending), Verified) | (Some(Pending), Failed) | (Some(Verified), Failed) | (Some(Failed), Pending)
+    )
+}
+
+// Where a resolved price came from
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+pub enum PriceSource {
+    /// Latest consensus price, if not stale
+    PrimaryFeed,
+    /// Time-weighted average of prices finalized within the TWAP window
+    Twap,
+    /// Most recent consensus price, regardless of age
+    LastKnownGood,
+}
+
 // Events
 #[pallet::event]
 #[pallet::generate_deposit(pub(super) fn deposit_event)]
@@ -204,7 +456,7 @@ pub enum Event<T: Config> {
     PriceUpdated {
         asset_id: AssetId,
         price: Balance<T>,
-        confidence: u8,
+        confidence: Percent,
     },
     /// New validator registered
     ValidatorRegistered {
@@ -223,6 +475,52 @@ pub enum Event<T: Config> {
         asset_id: AssetId,
         price: Balance<T>,
     },
+    /// Price feed and its liquidity pool prices removed
+    PriceFeedCleared {
+        asset_id: AssetId,
+    },
+    /// Verification status of an asset or validator changed
+    VerificationStatusChanged {
+        subject: VerificationSubject<T::AccountId>,
+        from: Option<VerificationStatus>,
+        to: VerificationStatus,
+    },
+    /// Recovered price bytes did not decode to a balance; the stored price was used
+    PriceDecodeFailed {
+        asset_id: AssetId,
+    },
+    /// A feed's quantum proof was checked against its stored price
+    QuantumProofVerified {
+        asset_id: AssetId,
+        matches: bool,
+    },
+    /// Price submissions halted
+    OraclePaused,
+    /// Price submissions resumed
+    OracleResumed,
+    /// Validator claimed its accrued rewards
+    RewardsClaimed {
+        account_id: T::AccountId,
+        amount: Balance<T>,
+    },
+    /// Feed rolled back to its last known good price
+    FeedRestored {
+        asset_id: AssetId,
+        price: Balance<T>,
+    },
+    /// Fallback price sources of an asset changed
+    FallbackSourcesSet {
+        asset_id: AssetId,
+        sources: Vec<PriceSource>,
+    },
+    /// Validator registered a Falcon public key
+    FalconKeySet {
+        account_id: T::AccountId,
+    },
+    /// Validator registered a Dilithium3 public key
+    DilithiumKeySet {
+        account_id: T::AccountId,
+    },
 }
 
 // Errors
@@ -230,6 +528,8 @@ pub enum Event<T: Config> {
 pub enum Error<T> {
     /// Account is not a registered validator
     NotValidator,
+    /// Account is already a registered validator
+    AlreadyValidator,
     /// Minimum stake requirement not met
     InsufficientStake,
     /// Not enough signatures to reach consensus
@@ -240,12 +540,46 @@ pub enum Error<T> {
     InvalidQuantumProof,
     /// Invalid signature
     InvalidSignature,
+    /// Signature scheme is not on the allow-list
+    SignatureSchemeNotAllowed,
     /// Price feed does not exist
     PriceFeedNotFound,
     /// Pool does not exist
     PoolNotFound,
     /// Asset not in pool
     AssetNotInPool,
+    /// Validator stake would overflow
+    StakeOverflow,
+    /// Validator stake would exceed the per-validator maximum
+    StakeExceedsMaximum,
+    /// Verification status cannot move to the requested state
+    IllegalVerificationTransition,
+    /// Price feed is still referenced by an oracle-driven pool
+    AssetInUse,
+    /// None of the evidence batches is healthy
+    NoHealthyEvidence,
+    /// The same evidence batch was listed more than once
+    DuplicateEvidenceBatch,
+    /// Asset's price cannot be derived from fermentation telemetry
+    AssetNotDerivable,
+    /// Fallback sources must be non-empty and free of duplicates
+    InvalidFallbackSources,
+    /// No price has been finalized for the asset yet
+    NoLastKnownGood,
+    /// No rewards have accrued for the account
+    NoRewards,
+    /// Price submissions are paused
+    OraclePaused,
+    /// Validator submitted for this asset too recently
+    SubmittingTooFast,
+    /// Falcon public key is not a Falcon-512 key
+    InvalidFalconKey,
+    /// Dilithium public key is not a Dilithium3 key
+    InvalidDilithiumKey,
+    /// Asset's feed has not been verified by governance
+    AssetNotVerified,
+    /// Validator failed governance verification
+    ValidatorNotTrusted,
 }
 
 // Calls
@@ -257,8 +591,422 @@ impl<T: Config> Pallet<T> {
     pub fn register_validator(origin: OriginFor<T>, stake: BalanceOf<T>) -> DispatchResult {
         let who = ensure_signed(origin)?;
         
+        Self::do_register_validator(who, stake)
+    }
+    
+    /// Submit a price update for an asset
+    #[pallet::call_index(1)]
+    #[pallet::weight(T::WeightInfo::submit_price_update())]
+    pub fn submit_price_update(
+        origin: OriginFor<T>,
+        asset_id: AssetId,
+        price: Balance<T>,
+        confidence: Percent,
+        scheme: SignatureScheme,
+        signature: Vec<u8>,
+    ) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        
+        ensure!(!Paused::<T>::get(), Error::<T>::OraclePaused);
+        
+        // Verify validator status
+        let validator = Validators::<T>::get(&who).ok_or(Error::<T>::NotValidator)?;
+        Self::ensure_trusted(&who, asset_id)?;
+        
+        
+        ensure!(
+            scheme != SignatureScheme::Unsigned && T::AllowedSignatureSchemes::contains(&scheme),
+            Error::<T>::SignatureSchemeNotAllowed
+        );
+        
+        let message = Self::signing_payload(asset_id, price, confidence);
+        let valid = match scheme {
+            SignatureScheme::Dilithium3 => crypto::dilithium_verify(
+                &crypto::DilithiumPublicKey(validator.dilithium_public_key.clone()),
+                &message,
+                &crypto::DilithiumSignature(signature.clone()),
+            ),
+            SignatureScheme::Falcon => FalconKeys::<T>::get(&who).map_or(false, |public_key| {
+                crypto::falcon_verify(
+                    &crypto::FalconPublicKey(public_key),
+                    &message,
+                    &crypto::FalconSignature(signature.clone()),
+                )
+            }),
+            SignatureScheme::Unsigned => false,
+        };
+        ensure!(valid, Error::<T>::InvalidSignature);
+        
+        // Low-stake validators cannot assert high confidence
+        let confidence = confidence.min(Self::confidence_cap(validator.stake));
+        
+        Self::apply_price_submission(who, asset_id, price, confidence, scheme, signature)
+    }
+    
+    /// Increase validator stake
+    #[pallet::call_index(2)]
+    #[pallet::weight(T::WeightInfo::increase_stake())]
+    pub fn increase_stake(origin: OriginFor<T>, additional_stake: BalanceOf<T>) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        
+        // Verify validator status
+        let mut validator = Validators::<T>::get(&who).ok_or(Error::<T>::NotValidator)?;
+        
+        // Check the new total before reserving anything
+        let total_stake = validator.stake.checked_add(&additional_stake)
+            .ok_or(Error::<T>::StakeOverflow)?;
+        ensure!(total_stake <= T::MaxStakePerValidator::get(), Error::<T>::StakeExceedsMaximum);
+        
+        // Reserve additional stake
+        T::Currency::reserve(&who, additional_stake)?;
+        
+        // Update validator info
+        validator.stake = total_stake;
+        
+        Validators::<T>::insert(&who, validator.clone());
+        ValidatorStakes::<T>::insert(&who, validator.stake);
+        
+        // Emit event
+        Self::deposit_event(Event::StakeIncreased {
+            account_id: who,
+            additional_stake,
+            total_stake: validator.stake,
+        });
+        
+        Ok(())
+    }
+    
+    /// Move an asset or validator through the verification states
+    #[pallet::call_index(3)]
+    #[pallet::weight(T::WeightInfo::set_verification_status())]
+    pub fn set_verification_status(
+        origin: OriginFor<T>,
+        subject: VerificationSubject<T::AccountId>,
+        status: VerificationStatus,
+    ) -> DispatchResult {
+        ensure_root(origin)?;
+        
+        let current = VerificationStatuses::<T>::get(&subject);
+        ensure!(can_transition_verification(current, status), Error::<T>::IllegalVerificationTransition);
+        
+        VerificationStatuses::<T>::insert(&subject, status);
+        
+        // Emit event
+        Self::deposit_event(Event::VerificationStatusChanged {
+            subject,
+            from: current,
+            to: status,
+        });
+        
+        Ok(())
+    }
+    
+    /// Remove a compromised or deprecated price feed entirely, along with every price
+    /// it could still resolve from and its submission history
+    #[pallet::call_index(4)]
+    #[pallet::weight(T::WeightInfo::clear_price_feed())]
+    pub fn clear_price_feed(origin: OriginFor<T>, asset_id: AssetId) -> DispatchResult {
+        ensure_root(origin)?;
+        
+        ensure!(PriceFeeds::<T>::contains_key(asset_id), Error::<T>::PriceFeedNotFound);
+        ensure!(!T::FeedConsumers::is_asset_in_use(asset_id), Error::<T>::AssetInUse);
+        
+        PriceFeeds::<T>::remove(asset_id);
+        PriceSamples::<T>::remove(asset_id);
+        LastKnownGood::<T>::remove(asset_id);
+        FallbackSources::<T>::remove(asset_id);
+        ReportedProofMismatches::<T>::remove(asset_id);
+        
+        // Let validators submit for the asset again straight away
+        let submitters: Vec<T::AccountId> = LastSubmission::<T>::iter()
+            .filter(|(_, submitted_asset, _)| *submitted_asset == asset_id)
+            .map(|(who, _, _)| who)
+            .collect();
+        for who in submitters {
+            LastSubmission::<T>::remove(&who, asset_id);
+        }
+        
+        // Remove the asset's price from every liquidity pool
+        let pool_ids: Vec<PoolId> = LiquidityOraclePrices::<T>::iter()
+            .filter(|(_, pool_asset, _)| *pool_asset == asset_id)
+            .map(|(pool_id, _, _)| pool_id)
+            .collect();
+        for pool_id in pool_ids {
+            LiquidityOraclePrices::<T>::remove(pool_id, asset_id);
+        }
+        
+        // Emit event
+        Self::deposit_event(Event::PriceFeedCleared { asset_id });
+        
+        Ok(())
+    }
+    
+    /// Submit an ELXR price derived from the telemetry of healthy fermentation batches
+    ///
+    /// The price is the mean valuation of the healthy evidence batches; confidence
+    /// scales with how many of the allowed evidence batches are healthy, capped by
+    /// the submitter's stake as for signed submissions.
+    #[pallet::call_index(5)]
+    #[pallet::weight(T::WeightInfo::submit_derived_price(evidence_batches.len() as u32))]
+    pub fn submit_derived_price(
+        origin: OriginFor<T>,
+        asset_id: AssetId,
+        evidence_batches: BoundedVec<BatchId, T::MaxEvidenceBatches>,
+    ) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        
+        ensure!(!Paused::<T>::get(), Error::<T>::OraclePaused);
+        
+        // Verify validator status
+        let validator = Validators::<T>::get(&who).ok_or(Error::<T>::NotValidator)?;
+        Self::ensure_trusted(&who, asset_id)?;
+        
+        
+        ensure!(T::DerivedPriceAssets::contains(&asset_id), Error::<T>::AssetNotDerivable);
+        
+        // A batch listed twice would count twice towards confidence
+        for (i, batch_id) in evidence_batches.iter().enumerate() {
+            ensure!(!evidence_batches[..i].contains(batch_id), Error::<T>::DuplicateEvidenceBatch);
+        }
+        
+        // Only healthy batches count as evidence
+        let valuations: Vec<Balance<T>> = evidence_batches
+            .iter()
+            .filter_map(|batch_id| T::Fermentation::healthy_batch_valuation(batch_id))
+            .collect();
+        ensure!(!valuations.is_empty(), Error::<T>::NoHealthyEvidence);
+        
+        let healthy = valuations.len() as u32;
+        let total = valuations.iter().fold(Zero::zero(), |acc: Balance<T>, v| acc.saturating_add(*v));
+        let price = total / healthy.into();
+        let confidence = Percent::from_rational(healthy, T::MaxEvidenceBatches::get().max(1))
+            .min(Self::confidence_cap(validator.stake));
+        
+        // Evidence is on-chain, so there is no off-chain signature to record
+        Self::apply_price_submission(who, asset_id, price, confidence, SignatureScheme::Unsigned, Vec::new())
+    }
+    
+    /// Set the order in which price sources are tried when resolving an asset's price
+    #[pallet::call_index(6)]
+    #[pallet::weight(T::WeightInfo::set_fallback_sources())]
+    pub fn set_fallback_sources(
+        origin: OriginFor<T>,
+        asset_id: AssetId,
+        sources: Vec<PriceSource>,
+    ) -> DispatchResult {
+        ensure_root(origin)?;
+        
+        ensure!(
+            !sources.is_empty() && sources.len() <= DEFAULT_PRICE_SOURCES.len(),
+            Error::<T>::InvalidFallbackSources
+        );
+        for (i, source) in sources.iter().enumerate() {
+            ensure!(!sources[..i].contains(source), Error::<T>::InvalidFallbackSources);
+        }
+        
+        FallbackSources::<T>::insert(asset_id, sources.clone());
+        
+        // Emit event
+        Self::deposit_event(Event::FallbackSourcesSet { asset_id, sources });
+        
+        Ok(())
+    }
+    
+    /// Check that a feed's quantum proof still recovers to its stored price
+    ///
+    /// Anyone may challenge a feed; only the first challenge exposing a given
+    /// mismatched proof is free, so repeating it cannot be used as free spam.
+    #[pallet::call_index(7)]
+    #[pallet::weight(T::WeightInfo::verify_quantum_proof())]
+    pub fn verify_quantum_proof(origin: OriginFor<T>, asset_id: AssetId) -> DispatchResultWithPostInfo {
+        ensure_signed(origin)?;
+        
+        let feed = PriceFeeds::<T>::get(asset_id).ok_or(Error::<T>::PriceFeedNotFound)?;
+        
+        let recovered = error_correction::quantum::recover(&feed.quantum_proof)
+            .filter(|bytes| bytes.len() == Balance::<T>::max_encoded_len())
+            .and_then(|bytes| Balance::<T>::decode(&mut &bytes[..]).ok());
+        let matches = recovered == Some(feed.price);
+        
+        let proof_hash = sp_io::hashing::blake2_256(&feed.quantum_proof);
+        let newly_reported = !matches && ReportedProofMismatches::<T>::get(asset_id) != Some(proof_hash);
+        if newly_reported {
+            ReportedProofMismatches::<T>::insert(asset_id, proof_hash);
+        }
+        
+        // Emit event
+        Self::deposit_event(Event::QuantumProofVerified { asset_id, matches });
+        
+        let pays_fee = if newly_reported { Pays::No } else { Pays::Yes };
+        Ok(pays_fee.into())
+    }
+    
+    /// Roll a corrupted feed back to its last finalized price
+    #[pallet::call_index(8)]
+    #[pallet::weight(T::WeightInfo::restore_feed())]
+    pub fn restore_feed(origin: OriginFor<T>, asset_id: AssetId) -> DispatchResult {
+        ensure_root(origin)?;
+        
+        let mut feed = PriceFeeds::<T>::get(asset_id).ok_or(Error::<T>::PriceFeedNotFound)?;
+        let (price, finalized_at) = LastKnownGood::<T>::get(asset_id).ok_or(Error::<T>::NoLastKnownGood)?;
+        
+        feed.price = price;
+        feed.timestamp = finalized_at;
+        feed.quantum_proof = error_correction::quantum::protect(&price.encode());
+        PriceFeeds::<T>::insert(asset_id, feed);
+        ReportedProofMismatches::<T>::remove(asset_id);
+        
+        // Emit event
+        Self::deposit_event(Event::FeedRestored { asset_id, price });
+        
+        Ok(())
+    }
+    
+    /// Pay out the rewards accrued for contributing to finalized prices
+    #[pallet::call_index(9)]
+    #[pallet::weight(T::WeightInfo::claim_rewards())]
+    pub fn claim_rewards(origin: OriginFor<T>) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        
+        let amount = PendingRewards::<T>::get(&who);
+        ensure!(!amount.is_zero(), Error::<T>::NoRewards);
+        
+        // Fails, leaving the rewards accrued, if the pool cannot cover them
+        T::Currency::transfer(&Self::reward_pool_account(), &who, amount, ExistenceRequirement::KeepAlive)?;
+        PendingRewards::<T>::remove(&who);
+        TotalPendingRewards::<T>::mutate(|total| *total = total.saturating_sub(amount));
+        
+        // Emit event
+        Self::deposit_event(Event::RewardsClaimed { account_id: who, amount });
+        
+        Ok(())
+    }
+    
+    /// Halt or resume price submissions chain-wide; reads are unaffected
+    #[pallet::call_index(10)]
+    #[pallet::weight(T::WeightInfo::set_paused())]
+    pub fn set_paused(origin: OriginFor<T>, paused: bool) -> DispatchResult {
+        ensure_root(origin)?;
+        
+        Paused::<T>::put(paused);
+        
+        // Emit event
+        if paused {
+            Self::deposit_event(Event::OraclePaused);
+        } else {
+            Self::deposit_event(Event::OracleResumed);
+        }
+        
+        Ok(())
+    }
+    
+    /// Register the Falcon-512 public key the validator signs `Falcon` submissions with
+    #[pallet::call_index(11)]
+    #[pallet::weight(T::WeightInfo::set_falcon_key())]
+    pub fn set_falcon_key(origin: OriginFor<T>, public_key: Vec<u8>) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        
+        ensure!(Validators::<T>::contains_key(&who), Error::<T>::NotValidator);
+        ensure!(public_key.len() == FALCON_PUBLIC_KEY_LEN, Error::<T>::InvalidFalconKey);
+        
+        FalconKeys::<T>::insert(&who, public_key);
+        
+        // Emit event
+        Self::deposit_event(Event::FalconKeySet { account_id: who });
+        
+        Ok(())
+    }
+    
+    /// Register the Dilithium3 public key the validator signs `Dilithium3` submissions with,
+    /// replacing the placeholder key it was registered with
+    #[pallet::call_index(12)]
+    #[pallet::weight(T::WeightInfo::set_dilithium_key())]
+    pub fn set_dilithium_key(origin: OriginFor<T>, public_key: Vec<u8>) -> DispatchResult {
+        let who = ensure_signed(origin)?;
+        
+        let mut validator = Validators::<T>::get(&who).ok_or(Error::<T>::NotValidator)?;
+        ensure!(public_key.len() == DILITHIUM_PUBLIC_KEY_LEN, Error::<T>::InvalidDilithiumKey);
+        
+        validator.dilithium_public_key = public_key;
+        Validators::<T>::insert(&who, validator);
+        
+        // Emit event
+        Self::deposit_event(Event::DilithiumKeySet { account_id: who });
+        
+        Ok(())
+    }
+}
+
+// Implementation of helper functions
+impl<T: Config> Pallet<T> {
+    /// Migrate from the baseline layout: tag every signature as Dilithium3, the only
+    /// scheme the baseline accepted, and move confidence from a raw u8 to `Percent`,
+    /// clamping out-of-range values. Every existing signature contributed at the
+    /// feed's confidence. Rounds that were already finalized are closed, so their
+    /// contributors can submit again, and `ValidatorCount` is seeded with the
+    /// validators registered before it was tracked.
+    fn migrate_to_v1() -> Weight {
+        let mut migrated = 0u64;
+        PriceFeeds::<T>::translate::<PriceFeedV0<T>, _>(|_, old| {
+            migrated += 1;
+            let confidence = Percent::from_percent(old.confidence.min(100));
+            let signatures = if old.timestamp.is_zero() {
+                old.signatures
+                    .into_iter()
+                    .map(|(validator, signature)| (validator, SignatureScheme::Dilithium3, signature, confidence))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            Some(PriceFeed {
+                asset_id: old.asset_id,
+                price: old.price,
+                timestamp: old.timestamp,
+                confidence,
+                signatures,
+                quantum_proof: old.quantum_proof,
+            })
+        });
+        
+        let count = Validators::<T>::iter_keys().count() as u32;
+        ValidatorCount::<T>::put(count);
+        OracleVersion::<T>::put(1);
+        
+        T::DbWeight::get().reads_writes(migrated + count as u64, migrated + 2)
+    }
+    
+    /// Message a validator signs for a price submission: the encoded
+    /// `(asset_id, price, confidence)` under every error correction level
+    pub fn signing_payload(asset_id: AssetId, price: Balance<T>, confidence: Percent) -> Vec<u8> {
+        let message = (asset_id, price, confidence).encode();
+        let encoded_message = error_correction::classical::encode(&message, 4);
+        let bridge_encoded = error_correction::bridge::encode(&encoded_message);
+        error_correction::quantum::protect(&bridge_encoded)
+    }
+    
+    /// Whether governance has verified the feed for `asset_id`
+    pub fn is_feed_verified(asset_id: AssetId) -> bool {
+        VerificationStatuses::<T>::get(VerificationSubject::Asset(asset_id)) == Some(VerificationStatus::Verified)
+    }
+    
+    /// Reject submissions to unverified feeds and from validators that failed verification
+    fn ensure_trusted(who: &T::AccountId, asset_id: AssetId) -> DispatchResult {
+        ensure!(Self::is_feed_verified(asset_id), Error::<T>::AssetNotVerified);
+        ensure!(
+            VerificationStatuses::<T>::get(VerificationSubject::Validator(who.clone())) != Some(VerificationStatus::Failed),
+            Error::<T>::ValidatorNotTrusted
+        );
+        Ok(())
+    }
+    
+    /// Reserve the stake and register `who` as a validator with fresh quantum keys
+    fn do_register_validator(who: T::AccountId, stake: BalanceOf<T>) -> DispatchResult {
+        // Registering twice would double-reserve and inflate the validator count
+        ensure!(!Validators::<T>::contains_key(&who), Error::<T>::AlreadyValidator);
+        
         // Check minimum stake
         ensure!(stake >= T::MinStake::get(), Error::<T>::InsufficientStake);
+        ensure!(stake <= T::MaxStakePerValidator::get(), Error::<T>::StakeExceedsMaximum);
         
         // Reserve stake
         T::Currency::reserve(&who, stake)?;
@@ -278,6 +1026,8 @@ impl<T: Config> Pallet<T> {
         
         Validators::<T>::insert(&who, validator_info);
         ValidatorStakes::<T>::insert(&who, stake);
+        // Kept alongside `Validators` so submissions avoid an O(n) scan
+        ValidatorCount::<T>::mutate(|count| *count = count.saturating_add(1));
         
         // Store quantum keys securely
         // In production, this would need secure key management
@@ -292,121 +1042,170 @@ impl<T: Config> Pallet<T> {
         Ok(())
     }
     
-    /// Submit a price update for an asset
-    #[pallet::call_index(1)]
-    #[pallet::weight(T::WeightInfo::submit_price_update())]
-    pub fn submit_price_update(
-        origin: OriginFor<T>,
+    /// Record a validator's price submission and finalize the feed once consensus is reached
+    fn apply_price_submission(
+        who: T::AccountId,
         asset_id: AssetId,
         price: Balance<T>,
-        confidence: u8,
+        confidence: Percent,
+        scheme: SignatureScheme,
         signature: Vec<u8>,
     ) -> DispatchResult {
-        let who = ensure_signed(origin)?;
-        
-        // Verify validator status
-        let validator = Validators::<T>::get(&who).ok_or(Error::<T>::NotValidator)?;
-        
-        // Verify signature using Dilithium
-        let message = (asset_id, price, confidence).encode();
-        let dilithium_public = crypto::DilithiumPublicKey(validator.dilithium_public_key.clone());
-        let signature = crypto::DilithiumSignature(signature);
-        
-        // Use error correction for verification
-        let encoded_message = error_correction::classical::encode(&message, 4);
-        let bridge_encoded = error_correction::bridge::encode(&encoded_message, 2);
-        let quantum_protected = error_correction::quantum::protect(&bridge_encoded);
-        
-        ensure!(
-            crypto::dilithium_verify(&dilithium_public, &quantum_protected, &signature),
-            Error::<T>::InvalidSignature
-        );
+        let now = <frame_system::Pallet<T>>::block_number();
+        if let Some(last) = LastSubmission::<T>::get(&who, asset_id) {
+            ensure!(
+                now.saturating_sub(last) >= T::MinBlocksBetweenSubmissions::get(),
+                Error::<T>::SubmittingTooFast
+            );
+        }
         
         // Get existing price feed or create new one
         let mut feed = PriceFeeds::<T>::get(asset_id).unwrap_or_else(|| PriceFeed::<T> {
             asset_id,
             price: Zero::zero(),
             timestamp: Zero::zero(),
-            confidence: 0,
+            confidence: Percent::zero(),
             signatures: Vec::new(),
             quantum_proof: Vec::new(),
         });
         
         // Ensure no duplicate signature
         ensure!(
-            !feed.signatures.iter().any(|(validator, _)| validator == &who),
+            !feed.signatures.iter().any(|(validator, _, _, _)| validator == &who),
             Error::<T>::DuplicateSignature
         );
         
+        // All validation happens before any storage write or event, so a failing
+        // check never leaves a partially applied update behind
+        let total_validators = ValidatorCount::<T>::get();
+        ensure!(total_validators >= T::MinValidators::get(), Error::<T>::ConsensusNotReached);
+        
         // Add signature
-        feed.signatures.push((who.clone(), signature.0));
+        LastSubmission::<T>::insert(&who, asset_id, now);
+        feed.signatures.push((who, scheme, signature, confidence));
         
         // Check if consensus is reached
-        let total_validators = Validators::<T>::iter().count() as u32;
-        ensure!(total_validators >= T::MinValidators::get(), Error::<T>::ConsensusNotReached);
-        
         let threshold = T::ConsensusThreshold::get();
         let signatures_count = feed.signatures.len() as u32;
+        let consensus_reached = Percent::from_rational(signatures_count, total_validators) >= threshold;
         
-        if Percent::from_rational(signatures_count, total_validators) >= threshold {
+        if consensus_reached {
             // Consensus reached, update price feed
             feed.price = price;
-            feed.timestamp = <frame_system::Pallet<T>>::block_number();
-            feed.confidence = confidence;
+            feed.timestamp = now;
+            // A price is only as trustworthy as its least confident contributor
+            feed.confidence = feed
+                .signatures
+                .iter()
+                .map(|(_, _, _, confidence)| *confidence)
+                .min()
+                .unwrap_or(confidence);
             
             // Update quantum proof with surface code protection
             let price_data = price.encode();
             feed.quantum_proof = error_correction::quantum::protect(&price_data);
+        }
+        
+        // Store updated feed. A finalized price closes the round: its signatures are
+        // cleared so every validator may sign the next one.
+        let finalized_at = feed.timestamp;
+        let finalized_confidence = feed.confidence;
+        let contributors: Vec<T::AccountId> = if consensus_reached {
+            feed.signatures.drain(..).map(|(validator, _, _, _)| validator).collect()
+        } else {
+            Vec::new()
+        };
+        PriceFeeds::<T>::insert(asset_id, feed);
+        
+        if consensus_reached {
+            Self::record_finalized_price(asset_id, price, finalized_at);
+            Self::accrue_rewards(&contributors);
+        }
+        
+        if consensus_reached {
+            // Update liquidity pool prices if applicable; dispatch is transactional,
+            // so a failure here also reverts the feed written above
+            Self::update_liquidity_pool_prices(asset_id, price)?;
             
-            // Emit event
-            Self::deposit_event(Event::PriceUpdated {
+            // Emit event once every write has succeeded, indexed by asset so
+            // subscribers can filter feeds server-side
+            Self::deposit_event_with_topic(&asset_id, Event::PriceUpdated {
                 asset_id,
                 price,
-                confidence,
+                confidence: finalized_confidence,
             });
-            
-            // Update liquidity pool prices if applicable
-            Self::update_liquidity_pool_prices(asset_id, price)?;
         }
         
-        // Store updated feed
-        PriceFeeds::<T>::insert(asset_id, feed);
-        
         Ok(())
     }
     
-    /// Increase validator stake
-    #[pallet::call_index(2)]
-    #[pallet::weight(T::WeightInfo::increase_stake())]
-    pub fn increase_stake(origin: OriginFor<T>, additional_stake: BalanceOf<T>) -> DispatchResult {
-        let who = ensure_signed(origin)?;
-        
-        // Verify validator status
-        let mut validator = Validators::<T>::get(&who).ok_or(Error::<T>::NotValidator)?;
-        
-        // Reserve additional stake
-        T::Currency::reserve(&who, additional_stake)?;
-        
-        // Update validator info
-        validator.stake = validator.stake.checked_add(&additional_stake)
-            .ok_or(ArithmeticError::Overflow)?;
+    /// Highest confidence a validator with `stake` may submit: linear in stake, reaching
+    /// 100% at `FullConfidenceStakeMultiple` times `MinStake`
+    pub fn confidence_cap(stake: Balance<T>) -> Percent {
+        let full_confidence_stake = T::MinStake::get().saturating_mul(T::FullConfidenceStakeMultiple::get().into());
+        if full_confidence_stake.is_zero() {
+            return Percent::one();
+        }
+        Percent::from_rational(stake, full_confidence_stake)
+    }
+    
+    /// Account holding the reward pool, funded by the treasury or liquidity profits
+    pub fn reward_pool_account() -> T::AccountId {
+        T::PalletId::get().into_account_truncating()
+    }
+    
+    /// Split the per-finalization reward across the validators that signed it, by stake
+    fn accrue_rewards(contributors: &[T::AccountId]) {
+        let stakes: Vec<(&T::AccountId, Balance<T>)> = contributors
+            .iter()
+            .filter_map(|who| ValidatorStakes::<T>::get(who).map(|stake| (who, stake)))
+            .collect();
+        let total_stake = stakes.iter().fold(Zero::zero(), |acc: Balance<T>, (_, stake)| acc.saturating_add(*stake));
+        if total_stake.is_zero() {
+            return;
+        }
         
-        Validators::<T>::insert(&who, validator.clone());
-        ValidatorStakes::<T>::insert(&who, validator.stake);
+        // Never promise more than the pool can pay out while staying alive
+        let uncommitted = T::Currency::free_balance(&Self::reward_pool_account())
+            .saturating_sub(T::Currency::minimum_balance())
+            .saturating_sub(TotalPendingRewards::<T>::get());
+        let reward = T::RewardPerFinalization::get().min(uncommitted);
+        if reward.is_zero() {
+            return;
+        }
         
-        // Emit event
-        Self::deposit_event(Event::StakeIncreased {
-            account_id: who,
-            additional_stake,
-            total_stake: validator.stake,
+        let mut accrued: Balance<T> = Zero::zero();
+        for (who, stake) in stakes {
+            let share = Perbill::from_rational(stake, total_stake) * reward;
+            accrued = accrued.saturating_add(share);
+            PendingRewards::<T>::mutate(who, |pending| *pending = pending.saturating_add(share));
+        }
+        TotalPendingRewards::<T>::mutate(|total| *total = total.saturating_add(accrued));
+    }
+    
+    /// Deposit an event with the hash of `subject`'s encoding as its topic
+    fn deposit_event_with_topic<S: Encode>(subject: &S, event: Event<T>) {
+        let topic = T::Hashing::hash_of(subject);
+        let event: <T as Config>::RuntimeEvent = event.into();
+        <frame_system::Pallet<T>>::deposit_event_indexed(&[topic], event.into());
+    }
+    
+    /// Keep the finalized price as a TWAP sample and last known good value
+    fn record_finalized_price(asset_id: AssetId, price: Balance<T>, now: T::BlockNumber) {
+        let window_start = now.saturating_sub(T::TwapWindow::get());
+        PriceSamples::<T>::mutate(asset_id, |samples| {
+            // Keep the last sample before the window: it was still the prevailing
+            // price when the window opened
+            let first_in_window = samples
+                .iter()
+                .position(|(at, _, _)| *at >= window_start)
+                .unwrap_or(samples.len());
+            samples.drain(..first_in_window.saturating_sub(1));
+            samples.push((now, Self::now_millis(), price));
         });
-        
-        Ok(())
+        LastKnownGood::<T>::insert(asset_id, (price, now));
     }
-}
-
-// Implementation of helper functions
-impl<T: Config> Pallet<T> {
+    
     /// Update liquidity pool prices based on oracle data
     fn update_liquidity_pool_prices(asset_id: AssetId, price: Balance<T>) -> DispatchResult {
         // In a real implementation, this would connect to the liquidity module
@@ -435,16 +1234,146 @@ impl<T: Config> Pallet<T> {
         PriceFeeds::<T>::get(asset_id).map(|feed| feed.price)
     }
     
+    /// Current quote for each of `asset_ids`, in the order given
+    pub fn prices(asset_ids: Vec<AssetId>) -> Vec<(AssetId, Option<PriceQuote<Balance<T>, T::BlockNumber>>)> {
+        asset_ids
+            .into_iter()
+            .map(|asset_id| {
+                let quote = PriceFeeds::<T>::get(asset_id).map(|feed| PriceQuote {
+                    price: feed.price,
+                    updated_at: feed.timestamp,
+                    confidence: feed.confidence,
+                });
+                (asset_id, quote)
+            })
+            .collect()
+    }
+    
+    /// Confidence of the asset's finalized price, decayed linearly with its age
+    /// over `ConfidenceDecayBlocks`
+    pub fn effective_confidence(asset_id: AssetId) -> Option<Percent> {
+        let feed = PriceFeeds::<T>::get(asset_id)?;
+        let window: u64 = T::ConfidenceDecayBlocks::get().saturated_into();
+        if window == 0 {
+            return Some(feed.confidence);
+        }
+        
+        let age: u64 = <frame_system::Pallet<T>>::block_number()
+            .saturating_sub(feed.timestamp)
+            .saturated_into();
+        let remaining = window.saturating_sub(age);
+        let decayed = u64::from(feed.confidence.deconstruct()) * remaining / window;
+        Some(Percent::from_parts(decayed as u8))
+    }
+    
+    /// Current wall-clock time in milliseconds
+    fn now_millis() -> u64 {
+        T::UnixTime::now().as_millis().saturated_into()
+    }
+    
+    /// Time-weighted average price over the TWAP window
+    pub fn get_twap(asset_id: AssetId) -> Option<Balance<T>> {
+        let window_ms = T::TwapWindow::get().saturated_into::<u64>().saturating_mul(T::BlockTimeMillis::get());
+        Self::get_twap_over(asset_id, window_ms)
+    }
+    
+    /// Average price over the last `duration_ms`, each sample weighted by how long it
+    /// was the prevailing price. `None` if no price was finalized within the window.
+    pub fn get_twap_over(asset_id: AssetId, duration_ms: u64) -> Option<Balance<T>> {
+        let now = Self::now_millis();
+        let window_start = now.saturating_sub(duration_ms);
+        
+        let samples = PriceSamples::<T>::get(asset_id);
+        let (_, latest_ms, latest_price) = *samples.last()?;
+        if latest_ms < window_start {
+            return None;
+        }
+        
+        let mut weighted: Balance<T> = Zero::zero();
+        let mut total_ms: u64 = 0;
+        for (i, (_, at_ms, price)) in samples.iter().enumerate() {
+            // A sample prevails from its finalization until the next sample, clipped to the window
+            let start = (*at_ms).max(window_start);
+            let end = samples.get(i + 1).map(|(_, next_ms, _)| *next_ms).unwrap_or(now);
+            let held = end.saturating_sub(start);
+            weighted = weighted.saturating_add(price.saturating_mul(held.saturated_into()));
+            total_ms = total_ms.saturating_add(held);
+        }
+        
+        // Zero-length window: the newest price is the only one prevailing
+        if total_ms == 0 {
+            return Some(latest_price);
+        }
+        Some(weighted / total_ms.saturated_into())
+    }
+    
+    /// Resolve a price by trying the asset's price sources in order, returning the
+    /// first one available together with the source it came from. Feeds that are
+    /// not `Verified` never resolve.
+    pub fn resolve_price(asset_id: AssetId) -> Option<(Balance<T>, PriceSource)> {
+        if !Self::is_feed_verified(asset_id) {
+            return None;
+        }
+        let sources = FallbackSources::<T>::get(asset_id).unwrap_or_else(|| DEFAULT_PRICE_SOURCES.to_vec());
+        let now = <frame_system::Pallet<T>>::block_number();
+        
+        sources.into_iter().find_map(|source| {
+            let price = match source {
+                PriceSource::PrimaryFeed => PriceFeeds::<T>::get(asset_id)
+                    .filter(|feed| !feed.timestamp.is_zero())
+                    .filter(|feed| now.saturating_sub(feed.timestamp) <= T::MaxPriceAge::get())
+                    .map(|feed| feed.price),
+                PriceSource::Twap => Self::get_twap(asset_id),
+                PriceSource::LastKnownGood => LastKnownGood::<T>::get(asset_id).map(|(price, _)| price),
+            };
+            price.map(|price| (price, source))
+        })
+    }
+    
     /// Get the price with error correction capabilities
+    ///
+    /// Read-only: falls back to the stored price when recovery fails, and only logs
+    /// recovered bytes that do not decode. Dispatchables should use
+    /// `get_asset_price_with_correction_reported`, which also emits `PriceDecodeFailed`.
     pub fn get_asset_price_with_correction(asset_id: AssetId) -> Option<Balance<T>> {
-        PriceFeeds::<T>::get(asset_id).and_then(|feed| {
-            // Apply quantum error correction to recover potentially corrupted price
-            let quantum_protected = &feed.quantum_proof;
-            error_correction::quantum::recover(quantum_protected)
-                .and_then(|recovered| {
-                    Balance::<T>::decode(&mut &recovered[..]).ok()
-                })
-                .or(Some(feed.price)) // Fallback to stored price if recovery fails
+        Self::recover_asset_price(asset_id).map(|recovery| match recovery {
+            Ok(price) => price,
+            Err(stored) => {
+                frame_support::log::warn!(
+                    target: "runtime::elixir-oracle",
+                    "recovered price of asset {:?} did not decode; using the stored price",
+                    asset_id,
+                );
+                stored
+            }
+        })
+    }
+
+    /// As `get_asset_price_with_correction`, depositing `PriceDecodeFailed` when the
+    /// recovered bytes do not decode. Only for use from dispatchables.
+    pub fn get_asset_price_with_correction_reported(asset_id: AssetId) -> Option<Balance<T>> {
+        Self::recover_asset_price(asset_id).map(|recovery| {
+            recovery.unwrap_or_else(|stored| {
+                Self::deposit_event(Event::PriceDecodeFailed { asset_id });
+                stored
+            })
+        })
+    }
+
+    // Price recovered from the feed's quantum proof, or the stored price if recovery
+    // fails. `Err` holds the stored price when the recovered bytes did not decode.
+    // Unverified feeds have no price.
+    fn recover_asset_price(asset_id: AssetId) -> Option<Result<Balance<T>, Balance<T>>> {
+        PriceFeeds::<T>::get(asset_id).filter(|_| Self::is_feed_verified(asset_id)).map(|feed| {
+            match error_correction::quantum::recover(&feed.quantum_proof) {
+                // Recovery can "succeed" on corrupted input and hand back garbage;
+                // anything but a single, exactly sized balance is a decode failure
+                Some(recovered) if recovered.len() == Balance::<T>::max_encoded_len() => {
+                    Balance::<T>::decode(&mut &recovered[..]).map_err(|_| feed.price)
+                }
+                Some(_) => Err(feed.price),
+                None => Ok(feed.price),
+            }
         })
     }
 }
@@ -452,8 +1381,20 @@ impl<T: Config> Pallet<T> {
 // WeightInfo trait for the pallet
 pub trait WeightInfo {
     fn register_validator() -> Weight;
+    /// Includes one `pq_crypto::dilithium_verify` host call; the signature and public
+    /// key are copied across the Wasm boundary, so benchmarks must use full-size keys.
     fn submit_price_update() -> Weight;
     fn increase_stake() -> Weight;
+    fn set_verification_status() -> Weight;
+    fn clear_price_feed() -> Weight;
+    fn submit_derived_price(batches: u32) -> Weight;
+    fn set_fallback_sources() -> Weight;
+    fn verify_quantum_proof() -> Weight;
+    fn restore_feed() -> Weight;
+    fn claim_rewards() -> Weight;
+    fn set_paused() -> Weight;
+    fn set_falcon_key() -> Weight;
+    fn set_dilithium_key() -> Weight;
 }
 
 // Implement default weights
@@ -469,4 +1410,44 @@ impl WeightInfo for () {
     fn increase_stake() -> Weight {
         Weight::from_parts(10_000, 0)
     }
+    
+    fn set_verification_status() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn clear_price_feed() -> Weight {
+        Weight::from_parts(25_000, 0)
+    }
+    
+    fn submit_derived_price(batches: u32) -> Weight {
+        Weight::from_parts(15_000, 0).saturating_add(Weight::from_parts(2_000, 0).saturating_mul(batches as u64))
+    }
+    
+    fn set_fallback_sources() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn verify_quantum_proof() -> Weight {
+        Weight::from_parts(15_000, 0)
+    }
+    
+    fn restore_feed() -> Weight {
+        Weight::from_parts(15_000, 0)
+    }
+    
+    fn claim_rewards() -> Weight {
+        Weight::from_parts(20_000, 0)
+    }
+    
+    fn set_paused() -> Weight {
+        Weight::from_parts(5_000, 0)
+    }
+    
+    fn set_falcon_key() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
+    
+    fn set_dilithium_key() -> Weight {
+        Weight::from_parts(10_000, 0)
+    }
 }
@@ -11,10 +11,10 @@ use frame_support::{
     ensure,
     pallet_prelude::*,
     traits::{Currency, ExistenceRequirement, Get},
-    weights::Weight,
+    weights::{constants::RocksDbWeight, Weight},
 };
 use frame_system::pallet_prelude::*;
-use sp_runtime::{traits::Zero, DispatchError, Percent};
+use sp_runtime::{traits::Zero, DispatchError, FixedPointNumber, FixedU128, Percent};
 use sp_std::prelude::*;
 
 // Import crate and external dependencies
@@ -32,13 +32,22 @@ mod error_correction {
     // Re-export error correction from oracle pallet
     pub use super::oracle::error_correction::*;
     
-    // Additional bridge-specific error correction for oracle-liquidity communication
-    pub fn protect_price_data(price_data: &[u8]) -> Vec<u8> {
+    /// Largest multiple of its input size `protect_price_data` may expand a payload to
+    pub const MAX_EXPANSION_FACTOR: usize = 8;
+    
+    // Additional bridge-specific error correction for oracle-liquidity communication.
+    // `None` if the protected payload would exceed `MAX_EXPANSION_FACTOR` times the
+    // input; the fixed parity and metadata overhead rules out very small payloads.
+    pub fn protect_price_data(price_data: &[u8]) -> Option<Vec<u8>> {
+        if protected_len_estimate(price_data.len()) > price_data.len().saturating_mul(MAX_EXPANSION_FACTOR) {
+            return None;
+        }
+        
         // Apply all three layers of error correction
         let classical = self::classical::encode(price_data, 8); // Higher redundancy
-        let bridge = self::bridge::encode(&classical, 4);       // Higher redundancy
+        let bridge = self::bridge::encode(&classical);
         let quantum = self::quantum::protect(&bridge);
-        quantum
+        Some(quantum)
     }
     
     pub fn recover_price_data(protected_data: &[u8]) -> Option<Vec<u8>> {
@@ -46,6 +55,55 @@ mod error_correction {
         self::quantum::recover(protected_data)
             .and_then(|quantum_recovered| self::bridge::decode(&quantum_recovered))
             .and_then(|bridge_recovered| self::classical::decode(&bridge_recovered))
+            .map(|(price_data, _)| price_data)
+    }
+    
+    // Length of `protect_price_data` output, for sizing buffers and fees
+    pub fn protected_len_estimate(input_len: usize) -> usize {
+        let classical = self::classical::encoded_len_estimate(input_len);
+        let bridge = self::bridge::encoded_len_estimate(classical);
+        self::quantum::encoded_len_estimate(bridge)
+    }
+    
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        
+        #[test]
+        fn estimate_matches_protected_length() {
+            for len in [0, 1, 11, 32, 100] {
+                let payload = vec![0xAB; len];
+                let protected = self::classical::encode(&payload, 8);
+                let protected = self::quantum::protect(&self::bridge::encode(&protected));
+                assert_eq!(protected_len_estimate(len), protected.len());
+            }
+        }
+        
+        #[test]
+        fn estimate_adds_parity_doubles_and_appends_metadata() {
+            // (n + 16) * 2 + 32
+            assert_eq!(protected_len_estimate(0), 64);
+            assert_eq!(protected_len_estimate(10), 84);
+            assert_eq!(protected_len_estimate(11), 86);
+        }
+        
+        // The estimate is 2n + 64 against a limit of 8n, so the cutoff falls between
+        // 10 bytes (84 > 80) and 11 bytes (86 <= 88); no length lands exactly on it
+        #[test]
+        fn smallest_protectable_payload_is_eleven_bytes() {
+            let payload = [7u8; 11];
+            let protected = protect_price_data(&payload).unwrap();
+            
+            assert_eq!(protected.len(), 86);
+            assert!(protected.len() <= payload.len() * MAX_EXPANSION_FACTOR);
+            assert_eq!(recover_price_data(&protected), Some(payload.to_vec()));
+        }
+        
+        #[test]
+        fn payloads_below_the_cutoff_are_refused() {
+            assert_eq!(protect_price_data(&[7u8; 10]), None);
+            assert_eq!(protect_price_data(&[]), None);
+        }
     }
 }
 
@@ -62,6 +120,10 @@ pub trait Config: frame_system::Config + oracle::Config {
     /// The overarching event type
     type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
     
+    /// Lowest effective confidence of both assets' prices at which a pool's price
+    /// is overridden by the oracle
+    type MinOverrideConfidence: Get<Percent>;
+    
     /// Weight information for extrinsics
     type WeightInfo: WeightInfo;
 }
@@ -81,6 +143,19 @@ pub type OracleDrivenPools<T: Config> = StorageMap<_, Blake2_128Concat, PoolId,
 #[pallet::storage]
 pub type AssetPriceDeviations<T: Config> = StorageMap<_, Blake2_128Concat, AssetId, Percent>;
 
+/// Oracle price ratio each pool was last synchronized at, standing in for the pool's
+/// own price until it is read from the AMM
+#[pallet::storage]
+pub type SynchronizedRatios<T: Config> = StorageMap<_, Blake2_128Concat, PoolId, FixedU128>;
+
+// Pools keep reading their assets' feeds, so those feeds must not be cleared
+impl<T: Config> oracle::PriceFeedConsumers for Pallet<T> {
+    fn is_asset_in_use(asset_id: AssetId) -> bool {
+        OracleDrivenPools::<T>::iter_values()
+            .any(|pool| pool.base_asset == asset_id || pool.quote_asset == asset_id)
+    }
+}
+
 // Oracle-driven pool information
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub struct OracleDrivenPool {
@@ -100,7 +175,8 @@ pub enum Event<T: Config> {
         pool_id: PoolId,
         base_asset: AssetId,
         quote_asset: AssetId,
-        new_price: BalanceOf<T>,
+        /// Base price in units of the quote asset, as an 18-decimal fixed-point ratio
+        new_price: FixedU128,
     },
     /// New oracle-driven pool registered
     OracleDrivenPoolRegistered {
@@ -132,6 +208,8 @@ pub enum Error<T> {
     InsufficientLiquidity,
     /// Pool price deviation exceeds threshold
     ExcessiveDeviation,
+    /// Error-corrected price data would exceed the maximum expansion factor
+    ExcessiveExpansion,
 }
 
 // Calls
@@ -185,32 +263,39 @@ impl<T: Config> Pallet<T> {
     }
     
     /// Synchronize a liquidity pool with oracle prices
+    ///
+    /// Charged for the arbitrage path and refunded when no arbitrage executes.
     #[pallet::call_index(1)]
-    #[pallet::weight(T::WeightInfo::synchronize_pool())]
+    #[pallet::weight(T::WeightInfo::synchronize_pool(1))]
     pub fn synchronize_pool(
         origin: OriginFor<T>,
         pool_id: PoolId,
-    ) -> DispatchResult {
+    ) -> DispatchResultWithPostInfo {
         ensure_signed(origin)?;
         
         // Get pool info
         let pool = OracleDrivenPools::<T>::get(pool_id).ok_or(Error::<T>::PoolNotFound)?;
         
         // Get asset prices from oracle with error correction
-        let base_price = oracle::Pallet::<T>::get_asset_price_with_correction(pool.base_asset)
+        let base_price = oracle::Pallet::<T>::get_asset_price_with_correction_reported(pool.base_asset)
             .ok_or(Error::<T>::AssetPriceNotAvailable)?;
             
-        let quote_price = oracle::Pallet::<T>::get_asset_price_with_correction(pool.quote_asset)
+        let quote_price = oracle::Pallet::<T>::get_asset_price_with_correction_reported(pool.quote_asset)
             .ok_or(Error::<T>::AssetPriceNotAvailable)?;
         
         // Calculate relative price
         let price_ratio = Self::calculate_price_ratio(base_price, quote_price)?;
         
-        // Only synchronize if oracle override is allowed
-        if pool.allow_oracle_price_override {
+        // Only synchronize if oracle override is allowed and both prices are still
+        // trustworthy after confidence decay
+        let confidence_of = |asset_id| oracle::Pallet::<T>::effective_confidence(asset_id).unwrap_or_default();
+        let confident = confidence_of(pool.base_asset).min(confidence_of(pool.quote_asset))
+            >= T::MinOverrideConfidence::get();
+        if pool.allow_oracle_price_override && confident {
             // Apply multi-level error correction to the price data for transmission
             let price_data = price_ratio.encode();
-            let protected_data = error_correction::protect_price_data(&price_data);
+            let protected_data = error_correction::protect_price_data(&price_data)
+                .ok_or(Error::<T>::ExcessiveExpansion)?;
             
             // TODO: In production, this would use XCM to communicate with the AMM
             // For now, we'll simulate it with a direct call
@@ -224,10 +309,18 @@ impl<T: Config> Pallet<T> {
             });
         }
         
-        // Check for arbitrage opportunities
-        Self::check_for_arbitrage(pool_id, pool.base_asset, pool.quote_asset, price_ratio)?;
+        // Check for arbitrage opportunities once the oracle has moved away from the
+        // ratio the pool was last synchronized at by more than the pool's threshold
+        let arbitraged = match SynchronizedRatios::<T>::get(pool_id) {
+            Some(pool_price) if Self::exceeds_deviation(pool_price, price_ratio, pool.deviation_threshold) => {
+                Self::check_for_arbitrage(pool_id, pool.base_asset, pool.quote_asset, price_ratio)?;
+                true
+            }
+            _ => false,
+        };
+        SynchronizedRatios::<T>::insert(pool_id, price_ratio);
         
-        Ok(())
+        Ok(Some(T::WeightInfo::synchronize_pool(arbitraged as u32)).into())
     }
     
     /// Set price deviation threshold for an asset
@@ -249,18 +342,30 @@ impl<T: Config> Pallet<T> {
 
 // Helper functions
 impl<T: Config> Pallet<T> {
-    /// Calculate price ratio between base and quote assets
+    /// Calculate the price of the base asset in units of the quote asset
+    ///
+    /// The ratio is a `FixedU128`: the base price is scaled by 10^18 before dividing, so
+    /// a base worth half the quote yields 0.5 (inner value 5 * 10^17) instead of
+    /// truncating to zero. Consumers must read it as fixed-point, not as a balance.
     fn calculate_price_ratio(
         base_price: BalanceOf<T>,
         quote_price: BalanceOf<T>,
-    ) -> Result<BalanceOf<T>, DispatchError> {
+    ) -> Result<FixedU128, DispatchError> {
         if quote_price.is_zero() {
             return Err(ArithmeticError::DivisionByZero.into());
         }
         
-        // In a real implementation, this would handle decimal precision properly
-        // This is a simplified version
-        Ok(base_price.checked_div(&quote_price).ok_or(ArithmeticError::DivisionByZero)?)
+        FixedU128::checked_from_rational(base_price, quote_price).ok_or_else(|| ArithmeticError::Overflow.into())
+    }
+    
+    /// Whether `oracle_price` deviates from `pool_price` by more than `threshold`
+    fn exceeds_deviation(pool_price: FixedU128, oracle_price: FixedU128, threshold: Percent) -> bool {
+        let deviation = if oracle_price > pool_price {
+            oracle_price - pool_price
+        } else {
+            pool_price - oracle_price
+        };
+        deviation > pool_price.saturating_mul(FixedU128::saturating_from_rational(threshold.deconstruct(), 100))
     }
     
     /// Check for arbitrage opportunities between oracle and pool prices
@@ -268,7 +373,7 @@ impl<T: Config> Pallet<T> {
         pool_id: PoolId,
         base_asset: AssetId,
         quote_asset: AssetId,
-        oracle_price: BalanceOf<T>,
+        oracle_price: FixedU128,
     ) -> DispatchResult {
         // In a real implementation, this would:
         // 1. Get current pool price
@@ -327,21 +432,61 @@ impl<T: Config> Pallet<T> {
 // Define weight info trait
 pub trait WeightInfo {
     fn register_oracle_driven_pool() -> Weight;
-    fn synchronize_pool() -> Weight;
+    /// `a` is 1 if arbitrage executes, 0 otherwise
+    fn synchronize_pool(a: u32) -> Weight;
     fn set_deviation_threshold() -> Weight;
 }
 
-// Default weight implementation
+// Placeholder weights: the execution times below are hand-estimated, not output of
+// `oracle_liquidity_benchmarking`. Regenerate this impl with `benchmark pallet`
+// before relying on it. Storage access is charged per the runtime's `DbWeight`.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+    // Reads: OracleDrivenPools, AssetPriceDeviations (x2)
+    // Writes: OracleDrivenPools, AssetPriceDeviations (x2)
+    fn register_oracle_driven_pool() -> Weight {
+        Weight::from_parts(18_000_000, 0)
+            .saturating_add(T::DbWeight::get().reads(3))
+            .saturating_add(T::DbWeight::get().writes(3))
+    }
+    
+    // Reads: OracleDrivenPools, PriceFeeds (x2), SynchronizedRatios
+    // Writes: SynchronizedRatios
+    // Dominated by quantum recovery of both feeds and protecting the ratio.
+    // The range of component `a` is `[0, 1]`.
+    fn synchronize_pool(a: u32) -> Weight {
+        Weight::from_parts(42_000_000, 0)
+            .saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(a.into()))
+            .saturating_add(T::DbWeight::get().reads(4))
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+    
+    // Writes: AssetPriceDeviations
+    fn set_deviation_threshold() -> Weight {
+        Weight::from_parts(9_000_000, 0)
+            .saturating_add(T::DbWeight::get().writes(1))
+    }
+}
+
+// For tests and runtimes without benchmarked weights; the same hand-estimated
+// placeholders as `SubstrateWeight`, charged at RocksDB storage costs
 impl WeightInfo for () {
     fn register_oracle_driven_pool() -> Weight {
-        Weight::from_parts(10_000, 0)
+        Weight::from_parts(18_000_000, 0)
+            .saturating_add(RocksDbWeight::get().reads(3))
+            .saturating_add(RocksDbWeight::get().writes(3))
     }
     
-    fn synchronize_pool() -> Weight {
-        Weight::from_parts(15_000, 0)
+    fn synchronize_pool(a: u32) -> Weight {
+        Weight::from_parts(42_000_000, 0)
+            .saturating_add(Weight::from_parts(7_000_000, 0).saturating_mul(a.into()))
+            .saturating_add(RocksDbWeight::get().reads(4))
+            .saturating_add(RocksDbWeight::get().writes(1))
     }
     
     fn set_deviation_threshold() -> Weight {
-        Weight::from_parts(5_000, 0)
+        Weight::from_parts(9_000_000, 0)
+            .saturating_add(RocksDbWeight::get().writes(1))
     }
 }
@@ -0,0 +1,75 @@
+//! Benchmarks for the oracle-liquidity integration extrinsics.
+
+#![cfg(feature = "runtime-benchmarks")]
+
+use codec::Encode;
+use frame_benchmarking::{account, benchmarks};
+use frame_system::RawOrigin;
+use sp_runtime::{traits::Zero, FixedPointNumber, FixedU128, Percent};
+use sp_std::prelude::*;
+
+use crate::oracle::{error_correction, PriceFeed, PriceFeeds, VerificationStatuses, VerificationSubject};
+use crate::types::VerificationStatus;
+use crate::oracle_liquidity::{Call, Config, OracleDrivenPools, Pallet, SynchronizedRatios};
+use shared::liquidity::types::{AssetId, PoolId};
+
+const BASE_ASSET: AssetId = AssetId(1);
+const QUOTE_ASSET: AssetId = AssetId(2);
+const POOL: PoolId = PoolId(1);
+
+// Seed a finalized feed whose quantum proof recovers cleanly, so
+// `get_asset_price_with_correction` runs the full recovery path
+fn seed_price<T: Config>(asset_id: AssetId, price: u32) {
+    let mut feed = PriceFeed::<T> {
+        asset_id,
+        price: price.into(),
+        timestamp: Zero::zero(),
+        confidence: Percent::from_percent(100),
+        signatures: Vec::new(),
+        quantum_proof: Vec::new(),
+    };
+    feed.quantum_proof = error_correction::quantum::protect(&feed.price.encode());
+    PriceFeeds::<T>::insert(asset_id, feed);
+    VerificationStatuses::<T>::insert(VerificationSubject::Asset(asset_id), VerificationStatus::Verified);
+}
+
+fn register_pool<T: Config>(allow_oracle_override: bool) {
+    Pallet::<T>::register_oracle_driven_pool(
+        RawOrigin::Root.into(),
+        POOL,
+        BASE_ASSET,
+        QUOTE_ASSET,
+        allow_oracle_override,
+        Percent::from_percent(5),
+    )
+    .expect("pool is not registered yet");
+}
+
+benchmarks! {
+    where_clause { where T: Config }
+
+    register_oracle_driven_pool {
+    }: _(RawOrigin::Root, POOL, BASE_ASSET, QUOTE_ASSET, true, Percent::from_percent(5))
+    verify {
+        assert!(OracleDrivenPools::<T>::contains_key(POOL));
+    }
+
+    // The pool accepts oracle overrides, so the ratio is also encoded and protected
+    // for transmission. `a` is whether arbitrage executes: with `a = 1` the pool was
+    // last synchronized at half the oracle ratio, well past its 5% threshold.
+    synchronize_pool {
+        let a in 0 .. 1;
+        register_pool::<T>(true);
+        seed_price::<T>(BASE_ASSET, 2_000_000);
+        seed_price::<T>(QUOTE_ASSET, 1_000_000);
+        let pool_price = if a == 1 { FixedU128::saturating_from_integer(1u32) } else { FixedU128::saturating_from_integer(2u32) };
+        SynchronizedRatios::<T>::insert(POOL, pool_price);
+        let caller: T::AccountId = account("caller", 0, 0);
+    }: _(RawOrigin::Signed(caller), POOL)
+    verify {
+        assert_eq!(SynchronizedRatios::<T>::get(POOL), Some(FixedU128::saturating_from_integer(2u32)));
+    }
+
+    set_deviation_threshold {
+    }: _(RawOrigin::Root, BASE_ASSET, Percent::from_percent(10))
+}
@@ -0,0 +1,1199 @@
+//! Tests for the oracle pallet.
+
+use crate::mock::*;
+use crate::{types::VerificationStatus, BatchId};
+use crate::oracle::{
+    error_correction, Error, Event as OracleEvent, FallbackSources, FalconKeys, PriceFeed, LastKnownGood, LastSubmission,
+    OracleVersion, PendingRewards, PriceSource, PriceFeeds, PriceSamples, ReportedProofMismatches, SignatureScheme, TotalPendingRewards,
+    ValidatorCount, Validators, VerificationStatuses, VerificationSubject, DILITHIUM_PUBLIC_KEY_LEN, FALCON_PUBLIC_KEY_LEN, ORACLE_VERSION,
+};
+use codec::Encode;
+use frame_support::{
+    assert_noop, assert_ok,
+    dispatch::Pays,
+    traits::{ConstU32, Currency, GenesisBuild, Hooks},
+    BoundedVec,
+};
+use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+use sp_runtime::{ArithmeticError, BuildStorage, DispatchError, FixedPointNumber, FixedU128, Percent};
+
+/// Store `feed` in `PriceFeeds` under `asset_id` exactly as the baseline encoded it:
+/// `(asset_id, price, timestamp, confidence: u8, signatures: Vec<(AccountId, Vec<u8>)>, quantum_proof)`
+fn insert_baseline_feed(
+    asset_id: shared::liquidity::types::AssetId,
+    feed: (shared::liquidity::types::AssetId, Balance, u64, u8, Vec<(AccountId, Vec<u8>)>, Vec<u8>),
+) {
+    let key = PriceFeeds::<Test>::hashed_key_for(asset_id);
+    sp_io::storage::set(&key, &feed.encode());
+}
+
+#[test]
+fn migration_round_trips_baseline_feed() {
+    new_test_ext().execute_with(|| {
+        // Feeds in their first round, so their signatures survive the migration
+        insert_baseline_feed(ELXR, (ELXR, 500, 0, 70, vec![(1, vec![0xAA; 4]), (2, vec![0xBB; 4])], vec![0xEC; 8]));
+        insert_baseline_feed(NRSH, (NRSH, 900, 0, 250, vec![(3, vec![0xCC; 4])], Vec::new()));
+        assert_eq!(OracleVersion::<Test>::get(), 0);
+
+        Oracle::on_runtime_upgrade();
+
+        let feed = PriceFeeds::<Test>::get(ELXR).expect("baseline feed decodes after the migration");
+        assert_eq!(feed.price, 500);
+        assert_eq!(feed.timestamp, 0);
+        assert_eq!(feed.confidence, Percent::from_percent(70));
+        assert_eq!(feed.quantum_proof, vec![0xEC; 8]);
+        assert_eq!(
+            feed.signatures,
+            vec![
+                (1, SignatureScheme::Dilithium3, vec![0xAA; 4], Percent::from_percent(70)),
+                (2, SignatureScheme::Dilithium3, vec![0xBB; 4], Percent::from_percent(70)),
+            ]
+        );
+
+        // Out-of-range baseline confidence is clamped
+        let feed = PriceFeeds::<Test>::get(NRSH).unwrap();
+        assert_eq!(feed.confidence, Percent::from_percent(100));
+        assert_eq!(feed.signatures[0].3, Percent::from_percent(100));
+
+        assert_eq!(OracleVersion::<Test>::get(), ORACLE_VERSION);
+    });
+}
+
+#[test]
+fn migrated_feed_finalizes_at_lowest_contributor_confidence() {
+    new_test_ext().execute_with(|| {
+        insert_baseline_feed(ELXR, (ELXR, 500, 0, 70, vec![(1, vec![0xAA; 4]), (2, vec![0xBB; 4])], Vec::new()));
+        Oracle::on_runtime_upgrade();
+        register_validators(&[1, 2, 3], 1_000);
+
+        // One healthy batch of the four allowed derives a 25% confidence price
+        MockFermentation::set_healthy(b"batch-1", 800);
+        let evidence: BoundedVec<_, _> = vec![b"batch-1".to_vec()].try_into().unwrap();
+        assert_ok!(Oracle::submit_derived_price(Origin::signed(3), ELXR, evidence));
+
+        // The migrated signatures count towards consensus, then the round closes
+        let feed = PriceFeeds::<Test>::get(ELXR).unwrap();
+        assert_eq!(feed.price, 800);
+        assert!(feed.signatures.is_empty());
+        assert_eq!(feed.confidence, Percent::from_percent(25));
+    });
+}
+
+#[test]
+fn migration_runs_once() {
+    new_test_ext().execute_with(|| {
+        insert_baseline_feed(ELXR, (ELXR, 500, 1, 70, vec![(1, vec![0xAA; 4])], Vec::new()));
+        Oracle::on_runtime_upgrade();
+        let migrated = PriceFeeds::<Test>::get(ELXR).unwrap();
+
+        // A second upgrade must not try to decode the new layout as the old one
+        Oracle::on_runtime_upgrade();
+        assert_eq!(PriceFeeds::<Test>::get(ELXR), Some(migrated));
+    });
+}
+
+/// Submit an ELXR price derived from a single healthy batch worth `value`
+fn submit_derived(who: AccountId, batch_id: &[u8], value: Balance) -> frame_support::dispatch::DispatchResult {
+    MockFermentation::set_healthy(batch_id, value);
+    let evidence: BoundedVec<_, _> = vec![batch_id.to_vec()].try_into().unwrap();
+    Oracle::submit_derived_price(Origin::signed(who), ELXR, evidence)
+}
+
+#[test]
+fn migration_seeds_validator_count_and_consensus_resumes() {
+    new_test_ext().execute_with(|| {
+        // A chain whose validators registered before `ValidatorCount` existed
+        register_validators(&[1, 2, 3], 1_000);
+        ValidatorCount::<Test>::kill();
+        assert_noop!(submit_derived(1, b"batch-1", 800), Error::<Test>::ConsensusNotReached);
+
+        Oracle::on_runtime_upgrade();
+        assert_eq!(ValidatorCount::<Test>::get(), 3);
+        assert_eq!(OracleVersion::<Test>::get(), ORACLE_VERSION);
+
+        // Two of three validators pass the 60% threshold
+        assert_ok!(submit_derived(1, b"batch-1", 800));
+        assert_eq!(PriceFeeds::<Test>::get(ELXR).unwrap().timestamp, 0);
+        assert_ok!(submit_derived(2, b"batch-1", 800));
+        let feed = PriceFeeds::<Test>::get(ELXR).unwrap();
+        assert_eq!((feed.price, feed.timestamp), (800, 1));
+    });
+}
+
+#[test]
+fn rejected_submission_leaves_no_trace() {
+    new_test_ext().execute_with(|| {
+        // Two validators are below `MinValidators`
+        register_validators(&[1, 2], 1_000);
+        let events_before = System::events().len();
+
+        assert_noop!(submit_derived(1, b"batch-1", 800), Error::<Test>::ConsensusNotReached);
+
+        assert_eq!(LastSubmission::<Test>::get(1, ELXR), None);
+        assert!(PriceFeeds::<Test>::get(ELXR).is_none());
+        assert_eq!(System::events().len(), events_before);
+
+        // No cooldown was started, so the validator may submit as soon as quorum exists
+        register_validators(&[3], 1_000);
+        assert_ok!(submit_derived(1, b"batch-1", 800));
+        assert_eq!(PriceFeeds::<Test>::get(ELXR).unwrap().signatures.len(), 1);
+    });
+}
+
+#[test]
+fn dilithium3_submission_is_verified_against_validator_key() {
+    new_test_ext().execute_with(|| {
+        use pqcrypto_dilithium::dilithium3;
+
+        register_validators(&[1, 2, 3], 1_000);
+        let (public_key, secret_key) = dilithium3::keypair();
+        let confidence = Percent::from_percent(90);
+        let message = Oracle::signing_payload(NRSH, 700, confidence);
+        let signature = dilithium3::detached_sign(&message, &secret_key).as_bytes().to_vec();
+
+        // The placeholder key a validator registers with verifies nothing
+        assert_noop!(
+            Oracle::submit_price_update(
+                Origin::signed(1), NRSH, 700, confidence, SignatureScheme::Dilithium3, signature.clone()
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        assert_noop!(
+            Oracle::set_dilithium_key(Origin::signed(1), vec![0; DILITHIUM_PUBLIC_KEY_LEN - 1]),
+            Error::<Test>::InvalidDilithiumKey
+        );
+        assert_noop!(
+            Oracle::set_dilithium_key(Origin::signed(4), public_key.as_bytes().to_vec()),
+            Error::<Test>::NotValidator
+        );
+        assert_ok!(Oracle::set_dilithium_key(Origin::signed(1), public_key.as_bytes().to_vec()));
+        assert_eq!(Validators::<Test>::get(1).unwrap().dilithium_public_key, public_key.as_bytes().to_vec());
+        assert_eq!(oracle_events().last(), Some(&OracleEvent::DilithiumKeySet { account_id: 1 }));
+
+        // A signature over a different price does not verify
+        assert_noop!(
+            Oracle::submit_price_update(
+                Origin::signed(1), NRSH, 701, confidence, SignatureScheme::Dilithium3, signature.clone()
+            ),
+            Error::<Test>::InvalidSignature
+        );
+        assert_ok!(Oracle::submit_price_update(
+            Origin::signed(1), NRSH, 700, confidence, SignatureScheme::Dilithium3, signature
+        ));
+        assert_eq!(PriceFeeds::<Test>::get(NRSH).unwrap().signatures[0].1, SignatureScheme::Dilithium3);
+    });
+}
+
+/// Submit a Dilithium3-signed price for `who`, registering a fresh key for it first
+fn submit_signed(who: AccountId, asset_id: shared::liquidity::types::AssetId, price: Balance, confidence: Percent) -> frame_support::dispatch::DispatchResult {
+    use pqcrypto_dilithium::dilithium3;
+
+    let (public_key, secret_key) = dilithium3::keypair();
+    Oracle::set_dilithium_key(Origin::signed(who), public_key.as_bytes().to_vec())?;
+    let message = Oracle::signing_payload(asset_id, price, confidence);
+    let signature = dilithium3::detached_sign(&message, &secret_key).as_bytes().to_vec();
+
+    Oracle::submit_price_update(Origin::signed(who), asset_id, price, confidence, SignatureScheme::Dilithium3, signature)
+}
+
+#[test]
+fn signed_confidence_is_capped_by_stake() {
+    new_test_ext().execute_with(|| {
+        // `MinStake` is 100 and full confidence takes ten times that
+        register_validators(&[1], 100);
+        register_validators(&[2], 1_000);
+        register_validators(&[3], 500);
+
+        // A full-confidence stake asserts 100% in full
+        assert_ok!(submit_signed(2, NRSH, 700, Percent::from_percent(100)));
+        assert_eq!(PriceFeeds::<Test>::get(NRSH).unwrap().signatures[0].3, Percent::from_percent(100));
+
+        // A minimum stake is clamped to 10%, and the finalized price inherits it
+        assert_ok!(submit_signed(1, NRSH, 700, Percent::from_percent(100)));
+        let feed = PriceFeeds::<Test>::get(NRSH).unwrap();
+        assert_eq!((feed.price, feed.confidence), (700, Percent::from_percent(10)));
+
+        // Confidence below the cap is kept as submitted
+        assert_ok!(submit_signed(3, ELXR, 900, Percent::from_percent(30)));
+        assert_eq!(PriceFeeds::<Test>::get(ELXR).unwrap().signatures[0].3, Percent::from_percent(30));
+    });
+}
+
+#[test]
+fn confidence_cap_is_linear_in_stake_up_to_full_confidence() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Oracle::confidence_cap(100), Percent::from_percent(10));
+        assert_eq!(Oracle::confidence_cap(550), Percent::from_percent(55));
+        assert_eq!(Oracle::confidence_cap(1_000), Percent::from_percent(100));
+        assert_eq!(Oracle::confidence_cap(10_000), Percent::from_percent(100));
+    });
+}
+
+#[test]
+fn falcon_submission_is_verified_against_registered_key() {
+    new_test_ext().execute_with(|| {
+        use pqcrypto_falcon::falcon512;
+
+        register_validators(&[1, 2, 3], 1_000);
+        let (public_key, secret_key) = falcon512::keypair();
+        let confidence = Percent::from_percent(90);
+        let message = Oracle::signing_payload(NRSH, 700, confidence);
+        let signature = falcon512::detached_sign(&message, &secret_key).as_bytes().to_vec();
+
+        // Without a registered key there is nothing to verify against
+        assert_noop!(
+            Oracle::submit_price_update(
+                Origin::signed(1), NRSH, 700, confidence, SignatureScheme::Falcon, signature.clone()
+            ),
+            Error::<Test>::InvalidSignature
+        );
+
+        assert_noop!(
+            Oracle::set_falcon_key(Origin::signed(1), vec![0; FALCON_PUBLIC_KEY_LEN - 1]),
+            Error::<Test>::InvalidFalconKey
+        );
+        assert_noop!(
+            Oracle::set_falcon_key(Origin::signed(4), public_key.as_bytes().to_vec()),
+            Error::<Test>::NotValidator
+        );
+        assert_ok!(Oracle::set_falcon_key(Origin::signed(1), public_key.as_bytes().to_vec()));
+        assert_eq!(FalconKeys::<Test>::get(1), Some(public_key.as_bytes().to_vec()));
+
+        assert_ok!(Oracle::submit_price_update(
+            Origin::signed(1), NRSH, 700, confidence, SignatureScheme::Falcon, signature
+        ));
+        assert_eq!(PriceFeeds::<Test>::get(NRSH).unwrap().signatures[0].1, SignatureScheme::Falcon);
+    });
+}
+
+#[test]
+fn unsigned_scheme_is_rejected_for_signed_submissions() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+
+        assert_noop!(
+            Oracle::submit_price_update(
+                Origin::signed(1), NRSH, 700, Percent::from_percent(90), SignatureScheme::Unsigned, Vec::new()
+            ),
+            Error::<Test>::SignatureSchemeNotAllowed
+        );
+    });
+}
+
+/// Bound `batches` as the evidence of a derived price submission
+fn evidence(batches: &[&str]) -> BoundedVec<BatchId, ConstU32<4>> {
+    batches.iter().map(|batch_id| batch_id.as_bytes().to_vec()).collect::<Vec<_>>().try_into().unwrap()
+}
+
+#[test]
+fn derived_price_confidence_scales_with_healthy_batches() {
+    for healthy in 1..=4u8 {
+        new_test_ext().execute_with(|| {
+            register_validators(&[1, 2, 3, 4, 5], 1_000);
+            let batches = ["batch-1", "batch-2", "batch-3", "batch-4"];
+            for batch_id in &batches[..healthy as usize] {
+                MockFermentation::set_healthy(batch_id.as_bytes(), 800);
+            }
+
+            assert_ok!(Oracle::submit_derived_price(Origin::signed(1), ELXR, evidence(&batches)));
+
+            let feed = PriceFeeds::<Test>::get(ELXR).unwrap();
+            assert_eq!(feed.signatures[0].3, Percent::from_percent(25 * healthy));
+        });
+    }
+}
+
+#[test]
+fn duplicate_evidence_batches_are_rejected() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        MockFermentation::set_healthy(b"batch-1", 800);
+
+        // Listing one healthy batch four times must not claim full confidence
+        assert_noop!(
+            Oracle::submit_derived_price(
+                Origin::signed(1),
+                ELXR,
+                evidence(&["batch-1", "batch-1", "batch-1", "batch-1"])
+            ),
+            Error::<Test>::DuplicateEvidenceBatch
+        );
+    });
+}
+
+#[test]
+fn only_derivable_assets_accept_derived_prices() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        MockFermentation::set_healthy(b"batch-1", 800);
+
+        assert_noop!(
+            Oracle::submit_derived_price(Origin::signed(1), NRSH, evidence(&["batch-1"])),
+            Error::<Test>::AssetNotDerivable
+        );
+        assert_ok!(Oracle::submit_derived_price(Origin::signed(1), ELXR, evidence(&["batch-1"])));
+    });
+}
+
+#[test]
+fn derived_price_confidence_is_capped_by_stake() {
+    new_test_ext().execute_with(|| {
+        // A tenth of the full-confidence stake caps confidence at 10%
+        register_validators(&[1, 2, 3], 100);
+        let batches = ["batch-1", "batch-2", "batch-3", "batch-4"];
+        for batch_id in batches {
+            MockFermentation::set_healthy(batch_id.as_bytes(), 800);
+        }
+
+        assert_ok!(Oracle::submit_derived_price(
+            Origin::signed(1),
+            ELXR,
+            evidence(&batches)
+        ));
+
+        let feed = PriceFeeds::<Test>::get(ELXR).unwrap();
+        assert_eq!(feed.signatures[0].3, Percent::from_percent(10));
+    });
+}
+
+/// Finalize an ELXR price of 800 from validators 1 and 2 of three
+fn finalize_elxr_feed() {
+    register_validators(&[1, 2, 3], 1_000);
+    assert_ok!(submit_derived(1, b"batch-1", 800));
+    assert_ok!(submit_derived(2, b"batch-1", 800));
+}
+
+/// Replace the ELXR feed's quantum proof with one protecting `price`
+fn tamper_quantum_proof(price: Balance) {
+    PriceFeeds::<Test>::mutate(ELXR, |feed| {
+        feed.as_mut().unwrap().quantum_proof = error_correction::quantum::protect(&price.encode())
+    });
+}
+
+/// Replace the ELXR feed's quantum proof with one recovering to a wrong-length payload
+fn garble_quantum_proof() {
+    PriceFeeds::<Test>::mutate(ELXR, |feed| {
+        feed.as_mut().unwrap().quantum_proof = error_correction::quantum::protect(&[0xAB; 3])
+    });
+}
+
+#[test]
+fn price_getter_falls_back_without_depositing_events() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        garble_quantum_proof();
+        let events_before = System::events().len();
+
+        assert_eq!(Oracle::get_asset_price_with_correction(ELXR), Some(800));
+
+        assert_eq!(System::events().len(), events_before);
+    });
+}
+
+#[test]
+fn pool_synchronization_reports_undecodable_price() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        // Quote the pool against a copy of the ELXR feed
+        let mut quote = PriceFeeds::<Test>::get(ELXR).unwrap();
+        quote.asset_id = NRSH;
+        PriceFeeds::<Test>::insert(NRSH, quote);
+        garble_quantum_proof();
+        assert_ok!(OracleLiquidity::register_oracle_driven_pool(
+            Origin::root(),
+            0,
+            ELXR,
+            NRSH,
+            false,
+            Percent::from_percent(5)
+        ));
+
+        assert_ok!(OracleLiquidity::synchronize_pool(Origin::signed(4), 0));
+
+        let reports: Vec<_> = oracle_events()
+            .into_iter()
+            .filter(|event| matches!(event, OracleEvent::PriceDecodeFailed { .. }))
+            .collect();
+        assert_eq!(reports, vec![OracleEvent::PriceDecodeFailed { asset_id: ELXR }]);
+    });
+}
+
+#[test]
+fn matching_quantum_proof_pays_fee() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+
+        let info = Oracle::verify_quantum_proof(Origin::signed(4), ELXR).unwrap();
+
+        assert_eq!(info.pays_fee, Pays::Yes);
+        assert_eq!(
+            oracle_events().last(),
+            Some(&OracleEvent::QuantumProofVerified { asset_id: ELXR, matches: true })
+        );
+    });
+}
+
+#[test]
+fn first_report_of_a_mismatch_is_free() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        tamper_quantum_proof(999);
+
+        let info = Oracle::verify_quantum_proof(Origin::signed(4), ELXR).unwrap();
+
+        assert_eq!(info.pays_fee, Pays::No);
+        assert_eq!(
+            oracle_events().last(),
+            Some(&OracleEvent::QuantumProofVerified { asset_id: ELXR, matches: false })
+        );
+    });
+}
+
+#[test]
+fn repeated_mismatch_reports_pay_fee() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        tamper_quantum_proof(999);
+        assert_eq!(Oracle::verify_quantum_proof(Origin::signed(4), ELXR).unwrap().pays_fee, Pays::No);
+
+        // Re-reporting the same broken proof, from any account, is charged
+        assert_eq!(Oracle::verify_quantum_proof(Origin::signed(4), ELXR).unwrap().pays_fee, Pays::Yes);
+        assert_eq!(Oracle::verify_quantum_proof(Origin::signed(5), ELXR).unwrap().pays_fee, Pays::Yes);
+
+        // A different corruption is a new mismatch
+        tamper_quantum_proof(998);
+        assert_eq!(Oracle::verify_quantum_proof(Origin::signed(5), ELXR).unwrap().pays_fee, Pays::No);
+    });
+}
+
+#[test]
+fn restoring_a_feed_clears_reported_mismatch() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        tamper_quantum_proof(999);
+        assert_eq!(Oracle::verify_quantum_proof(Origin::signed(4), ELXR).unwrap().pays_fee, Pays::No);
+
+        assert_ok!(Oracle::restore_feed(Origin::root(), ELXR));
+        assert_eq!(ReportedProofMismatches::<Test>::get(ELXR), None);
+
+        // The same corruption recurring after the restore is reported for free again
+        tamper_quantum_proof(999);
+        assert_eq!(Oracle::verify_quantum_proof(Origin::signed(4), ELXR).unwrap().pays_fee, Pays::No);
+    });
+}
+
+#[test]
+fn twap_weights_samples_by_wall_clock_time() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        assert_eq!(Timestamp::now(), 6_000);
+
+        // 100 is finalized at block 1 and prevails through five slow blocks totalling 54 seconds
+        assert_ok!(submit_derived(1, b"batch-1", 100));
+        assert_ok!(submit_derived(2, b"batch-1", 100));
+        for _ in 0..5 {
+            advance_block(10_800);
+        }
+
+        // 400 is finalized at block 6, once the submission cooldown has passed, and
+        // prevails for a regular 6 second block
+        assert_ok!(submit_derived(1, b"batch-2", 400));
+        assert_ok!(submit_derived(2, b"batch-2", 400));
+        advance_block(BLOCK_TIME_MILLIS);
+
+        let samples = PriceSamples::<Test>::get(ELXR);
+        assert_eq!(samples, vec![(1, 6_000, 100), (6, 60_000, 400)]);
+
+        // 100 held nine times as long as 400:
+        // (100 * 54_000 + 400 * 6_000) / 60_000, not the per-block mean of 150
+        assert_eq!(Oracle::get_twap_over(ELXR, 60_000), Some(130));
+        assert_eq!(Oracle::get_twap(ELXR), Some(130));
+
+        // A shorter window only sees the tail of the first sample
+        assert_eq!(Oracle::get_twap_over(ELXR, 12_000), Some(250));
+    });
+}
+
+#[test]
+fn finalizing_a_price_starts_a_new_round() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        assert!(PriceFeeds::<Test>::get(ELXR).unwrap().signatures.is_empty());
+
+        // The validators that finalized may sign the next round once their cooldown passes
+        run_to_block(6);
+        assert_ok!(submit_derived(1, b"batch-2", 900));
+        let feed = PriceFeeds::<Test>::get(ELXR).unwrap();
+        assert_eq!(feed.price, 800);
+        assert_eq!(feed.signatures.len(), 1);
+
+        assert_ok!(submit_derived(2, b"batch-2", 900));
+        let feed = PriceFeeds::<Test>::get(ELXR).unwrap();
+        assert_eq!((feed.price, feed.timestamp), (900, 6));
+    });
+}
+
+#[test]
+fn resubmitting_within_cooldown_is_rate_limited() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+
+        // Before, a leftover signature answered every resubmission with `DuplicateSignature`
+        run_to_block(5);
+        assert_noop!(submit_derived(1, b"batch-2", 900), Error::<Test>::SubmittingTooFast);
+        run_to_block(6);
+        assert_ok!(submit_derived(1, b"batch-2", 900));
+    });
+}
+
+#[test]
+fn signing_the_open_round_twice_is_rejected() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        assert_ok!(submit_derived(1, b"batch-1", 800));
+
+        run_to_block(6);
+        assert_noop!(submit_derived(1, b"batch-1", 800), Error::<Test>::DuplicateSignature);
+    });
+}
+
+#[test]
+fn migration_closes_finalized_rounds() {
+    new_test_ext().execute_with(|| {
+        // Finalized before signatures were cleared, and a first round still open
+        insert_baseline_feed(ELXR, (ELXR, 800, 1, 25, vec![(1, vec![0xAA; 4])], Vec::new()));
+        insert_baseline_feed(NRSH, (NRSH, 800, 0, 25, vec![(1, vec![0xAA; 4])], Vec::new()));
+
+        Oracle::on_runtime_upgrade();
+
+        assert!(PriceFeeds::<Test>::get(ELXR).unwrap().signatures.is_empty());
+        assert_eq!(
+            PriceFeeds::<Test>::get(NRSH).unwrap().signatures,
+            vec![(1, SignatureScheme::Dilithium3, vec![0xAA; 4], Percent::from_percent(25))]
+        );
+        assert_eq!(OracleVersion::<Test>::get(), ORACLE_VERSION);
+    });
+}
+
+/// Externalities built from an oracle genesis config over the endowed accounts
+fn genesis_ext(config: crate::oracle::GenesisConfig<Test>) -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: ENDOWED.iter().map(|who| (*who, ENDOWMENT)).collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+    config.assimilate_storage(&mut storage).unwrap();
+    storage.into()
+}
+
+#[test]
+fn genesis_registers_and_stakes_validators() {
+    genesis_ext(crate::oracle::GenesisConfig { validators: vec![(1, 1_000), (2, 2_000)], assets: vec![ELXR] })
+        .execute_with(|| {
+            assert_eq!(ValidatorCount::<Test>::get(), 2);
+            assert_eq!(Validators::<Test>::get(1).unwrap().stake, 1_000);
+            assert_eq!(Validators::<Test>::get(2).unwrap().stake, 2_000);
+            assert_eq!(Balances::reserved_balance(1), 1_000);
+            assert_eq!(Balances::reserved_balance(2), 2_000);
+            assert_eq!(
+                VerificationStatuses::<Test>::get(VerificationSubject::Asset(ELXR)),
+                Some(VerificationStatus::Verified)
+            );
+            assert_eq!(OracleVersion::<Test>::get(), ORACLE_VERSION);
+        });
+}
+
+#[test]
+fn genesis_validation_rejects_invalid_validators() {
+    new_test_ext().execute_with(|| {
+        let config = |validators| crate::oracle::GenesisConfig::<Test> { validators, assets: Vec::new() };
+
+        assert_eq!(config(vec![(1, 1_000), (2, 1_000)]).validate(), Ok(()));
+        assert_eq!(
+            config(vec![(1, 1_000), (1, 1_000)]).validate(),
+            Err("validator listed more than once")
+        );
+        assert_eq!(config(vec![(1, 99)]).validate(), Err("validator stake below MinStake"));
+        assert_eq!(config(vec![(1, 10_001)]).validate(), Err("validator stake above MaxStakePerValidator"));
+        // Account 7 has no endowment
+        assert_eq!(config(vec![(7, 1_000)]).validate(), Err("validator endowment does not cover its stake"));
+    });
+}
+
+#[test]
+#[should_panic(expected = "Invalid oracle genesis config: validator endowment does not cover its stake")]
+fn genesis_with_unendowed_validator_fails_to_build() {
+    genesis_ext(crate::oracle::GenesisConfig { validators: vec![(7, 1_000)], assets: Vec::new() });
+}
+
+#[test]
+fn genesis_validator_signs_after_registering_its_dilithium_key() {
+    genesis_ext(crate::oracle::GenesisConfig { validators: vec![(1, 1_000)], assets: Vec::new() })
+        .execute_with(|| {
+            System::set_block_number(1);
+
+            assert_ok!(submit_signed(1, NRSH, 700, Percent::from_percent(90)));
+            assert_eq!(PriceFeeds::<Test>::get(NRSH).unwrap().signatures[0].0, 1);
+        });
+}
+
+/// Fund the reward pool with `amount`
+fn fund_reward_pool(amount: Balance) {
+    Balances::make_free_balance_be(&Oracle::reward_pool_account(), amount);
+}
+
+#[test]
+fn contributors_accrue_rewards_by_stake() {
+    new_test_ext().execute_with(|| {
+        fund_reward_pool(1_000);
+
+        finalize_elxr_feed();
+
+        assert_eq!(PendingRewards::<Test>::get(1), 15);
+        assert_eq!(PendingRewards::<Test>::get(2), 15);
+        assert_eq!(PendingRewards::<Test>::get(3), 0);
+        assert_eq!(TotalPendingRewards::<Test>::get(), 30);
+
+        assert_ok!(Oracle::claim_rewards(Origin::signed(1)));
+        assert_eq!(Balances::free_balance(Oracle::reward_pool_account()), 985);
+        assert_eq!(TotalPendingRewards::<Test>::get(), 15);
+    });
+}
+
+#[test]
+fn unfunded_pool_accrues_no_rewards() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+
+        assert_eq!(PendingRewards::<Test>::get(1), 0);
+        assert_eq!(TotalPendingRewards::<Test>::get(), 0);
+        assert_noop!(Oracle::claim_rewards(Origin::signed(1)), Error::<Test>::NoRewards);
+    });
+}
+
+#[test]
+fn accrual_is_capped_at_uncommitted_pool_balance() {
+    new_test_ext().execute_with(|| {
+        // 20 above the existential deposit, less than one full reward
+        fund_reward_pool(21);
+
+        finalize_elxr_feed();
+        assert_eq!(PendingRewards::<Test>::get(1), 10);
+        assert_eq!(PendingRewards::<Test>::get(2), 10);
+
+        // Everything the pool holds is owed, so the next round accrues nothing
+        run_to_block(6);
+        assert_ok!(submit_derived(1, b"batch-2", 900));
+        assert_ok!(submit_derived(2, b"batch-2", 900));
+        assert_eq!(PriceFeeds::<Test>::get(ELXR).unwrap().price, 900);
+        assert_eq!(TotalPendingRewards::<Test>::get(), 20);
+
+        // Every accrued reward can still be paid out
+        assert_ok!(Oracle::claim_rewards(Origin::signed(1)));
+        assert_ok!(Oracle::claim_rewards(Origin::signed(2)));
+        assert_eq!(Balances::free_balance(Oracle::reward_pool_account()), 1);
+    });
+}
+
+/// Overwrite the ELXR feed with a garbage price and proof
+fn corrupt_elxr_feed() {
+    PriceFeeds::<Test>::mutate(ELXR, |feed| {
+        let feed = feed.as_mut().unwrap();
+        feed.price = 1;
+        feed.quantum_proof = vec![0xFF; 5];
+    });
+}
+
+#[test]
+fn restore_feed_rolls_back_to_last_finalized_price() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        corrupt_elxr_feed();
+
+        assert_noop!(Oracle::restore_feed(Origin::signed(1), ELXR), DispatchError::BadOrigin);
+        assert_ok!(Oracle::restore_feed(Origin::root(), ELXR));
+
+        let feed = PriceFeeds::<Test>::get(ELXR).unwrap();
+        assert_eq!((feed.price, feed.timestamp), (800, 1));
+        assert_eq!(feed.quantum_proof, error_correction::quantum::protect(&800u128.encode()));
+        assert_eq!(oracle_events().last(), Some(&OracleEvent::FeedRestored { asset_id: ELXR, price: 800 }));
+    });
+}
+
+#[test]
+fn last_known_good_follows_each_finalization() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        assert_eq!(LastKnownGood::<Test>::get(ELXR), Some((800, 1)));
+
+        // An open round does not move the restore point
+        run_to_block(6);
+        assert_ok!(submit_derived(1, b"batch-2", 900));
+        assert_eq!(LastKnownGood::<Test>::get(ELXR), Some((800, 1)));
+
+        assert_ok!(submit_derived(2, b"batch-2", 900));
+        assert_eq!(LastKnownGood::<Test>::get(ELXR), Some((900, 6)));
+
+        corrupt_elxr_feed();
+        assert_ok!(Oracle::restore_feed(Origin::root(), ELXR));
+        assert_eq!(PriceFeeds::<Test>::get(ELXR).unwrap().price, 900);
+    });
+}
+
+#[test]
+fn feed_without_finalized_price_cannot_be_restored() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        assert_ok!(submit_derived(1, b"batch-1", 800));
+
+        assert_noop!(Oracle::restore_feed(Origin::root(), ELXR), Error::<Test>::NoLastKnownGood);
+    });
+}
+
+#[test]
+fn stale_feed_resolves_to_last_known_good_price() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        assert_ok!(Oracle::set_fallback_sources(
+            Origin::root(),
+            ELXR,
+            vec![PriceSource::PrimaryFeed, PriceSource::LastKnownGood]
+        ));
+        assert_eq!(Oracle::resolve_price(ELXR), Some((800, PriceSource::PrimaryFeed)));
+
+        // Past `MaxPriceAge` the primary feed no longer answers
+        run_to_block(102);
+
+        assert_eq!(Oracle::resolve_price(ELXR), Some((800, PriceSource::LastKnownGood)));
+    });
+}
+
+#[test]
+fn stale_feed_falls_through_to_twap_then_last_known_good() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        assert_eq!(Oracle::resolve_price(ELXR), Some((800, PriceSource::PrimaryFeed)));
+
+        // Blocks come fast enough that the feed is stale while its price is still
+        // within the TWAP window
+        for _ in 0..101 {
+            advance_block(10);
+        }
+        assert_eq!(Oracle::resolve_price(ELXR), Some((800, PriceSource::Twap)));
+
+        // Once the TWAP window has passed too, only the last known good price remains
+        advance_block(10 * BLOCK_TIME_MILLIS);
+        assert_eq!(Oracle::resolve_price(ELXR), Some((800, PriceSource::LastKnownGood)));
+    });
+}
+
+#[test]
+fn cleared_feed_no_longer_resolves_from_any_source() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        assert_ok!(Oracle::set_fallback_sources(Origin::root(), ELXR, vec![PriceSource::LastKnownGood]));
+        tamper_quantum_proof(900);
+        assert_ok!(Oracle::verify_quantum_proof(Origin::signed(4), ELXR));
+
+        assert_ok!(Oracle::clear_price_feed(Origin::root(), ELXR));
+
+        assert_eq!(Oracle::resolve_price(ELXR), None);
+        assert!(PriceSamples::<Test>::get(ELXR).is_empty());
+        assert_eq!(LastKnownGood::<Test>::get(ELXR), None);
+        assert_eq!(FallbackSources::<Test>::get(ELXR), None);
+        assert_eq!(ReportedProofMismatches::<Test>::get(ELXR), None);
+        assert_eq!(LastSubmission::<Test>::get(1, ELXR), None);
+        assert_eq!(LastSubmission::<Test>::get(2, ELXR), None);
+    });
+}
+
+#[test]
+fn fallback_sources_must_be_non_empty_and_distinct() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Oracle::set_fallback_sources(Origin::root(), ELXR, Vec::new()),
+            Error::<Test>::InvalidFallbackSources
+        );
+        assert_noop!(
+            Oracle::set_fallback_sources(Origin::root(), ELXR, vec![PriceSource::Twap, PriceSource::Twap]),
+            Error::<Test>::InvalidFallbackSources
+        );
+        assert_noop!(
+            Oracle::set_fallback_sources(Origin::signed(1), ELXR, vec![PriceSource::Twap]),
+            DispatchError::BadOrigin
+        );
+        assert_eq!(Oracle::resolve_price(NRSH), None);
+    });
+}
+
+#[test]
+fn signing_with_mocked_dilithium_key_fails() {
+    use crate::oracle::crypto;
+
+    // `dilithium_keygen` is still mocked; its key must not yield an empty signature
+    let (_, private_key) = crypto::dilithium_keygen();
+
+    assert!(crypto::dilithium_sign(&private_key, b"price").is_none());
+}
+
+/// Move `subject` to `status` as root
+fn set_status(subject: VerificationSubject<AccountId>, status: VerificationStatus) -> frame_support::dispatch::DispatchResult {
+    Oracle::set_verification_status(Origin::root(), subject, status)
+}
+
+#[test]
+fn feeds_move_through_the_verification_states() {
+    new_test_ext().execute_with(|| {
+        let subject = VerificationSubject::Asset(shared::liquidity::types::AssetId(3));
+        assert_eq!(VerificationStatuses::<Test>::get(&subject), None);
+
+        for status in [
+            VerificationStatus::Pending,
+            VerificationStatus::Verified,
+            VerificationStatus::Failed,
+            VerificationStatus::Pending,
+            VerificationStatus::Failed,
+        ] {
+            assert_ok!(set_status(subject.clone(), status));
+            assert_eq!(VerificationStatuses::<Test>::get(&subject), Some(status));
+        }
+
+        assert_eq!(
+            oracle_events().first(),
+            Some(&OracleEvent::VerificationStatusChanged {
+                subject,
+                from: None,
+                to: VerificationStatus::Pending,
+            })
+        );
+    });
+}
+
+#[test]
+fn illegal_verification_transitions_are_rejected() {
+    new_test_ext().execute_with(|| {
+        let subject = VerificationSubject::Validator(1);
+        assert_noop!(set_status(subject.clone(), VerificationStatus::Verified), Error::<Test>::IllegalVerificationTransition);
+
+        assert_ok!(set_status(subject.clone(), VerificationStatus::Pending));
+        assert_ok!(set_status(subject.clone(), VerificationStatus::Verified));
+        assert_noop!(set_status(subject.clone(), VerificationStatus::Pending), Error::<Test>::IllegalVerificationTransition);
+
+        // Batch-only states are never valid for feeds or validators
+        assert_noop!(set_status(subject.clone(), VerificationStatus::Disputed), Error::<Test>::IllegalVerificationTransition);
+        assert_noop!(
+            Oracle::set_verification_status(Origin::signed(1), subject, VerificationStatus::Failed),
+            DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn feeds_that_lose_verification_stop_accepting_and_resolving_prices() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        assert_ok!(submit_derived(1, b"batch-1", 800));
+        assert_ok!(submit_derived(2, b"batch-1", 800));
+        assert!(Oracle::resolve_price(ELXR).is_some());
+
+        assert_ok!(set_status(VerificationSubject::Asset(ELXR), VerificationStatus::Failed));
+        assert_eq!(Oracle::resolve_price(ELXR), None);
+        assert_eq!(Oracle::get_asset_price_with_correction(ELXR), None);
+        run_to_block(10);
+        assert_noop!(submit_derived(3, b"batch-1", 800), Error::<Test>::AssetNotVerified);
+    });
+}
+
+#[test]
+fn unverified_feeds_reject_signed_submissions() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        let unlisted = shared::liquidity::types::AssetId(3);
+        assert_noop!(
+            Oracle::submit_price_update(
+                Origin::signed(1),
+                unlisted,
+                800,
+                Percent::from_percent(50),
+                SignatureScheme::Dilithium3,
+                Vec::new()
+            ),
+            Error::<Test>::AssetNotVerified
+        );
+
+        // Pending is not enough
+        assert_ok!(set_status(VerificationSubject::Asset(unlisted), VerificationStatus::Pending));
+        assert_eq!(Oracle::resolve_price(unlisted), None);
+    });
+}
+
+#[test]
+fn validators_that_fail_verification_cannot_submit() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        assert_ok!(set_status(VerificationSubject::Validator(1), VerificationStatus::Pending));
+        assert_ok!(set_status(VerificationSubject::Validator(1), VerificationStatus::Failed));
+
+        assert_noop!(submit_derived(1, b"batch-1", 800), Error::<Test>::ValidatorNotTrusted);
+        assert_ok!(submit_derived(2, b"batch-1", 800));
+    });
+}
+
+#[test]
+fn max_stake_per_validator_is_exposed_as_a_constant() {
+    assert!(Oracle::pallet_constants_metadata().iter().any(|constant| constant.name == "MaxStakePerValidator"));
+}
+
+#[test]
+fn registration_stake_is_capped() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Oracle::register_validator(Origin::signed(1), 10_001), Error::<Test>::StakeExceedsMaximum);
+
+        assert_ok!(Oracle::register_validator(Origin::signed(1), 10_000));
+        assert_eq!(Balances::reserved_balance(1), 10_000);
+    });
+}
+
+#[test]
+fn stake_increases_stop_at_the_cap() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1], 9_000);
+
+        assert_noop!(Oracle::increase_stake(Origin::signed(1), 1_001), Error::<Test>::StakeExceedsMaximum);
+
+        assert_ok!(Oracle::increase_stake(Origin::signed(1), 1_000));
+        assert_eq!(Validators::<Test>::get(1).unwrap().stake, 10_000);
+        assert_noop!(Oracle::increase_stake(Origin::signed(1), 1), Error::<Test>::StakeExceedsMaximum);
+        assert_eq!(Balances::reserved_balance(1), 10_000);
+    });
+}
+
+#[test]
+fn consensus_threshold_is_exposed_as_a_constant() {
+    assert!(Oracle::pallet_constants_metadata().iter().any(|constant| constant.name == "ConsensusThreshold"));
+}
+
+#[test]
+fn majority_consensus_threshold_passes_integrity_test() {
+    ConsensusThreshold::set(Percent::from_percent(50));
+    <Oracle as Hooks<u64>>::integrity_test();
+
+    ConsensusThreshold::set(Percent::from_percent(100));
+    <Oracle as Hooks<u64>>::integrity_test();
+}
+
+#[test]
+#[should_panic(expected = "ConsensusThreshold must be at least 50%")]
+fn minority_consensus_threshold_fails_integrity_test() {
+    ConsensusThreshold::set(Percent::from_percent(30));
+    <Oracle as Hooks<u64>>::integrity_test();
+}
+
+#[test]
+fn paused_oracle_rejects_submissions_but_serves_reads() {
+    new_test_ext().execute_with(|| {
+        finalize_elxr_feed();
+        assert_noop!(Oracle::set_paused(Origin::signed(1), true), DispatchError::BadOrigin);
+        assert_ok!(Oracle::set_paused(Origin::root(), true));
+        assert_eq!(oracle_events().last(), Some(&OracleEvent::OraclePaused));
+
+        run_to_block(6);
+        assert_noop!(submit_derived(1, b"batch-2", 900), Error::<Test>::OraclePaused);
+        assert_noop!(
+            Oracle::submit_price_update(
+                Origin::signed(1),
+                NRSH,
+                900,
+                Percent::from_percent(90),
+                SignatureScheme::Dilithium3,
+                vec![0; 64]
+            ),
+            Error::<Test>::OraclePaused
+        );
+
+        assert_eq!(Oracle::get_asset_price_with_correction(ELXR), Some(800));
+        assert_eq!(Oracle::resolve_price(ELXR), Some((800, PriceSource::PrimaryFeed)));
+        assert_eq!(Oracle::get_twap(ELXR), Some(800));
+    });
+}
+
+#[test]
+fn resumed_oracle_accepts_submissions_again() {
+    new_test_ext().execute_with(|| {
+        register_validators(&[1, 2, 3], 1_000);
+        assert_ok!(Oracle::set_paused(Origin::root(), true));
+        assert_noop!(submit_derived(1, b"batch-1", 800), Error::<Test>::OraclePaused);
+
+        assert_ok!(Oracle::set_paused(Origin::root(), false));
+        assert_eq!(oracle_events().last(), Some(&OracleEvent::OracleResumed));
+
+        assert_ok!(submit_derived(1, b"batch-1", 800));
+        assert_ok!(submit_derived(2, b"batch-1", 800));
+        assert_eq!(PriceFeeds::<Test>::get(ELXR).unwrap().price, 800);
+    });
+}
+
+/// Store a freshly finalized, fully confident feed pricing `asset_id` at `price`
+fn set_feed(asset_id: shared::liquidity::types::AssetId, price: Balance) {
+    PriceFeeds::<Test>::insert(asset_id, PriceFeed::<Test> {
+        asset_id,
+        price,
+        timestamp: System::block_number(),
+        confidence: Percent::from_percent(100),
+        signatures: Vec::new(),
+        quantum_proof: error_correction::quantum::protect(&price.encode()),
+    });
+}
+
+/// Synchronize an ELXR/NRSH pool that follows the oracle, returning the synchronized price
+fn synchronized_elxr_nrsh_price() -> Result<FixedU128, DispatchError> {
+    assert_ok!(OracleLiquidity::register_oracle_driven_pool(
+        Origin::root(),
+        0,
+        ELXR,
+        NRSH,
+        true,
+        Percent::from_percent(5)
+    ));
+    OracleLiquidity::synchronize_pool(Origin::signed(4), 0).map_err(|e| e.error)?;
+
+    Ok(System::events()
+        .into_iter()
+        .find_map(|record| match record.event {
+            Event::OracleLiquidity(crate::oracle_liquidity::Event::PoolSynchronized { new_price, .. }) => Some(new_price),
+            _ => None,
+        })
+        .expect("pool was synchronized"))
+}
+
+#[test]
+fn cheaper_base_asset_yields_fractional_price_ratio() {
+    new_test_ext().execute_with(|| {
+        set_feed(ELXR, 800);
+        set_feed(NRSH, 1_600);
+
+        let ratio = synchronized_elxr_nrsh_price().unwrap();
+
+        // Integer division would have truncated 800 / 1600 to zero
+        assert_eq!(ratio, FixedU128::from_rational(1, 2));
+        assert_eq!(ratio.into_inner(), 500_000_000_000_000_000);
+    });
+}
+
+#[test]
+fn price_ratio_keeps_whole_and_fractional_parts() {
+    new_test_ext().execute_with(|| {
+        set_feed(ELXR, 2_500);
+        set_feed(NRSH, 1_000);
+
+        assert_eq!(synchronized_elxr_nrsh_price().unwrap(), FixedU128::from_rational(5, 2));
+    });
+}
+
+#[test]
+fn zero_quote_price_cannot_be_synchronized() {
+    new_test_ext().execute_with(|| {
+        set_feed(ELXR, 800);
+        set_feed(NRSH, 0);
+
+        assert_eq!(synchronized_elxr_nrsh_price(), Err(ArithmeticError::DivisionByZero.into()));
+    });
+}
+
+/// Whether synchronizing the ELXR/NRSH pool overrode its price with the oracle's
+fn elxr_nrsh_pool_overridden() -> bool {
+    assert_ok!(OracleLiquidity::register_oracle_driven_pool(
+        Origin::root(),
+        0,
+        ELXR,
+        NRSH,
+        true,
+        Percent::from_percent(5)
+    ));
+    assert_ok!(OracleLiquidity::synchronize_pool(Origin::signed(4), 0));
+
+    System::events().into_iter().any(|record| {
+        matches!(record.event, Event::OracleLiquidity(crate::oracle_liquidity::Event::PoolSynchronized { .. }))
+    })
+}
+
+#[test]
+fn confidence_decays_linearly_to_zero_over_the_window() {
+    new_test_ext().execute_with(|| {
+        // `ConfidenceDecayBlocks` is 100
+        set_feed(ELXR, 800);
+        PriceFeeds::<Test>::mutate(ELXR, |feed| feed.as_mut().unwrap().confidence = Percent::from_percent(80));
+        let finalized_at = System::block_number();
+
+        for (age, expected) in [(0, 80), (25, 60), (50, 40), (75, 20), (100, 0), (150, 0)] {
+            System::set_block_number(finalized_at + age);
+            assert_eq!(Oracle::effective_confidence(ELXR), Some(Percent::from_percent(expected)), "age {}", age);
+        }
+
+        // The stored confidence itself is untouched
+        assert_eq!(PriceFeeds::<Test>::get(ELXR).unwrap().confidence, Percent::from_percent(80));
+        assert_eq!(Oracle::effective_confidence(NRSH), None);
+    });
+}
+
+#[test]
+fn pool_is_overridden_only_while_decayed_confidence_is_high_enough() {
+    // `MinOverrideConfidence` is 50%, reached by a fully confident price 50 blocks old
+    new_test_ext().execute_with(|| {
+        set_feed(ELXR, 800);
+        set_feed(NRSH, 1_600);
+        System::set_block_number(System::block_number() + 40);
+
+        assert!(elxr_nrsh_pool_overridden());
+    });
+
+    new_test_ext().execute_with(|| {
+        set_feed(ELXR, 800);
+        set_feed(NRSH, 1_600);
+        System::set_block_number(System::block_number() + 60);
+
+        assert!(!elxr_nrsh_pool_overridden());
+    });
+}
+
+/// Arbitrage executions recorded by the oracle-liquidity pallet so far
+fn arbitrage_count() -> usize {
+    System::events()
+        .into_iter()
+        .filter(|record| {
+            matches!(record.event, Event::OracleLiquidity(crate::oracle_liquidity::Event::ArbitrageExecuted { .. }))
+        })
+        .count()
+}
+
+#[test]
+fn arbitrage_executes_only_once_the_oracle_moves_past_the_pool_threshold() {
+    use crate::oracle_liquidity::{SynchronizedRatios, WeightInfo};
+
+    new_test_ext().execute_with(|| {
+        set_feed(ELXR, 2_000);
+        set_feed(NRSH, 1_000);
+        assert_ok!(OracleLiquidity::register_oracle_driven_pool(
+            Origin::root(),
+            0,
+            ELXR,
+            NRSH,
+            true,
+            Percent::from_percent(5)
+        ));
+        let sync = || OracleLiquidity::synchronize_pool(Origin::signed(4), 0).unwrap().actual_weight;
+
+        // Nothing to arbitrage against before the first synchronization, or within 5%
+        assert_eq!(sync(), Some(<() as WeightInfo>::synchronize_pool(0)));
+        set_feed(ELXR, 2_050);
+        assert_eq!(sync(), Some(<() as WeightInfo>::synchronize_pool(0)));
+        assert_eq!(arbitrage_count(), 0);
+
+        set_feed(ELXR, 2_400);
+        assert_eq!(sync(), Some(<() as WeightInfo>::synchronize_pool(1)));
+        assert_eq!(arbitrage_count(), 1);
+        assert_eq!(SynchronizedRatios::<Test>::get(0), Some(FixedU128::from_rational(12, 5)));
+    });
+}
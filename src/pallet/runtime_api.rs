@@ -5,6 +5,9 @@
 use codec::Codec;
 use sp_std::vec::Vec;
 
+use crate::health::HealthScore;
+use shared::liquidity::types::AssetId;
+
 /// The Elixir runtime API used to access kombucha batch verification data.
 sp_api::decl_runtime_apis! {
     pub trait ElixirRuntimeApi<AccountId, Balance> where
@@ -22,7 +25,31 @@ sp_api::decl_runtime_apis! {
         
         /// Check the fermentation status of a batch
         fn check_fermentation_status(batch_id: Vec<u8>) -> Option<FermentationStatus>;
+        
+        /// Get the SCOBY health score of a batch derived from its recent telemetry
+        fn scoby_health(batch_id: Vec<u8>) -> Option<HealthScore>;
     }
+
+    /// Price queries against the ELXR oracle.
+    pub trait OracleRuntimeApi<Balance, BlockNumber> where
+        Balance: Codec,
+        BlockNumber: Codec,
+    {
+        /// Get the current quote for each asset in one call, in the order requested;
+        /// `None` for assets without a price feed
+        fn prices(asset_ids: Vec<AssetId>) -> Vec<(AssetId, Option<PriceQuote<Balance, BlockNumber>>)>;
+    }
+}
+
+/// Current oracle price of an asset
+#[derive(codec::Encode, codec::Decode, Clone, PartialEq, Eq, Debug)]
+pub struct PriceQuote<Balance, BlockNumber> {
+    /// Finalized price
+    pub price: Balance,
+    /// Block the price was finalized in
+    pub updated_at: BlockNumber,
+    /// Lowest confidence among the submissions behind the price
+    pub confidence: sp_runtime::Percent,
 }
 
 /// Details of a kombucha production batch
@@ -0,0 +1,142 @@
+//! Runtime interface exposing post-quantum cryptography as native host functions.
+//!
+//! CRYSTALS-Dilithium, Falcon and Kyber are far too heavy to execute inside the Wasm
+//! executor, so the node provides them natively through `pq_crypto`. A host call
+//! still has to copy keys, messages and signatures across the Wasm boundary:
+//! Dilithium3 public keys are ~2KB and signatures ~3.3KB, so the weights of any
+//! extrinsic verifying a signature must account for that payload, not just the
+//! verification itself.
+//!
+//! Runtimes compiled without the `pq-host-functions` feature never import these
+//! functions and use the pure-Rust implementations in [`fallback`] instead. Those
+//! are correct but considerably slower inside Wasm. There is no pure-Rust Falcon
+//! verifier, so such runtimes reject every Falcon signature and should leave
+//! Falcon off their allowed signature schemes.
+
+use sp_runtime_interface::runtime_interface;
+use sp_std::vec::Vec;
+
+/// Post-quantum primitives executed natively by the node.
+#[runtime_interface]
+pub trait PqCrypto {
+    /// Verify a detached CRYSTALS-Dilithium signature over `message`
+    fn dilithium_verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        use pqcrypto_dilithium::dilithium3;
+        use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+
+        match (
+            dilithium3::PublicKey::from_bytes(public_key),
+            dilithium3::DetachedSignature::from_bytes(signature),
+        ) {
+            (Ok(public_key), Ok(signature)) => {
+                dilithium3::verify_detached_signature(&signature, message, &public_key).is_ok()
+            }
+            _ => false,
+        }
+    }
+
+    /// Verify a detached Falcon-512 signature over `message`
+    fn falcon_verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        use pqcrypto_falcon::falcon512;
+        use pqcrypto_traits::sign::{DetachedSignature, PublicKey};
+
+        match (
+            falcon512::PublicKey::from_bytes(public_key),
+            falcon512::DetachedSignature::from_bytes(signature),
+        ) {
+            (Ok(public_key), Ok(signature)) => {
+                falcon512::verify_detached_signature(&signature, message, &public_key).is_ok()
+            }
+            _ => false,
+        }
+    }
+
+    /// Produce a detached CRYSTALS-Dilithium signature over `message`
+    fn dilithium_sign(secret_key: &[u8], message: &[u8]) -> Option<Vec<u8>> {
+        use pqcrypto_dilithium::dilithium3;
+        use pqcrypto_traits::sign::{DetachedSignature, SecretKey};
+
+        let secret_key = dilithium3::SecretKey::from_bytes(secret_key).ok()?;
+        Some(dilithium3::detached_sign(message, &secret_key).as_bytes().to_vec())
+    }
+
+    /// Recover the Kyber shared secret encapsulated in `ciphertext`
+    fn kyber_decapsulate(secret_key: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        use pqcrypto_kyber::kyber768;
+        use pqcrypto_traits::kem::{Ciphertext, SecretKey, SharedSecret};
+
+        let secret_key = kyber768::SecretKey::from_bytes(secret_key).ok()?;
+        let ciphertext = kyber768::Ciphertext::from_bytes(ciphertext).ok()?;
+        Some(kyber768::decapsulate(&ciphertext, &secret_key).as_bytes().to_vec())
+    }
+}
+
+/// Pure-Rust implementations used when the host functions are unavailable
+pub mod fallback {
+    use sp_std::vec::Vec;
+
+    /// Verify a detached CRYSTALS-Dilithium signature over `message`
+    pub fn dilithium_verify(public_key: &[u8], message: &[u8], signature: &[u8]) -> bool {
+        pqc_dilithium::verify(signature, message, public_key).is_ok()
+    }
+
+    /// No pure-Rust Falcon verifier is available, so every signature is rejected
+    pub fn falcon_verify(_public_key: &[u8], _message: &[u8], _signature: &[u8]) -> bool {
+        false
+    }
+
+    /// Signing is never performed in-runtime without host support: `pqc_dilithium`
+    /// cannot rebuild a keypair from raw secret key bytes.
+    pub fn dilithium_sign(_secret_key: &[u8], _message: &[u8]) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Recover the Kyber shared secret encapsulated in `ciphertext`
+    pub fn kyber_decapsulate(secret_key: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        pqc_kyber::decapsulate(ciphertext, secret_key)
+            .ok()
+            .map(|shared_secret| shared_secret.to_vec())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::pq_crypto;
+    use pqcrypto_dilithium::dilithium3;
+    use pqcrypto_kyber::kyber768;
+    use pqcrypto_traits::{
+        kem::{Ciphertext as _, SecretKey as _, SharedSecret as _},
+        sign::{PublicKey as _, SecretKey as _},
+    };
+
+    #[test]
+    fn dilithium_signature_from_host_verifies() {
+        sp_io::TestExternalities::default().execute_with(|| {
+            let (public_key, secret_key) = dilithium3::keypair();
+
+            let signature = pq_crypto::dilithium_sign(secret_key.as_bytes(), b"price").unwrap();
+
+            assert!(pq_crypto::dilithium_verify(public_key.as_bytes(), b"price", &signature));
+            assert!(!pq_crypto::dilithium_verify(public_key.as_bytes(), b"prize", &signature));
+        });
+    }
+
+    #[test]
+    fn signing_with_malformed_key_fails() {
+        sp_io::TestExternalities::default().execute_with(|| {
+            assert_eq!(pq_crypto::dilithium_sign(&[0; 32], b"price"), None);
+        });
+    }
+
+    #[test]
+    fn kyber_decapsulation_recovers_shared_secret() {
+        sp_io::TestExternalities::default().execute_with(|| {
+            let (public_key, secret_key) = kyber768::keypair();
+            let (shared_secret, ciphertext) = kyber768::encapsulate(&public_key);
+
+            let recovered = pq_crypto::kyber_decapsulate(secret_key.as_bytes(), ciphertext.as_bytes());
+
+            assert_eq!(recovered, Some(shared_secret.as_bytes().to_vec()));
+        });
+    }
+}
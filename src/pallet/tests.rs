@@ -0,0 +1,503 @@
+//! Tests for the Elixir pallet.
+
+use crate::{
+    mock::*, oracle::{error_correction, FermentationValuation}, types::{VerificationInfo, VerificationStatus}, BatchId, BatchVerificationStatus,
+    CompletionCertificates, Error, RawEvent, Telemetry, TelemetrySample, VerificationDigests, VerificationEvidence, VerificationResult,
+    VerificationVotes, EVIDENCE_REDUNDANCY,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    storage::{StorageDoubleMap, StorageMap},
+    weights::Pays,
+    BoundedVec,
+};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+/// Endpoint authorized to submit telemetry in these tests
+const ENDPOINT: AccountId = 6;
+
+/// Authorize `ENDPOINT` and record `count` in-range samples for `batch_id`
+fn record_samples(batch_id: &[u8], count: u64) {
+    if !Elixir::authorized_endpoints().contains(&ENDPOINT) {
+        assert_ok!(Elixir::add_authorized_endpoint(Origin::root(), ENDPOINT));
+    }
+    let samples: Vec<TelemetrySample> = (1..=count)
+        .map(|i| TelemetrySample { timestamp: i * 1_000, ph_level: 30, temperature: 240 })
+        .collect();
+    assert_ok!(Elixir::record_telemetry_batch(
+        Origin::signed(ENDPOINT),
+        batch_id.to_vec(),
+        BoundedVec::try_from(samples).unwrap()
+    ));
+}
+
+/// Have verifiers 1 and 2 pass `batch_id`, each attaching its own evidence
+fn verify_batch(batch_id: &[u8]) {
+    record_samples(batch_id, 2);
+    for (verifier, evidence) in [(1, &b"lab report 1"[..]), (2, &b"lab report 2"[..])] {
+        assert_ok!(Elixir::record_verification(
+            Origin::signed(verifier),
+            batch_id.to_vec(),
+            VerificationResult::Passed,
+            evidence.to_vec()
+        ));
+    }
+}
+
+/// Digest carried by the `BatchVerified` event of `batch_id`
+fn verified_digest(batch_id: &[u8]) -> Option<H256> {
+    System::events().into_iter().find_map(|record| match record.event {
+        Event::Elixir(RawEvent::BatchVerified(id, digest)) if id == batch_id => Some(digest),
+        _ => None,
+    })
+}
+
+#[test]
+fn only_a_digest_of_the_raw_evidence_is_stored_on_chain() {
+    new_test_ext().execute_with(|| {
+        verify_batch(b"batch-1");
+
+        assert_eq!(
+            VerificationEvidence::<Test>::get(b"batch-1".to_vec(), 1),
+            Some(VerificationInfo { data_digest: BlakeTwo256::hash(b"lab report 1") })
+        );
+    });
+}
+
+#[test]
+fn correctly_stored_evidence_verifies() {
+    new_test_ext().execute_with(|| {
+        verify_batch(b"batch-1");
+
+        let digest = VerificationDigests::<Test>::get(b"batch-1".to_vec());
+        assert!(digest.is_some());
+        assert_eq!(verified_digest(b"batch-1"), digest);
+        assert!(Elixir::verify_digest(&b"batch-1".to_vec()));
+    });
+}
+
+#[test]
+fn tampered_evidence_digest_fails_verification() {
+    new_test_ext().execute_with(|| {
+        verify_batch(b"batch-1");
+
+        VerificationEvidence::<Test>::mutate(b"batch-1".to_vec(), 1, |info| {
+            info.as_mut().unwrap().data_digest = H256::repeat_byte(7);
+        });
+
+        assert!(!Elixir::verify_digest(&b"batch-1".to_vec()));
+    });
+}
+
+#[test]
+fn indexed_evidence_is_checked_against_its_digest() {
+    new_test_ext().execute_with(|| {
+        let digest = BlakeTwo256::hash(b"lab report 2");
+        let mut indexed = error_correction::classical::encode(b"lab report 2", EVIDENCE_REDUNDANCY);
+        assert!(Elixir::evidence_matches(&digest, &indexed));
+
+        // Damaged parity is corrected
+        let len = indexed.len();
+        indexed[len - 1] ^= 0xff;
+        assert!(Elixir::evidence_matches(&digest, &indexed));
+
+        // A tampered payload is not
+        indexed[0] ^= 0xff;
+        assert!(!Elixir::evidence_matches(&digest, &indexed));
+    });
+}
+
+#[test]
+fn tampered_batch_digest_fails_verification() {
+    new_test_ext().execute_with(|| {
+        verify_batch(b"batch-1");
+
+        VerificationDigests::<Test>::insert(b"batch-1".to_vec(), H256::repeat_byte(7));
+
+        assert!(!Elixir::verify_digest(&b"batch-1".to_vec()));
+    });
+}
+
+#[test]
+fn batch_without_digest_does_not_verify() {
+    new_test_ext().execute_with(|| {
+        let batch_id: BatchId = b"batch-1".to_vec();
+        record_samples(&batch_id, 2);
+        assert_ok!(Elixir::record_verification(
+            Origin::signed(1),
+            batch_id.clone(),
+            VerificationResult::Passed,
+            b"lab report 1".to_vec()
+        ));
+
+        assert!(!Elixir::verify_digest(&batch_id));
+    });
+}
+
+/// Cast `result` for `batch_id` on behalf of `verifier`, without evidence
+fn vote(verifier: AccountId, batch_id: &[u8], result: VerificationResult) {
+    assert_ok!(Elixir::record_verification(Origin::signed(verifier), batch_id.to_vec(), result, Vec::new()));
+}
+
+#[test]
+fn inconclusive_votes_below_threshold_keep_the_round_open() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 2);
+
+        vote(1, b"batch-1", VerificationResult::Inconclusive);
+        vote(2, b"batch-1", VerificationResult::Passed);
+
+        assert_eq!(BatchVerificationStatus::get(b"batch-1".to_vec()), Some(VerificationStatus::InProgress));
+        assert_eq!(VerificationVotes::<Test>::get(b"batch-1".to_vec()).len(), 2);
+        assert_noop!(
+            Elixir::record_verification(Origin::signed(1), b"batch-1".to_vec(), VerificationResult::Passed, Vec::new()),
+            Error::<Test>::AlreadyVoted
+        );
+    });
+}
+
+#[test]
+fn reaching_the_inconclusive_threshold_restarts_verification() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 2);
+
+        vote(1, b"batch-1", VerificationResult::Inconclusive);
+        vote(2, b"batch-1", VerificationResult::Inconclusive);
+
+        assert_eq!(BatchVerificationStatus::get(b"batch-1".to_vec()), Some(VerificationStatus::Pending));
+        assert!(VerificationVotes::<Test>::get(b"batch-1".to_vec()).is_empty());
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == Event::Elixir(RawEvent::VerificationInconclusive(b"batch-1".to_vec()))));
+
+        // The same verifiers may vote again in the new round
+        vote(1, b"batch-1", VerificationResult::Passed);
+        vote(2, b"batch-1", VerificationResult::Passed);
+
+        assert_eq!(BatchVerificationStatus::get(b"batch-1".to_vec()), Some(VerificationStatus::Verified));
+    });
+}
+
+/// Fee charged for `ENDPOINT` submitting one sample to `batch_id`
+fn telemetry_fee(batch_id: &[u8], timestamp: u64) -> Pays {
+    let samples = vec![TelemetrySample { timestamp, ph_level: 30, temperature: 240 }];
+    Elixir::record_telemetry_batch(Origin::signed(ENDPOINT), batch_id.to_vec(), BoundedVec::try_from(samples).unwrap())
+        .unwrap()
+        .pays_fee
+}
+
+#[test]
+fn untrusted_endpoint_pays_for_telemetry() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Elixir::add_authorized_endpoint(Origin::root(), ENDPOINT));
+
+        assert_eq!(telemetry_fee(b"batch-1", 1_000), Pays::Yes);
+    });
+}
+
+#[test]
+fn trusted_sensor_telemetry_is_fee_free_until_removed() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Elixir::add_authorized_endpoint(Origin::root(), ENDPOINT));
+        assert_ok!(Elixir::add_trusted_sensor(Origin::root(), ENDPOINT));
+
+        assert_eq!(telemetry_fee(b"batch-1", 1_000), Pays::No);
+
+        assert_ok!(Elixir::remove_trusted_sensor(Origin::root(), ENDPOINT));
+
+        assert_eq!(telemetry_fee(b"batch-1", 2_000), Pays::Yes);
+    });
+}
+
+#[test]
+fn trust_does_not_authorize_an_endpoint() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Elixir::add_trusted_sensor(Origin::root(), ENDPOINT));
+        let samples = vec![TelemetrySample { timestamp: 1_000, ph_level: 30, temperature: 240 }];
+
+        assert_noop!(
+            Elixir::record_telemetry_batch(
+                Origin::signed(ENDPOINT),
+                b"batch-1".to_vec(),
+                BoundedVec::try_from(samples).unwrap()
+            ),
+            Error::<Test>::EndpointNotAuthorized
+        );
+    });
+}
+
+#[test]
+fn pending_is_not_a_vote() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 2);
+
+        assert_noop!(
+            Elixir::record_verification(Origin::signed(1), b"batch-1".to_vec(), VerificationResult::Pending, Vec::new()),
+            Error::<Test>::InvalidVerificationResult
+        );
+    });
+}
+
+#[test]
+fn inconclusive_votes_need_no_telemetry() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Elixir::record_verification(Origin::signed(1), b"batch-1".to_vec(), VerificationResult::Passed, Vec::new()),
+            Error::<Test>::InsufficientTelemetry
+        );
+
+        vote(1, b"batch-1", VerificationResult::Inconclusive);
+
+        assert_eq!(BatchVerificationStatus::get(b"batch-1".to_vec()), Some(VerificationStatus::InProgress));
+    });
+}
+
+#[test]
+fn evidence_at_the_length_limit_is_accepted() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 2);
+
+        assert_ok!(Elixir::record_verification(
+            Origin::signed(1),
+            b"batch-1".to_vec(),
+            VerificationResult::Passed,
+            vec![7; 64]
+        ));
+
+        let info = VerificationEvidence::<Test>::get(b"batch-1".to_vec(), 1).unwrap();
+        assert_eq!(info.data_digest, BlakeTwo256::hash(&[7; 64]));
+    });
+}
+
+#[test]
+fn evidence_over_the_length_limit_is_rejected() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 2);
+
+        assert_noop!(
+            Elixir::record_verification(Origin::signed(1), b"batch-1".to_vec(), VerificationResult::Passed, vec![7; 65]),
+            Error::<Test>::VerificationDataTooLong
+        );
+    });
+}
+
+#[test]
+fn votes_without_evidence_store_none() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 2);
+
+        vote(1, b"batch-1", VerificationResult::Passed);
+
+        assert_eq!(VerificationEvidence::<Test>::get(b"batch-1".to_vec(), 1), None);
+        assert!(!System::events()
+            .iter()
+            .any(|record| matches!(record.event, Event::Elixir(RawEvent::VerificationEvidenceStored(..)))));
+    });
+}
+
+#[test]
+fn unverified_batch_gets_no_certificate() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 2);
+        vote(1, b"batch-1", VerificationResult::Passed);
+
+        assert_noop!(
+            Elixir::issue_completion_certificate(Origin::signed(3), b"batch-1".to_vec()),
+            Error::<Test>::BatchNotCompleted
+        );
+        assert_eq!(Elixir::certificate_of(b"batch-1".to_vec()), None);
+    });
+}
+
+#[test]
+fn verified_batch_is_certified_once() {
+    new_test_ext().execute_with(|| {
+        verify_batch(b"batch-1");
+
+        assert_ok!(Elixir::issue_completion_certificate(Origin::signed(3), b"batch-1".to_vec()));
+
+        let expected = BlakeTwo256::hash_of(&(
+            b"batch-1".to_vec(),
+            Some(VerificationStatus::Verified),
+            Telemetry::<Test>::get(b"batch-1".to_vec()),
+        ));
+        assert_eq!(Elixir::certificate_of(b"batch-1".to_vec()), Some(expected));
+        assert_eq!(CompletionCertificates::<Test>::get(b"batch-1".to_vec()), Some(expected));
+        assert!(System::events().iter().any(|record| {
+            record.event == Event::Elixir(RawEvent::CompletionCertificateIssued(b"batch-1".to_vec(), expected))
+        }));
+
+        assert_noop!(
+            Elixir::issue_completion_certificate(Origin::signed(3), b"batch-1".to_vec()),
+            Error::<Test>::CertificateAlreadyIssued
+        );
+    });
+}
+
+/// Authorize `ENDPOINT` and record `samples` as (ph_level, temperature), a second apart
+fn record_readings(batch_id: &[u8], readings: &[(u8, u16)]) {
+    if !Elixir::authorized_endpoints().contains(&ENDPOINT) {
+        assert_ok!(Elixir::add_authorized_endpoint(Origin::root(), ENDPOINT));
+    }
+    let samples: Vec<TelemetrySample> = readings
+        .iter()
+        .zip(1u64..)
+        .map(|(&(ph_level, temperature), i)| TelemetrySample { timestamp: i * 1_000, ph_level, temperature })
+        .collect();
+    assert_ok!(Elixir::record_telemetry_batch(
+        Origin::signed(ENDPOINT),
+        batch_id.to_vec(),
+        BoundedVec::try_from(samples).unwrap()
+    ));
+}
+
+#[test]
+fn batch_without_telemetry_has_no_health_or_value() {
+    new_test_ext().execute_with(|| {
+        assert_eq!(Elixir::scoby_health(b"batch-1".to_vec()), None);
+        assert_eq!(Elixir::healthy_batch_valuation(&b"batch-1".to_vec()), None);
+    });
+}
+
+#[test]
+fn batch_health_is_scored_from_recorded_telemetry() {
+    new_test_ext().execute_with(|| {
+        record_readings(b"batch-1", &[(30, 240), (40, 240)]);
+
+        let health = Elixir::scoby_health(b"batch-1".to_vec()).unwrap();
+        assert_eq!((health.score, health.ph, health.temperature, health.stage_time), (80, 20, 30, 30));
+    });
+}
+
+#[test]
+fn only_good_or_better_batches_are_valued() {
+    new_test_ext().execute_with(|| {
+        record_readings(b"healthy", &[(30, 240), (32, 245)]);
+        record_readings(b"good", &[(30, 240), (40, 240)]);
+        record_readings(b"poor", &[(60, 200), (60, 400)]);
+
+        // Scaled from `HealthyBatchValue` by the health score
+        assert_eq!(Elixir::healthy_batch_valuation(&b"healthy".to_vec()), Some(1_000));
+        assert_eq!(Elixir::healthy_batch_valuation(&b"good".to_vec()), Some(800));
+        assert_eq!(Elixir::healthy_batch_valuation(&b"poor".to_vec()), None);
+    });
+}
+
+/// Submit `samples` for `batch_id` as `ENDPOINT`, which must already be authorized
+fn submit(batch_id: &[u8], samples: Vec<TelemetrySample>) -> frame_support::dispatch::DispatchResultWithPostInfo {
+    Elixir::record_telemetry_batch(Origin::signed(ENDPOINT), batch_id.to_vec(), BoundedVec::try_from(samples).unwrap())
+}
+
+#[test]
+fn telemetry_batch_is_appended_with_one_event() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 3);
+        assert_ok!(submit(b"batch-1", vec![TelemetrySample { timestamp: 4_000, ph_level: 31, temperature: 241 }]));
+
+        let timestamps: Vec<u64> = Telemetry::<Test>::get(b"batch-1".to_vec()).iter().map(|s| s.timestamp).collect();
+        assert_eq!(timestamps, vec![1_000, 2_000, 3_000, 4_000]);
+        let recorded: Vec<u32> = System::events()
+            .into_iter()
+            .filter_map(|record| match record.event {
+                Event::Elixir(RawEvent::TelemetryBatchRecorded(_, count)) => Some(count),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(recorded, vec![3, 1]);
+    });
+}
+
+#[test]
+fn out_of_range_sample_rejects_the_whole_batch() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Elixir::add_authorized_endpoint(Origin::root(), ENDPOINT));
+        let samples = vec![
+            TelemetrySample { timestamp: 1_000, ph_level: 30, temperature: 240 },
+            TelemetrySample { timestamp: 2_000, ph_level: 30, temperature: 601 },
+        ];
+
+        assert_noop!(submit(b"batch-1", samples), Error::<Test>::SampleOutOfRange);
+    });
+}
+
+#[test]
+fn telemetry_timestamps_must_ascend_within_and_across_calls() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 2);
+        let sample = |timestamp| TelemetrySample { timestamp, ph_level: 30, temperature: 240 };
+
+        assert_noop!(submit(b"batch-1", vec![sample(2_000)]), Error::<Test>::NonMonotonicTimestamps);
+        assert_noop!(submit(b"batch-1", vec![sample(4_000), sample(3_000)]), Error::<Test>::NonMonotonicTimestamps);
+        assert_noop!(submit(b"batch-1", vec![sample(3_000), sample(3_000)]), Error::<Test>::NonMonotonicTimestamps);
+    });
+}
+
+#[test]
+fn stored_telemetry_is_bounded_per_batch() {
+    new_test_ext().execute_with(|| {
+        // `MaxTelemetrySamples` is 4
+        record_samples(b"batch-1", 3);
+        let sample = |timestamp| TelemetrySample { timestamp, ph_level: 30, temperature: 240 };
+
+        assert_noop!(submit(b"batch-1", vec![sample(4_000), sample(5_000)]), Error::<Test>::TooManyTelemetrySamples);
+
+        let info = submit(b"batch-1", vec![sample(4_000)]).unwrap();
+        assert_eq!(Telemetry::<Test>::get(b"batch-1".to_vec()).len(), 4);
+        assert_eq!(info.actual_weight, Some(<() as crate::WeightInfo>::record_telemetry_batch(1, 3)));
+        assert_noop!(submit(b"batch-1", vec![sample(6_000)]), Error::<Test>::TooManyTelemetrySamples);
+    });
+}
+
+#[test]
+fn only_root_manages_endpoints() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(
+            Elixir::add_authorized_endpoint(Origin::signed(1), ENDPOINT),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(Elixir::add_authorized_endpoint(Origin::root(), ENDPOINT));
+        assert_noop!(
+            Elixir::add_authorized_endpoint(Origin::root(), ENDPOINT),
+            Error::<Test>::EndpointAlreadyAuthorized
+        );
+        assert_noop!(
+            Elixir::remove_authorized_endpoint(Origin::signed(1), ENDPOINT),
+            sp_runtime::DispatchError::BadOrigin
+        );
+    });
+}
+
+#[test]
+fn endpoint_list_is_bounded() {
+    new_test_ext().execute_with(|| {
+        for endpoint in 1..=4 {
+            assert_ok!(Elixir::add_authorized_endpoint(Origin::root(), endpoint));
+        }
+
+        assert_noop!(Elixir::add_authorized_endpoint(Origin::root(), 5), Error::<Test>::TooManyEndpoints);
+    });
+}
+
+#[test]
+fn revoked_endpoint_can_no_longer_submit() {
+    new_test_ext().execute_with(|| {
+        record_samples(b"batch-1", 1);
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == Event::Elixir(RawEvent::EndpointAuthorized(ENDPOINT))));
+
+        assert_ok!(Elixir::remove_authorized_endpoint(Origin::root(), ENDPOINT));
+        assert!(System::events()
+            .iter()
+            .any(|record| record.event == Event::Elixir(RawEvent::EndpointRevoked(ENDPOINT))));
+
+        let sample = TelemetrySample { timestamp: 2_000, ph_level: 30, temperature: 240 };
+        assert_noop!(submit(b"batch-1", vec![sample]), Error::<Test>::EndpointNotAuthorized);
+        assert_noop!(
+            Elixir::remove_authorized_endpoint(Origin::root(), ENDPOINT),
+            Error::<Test>::EndpointNotAuthorized
+        );
+    });
+}
@@ -1,11 +1,13 @@
 //! Type definitions for the Elixir pallet.
 
-use codec::{Decode, Encode};
+use codec::{Decode, Encode, MaxEncodedLen};
 use frame_support::RuntimeDebug;
+use scale_info::TypeInfo;
 use sp_std::prelude::*;
 
-/// Status of a kombucha batch verification
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+/// Status of a kombucha batch verification, also used by the oracle for the trust
+/// status of asset feeds and validators
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 pub enum VerificationStatus {
     /// Batch is pending verification
     Pending,
@@ -19,6 +21,19 @@ pub enum VerificationStatus {
     Disputed,
 }
 
+/// Outcome of a single verifier's assessment of a batch
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen)]
+pub enum VerificationResult {
+    /// Assessment has not been completed
+    Pending,
+    /// Batch meets the production requirements
+    Passed,
+    /// Batch does not meet the production requirements
+    Failed,
+    /// Assessment completed without a determination (e.g. insufficient telemetry)
+    Inconclusive,
+}
+
 /// Status of fermentation process
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 pub enum FermentationStatus {
@@ -53,6 +68,41 @@ pub struct FermentationMetrics {
     pub fermentation_time: u32,
 }
 
+/// Identifier of a kombucha production batch
+pub type BatchId = Vec<u8>;
+
+/// Highest pH a sensor can report (scaled by 10)
+pub const MAX_PH_LEVEL: u8 = 140;
+
+/// Highest fermentation temperature accepted from a sensor (tenths of a degree Celsius)
+pub const MAX_TEMPERATURE: u16 = 600;
+
+/// A single fermentation telemetry reading
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, Default)]
+pub struct TelemetrySample {
+    /// Reading timestamp in milliseconds
+    pub timestamp: u64,
+    /// pH level (scaled by 10, e.g. 3.5 = 35)
+    pub ph_level: u8,
+    /// Temperature in tenths of a degree Celsius
+    pub temperature: u16,
+}
+
+impl TelemetrySample {
+    /// Whether the reading lies within what the sensors can physically report
+    pub fn is_in_range(&self) -> bool {
+        self.ph_level <= MAX_PH_LEVEL && self.temperature <= MAX_TEMPERATURE
+    }
+}
+
+/// Evidence a verifier attached to their vote on a batch. The evidence itself is
+/// kept off-chain, with classical error-correction parity appended.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct VerificationInfo<Hash> {
+    /// Hash of the evidence as submitted, before error correction was applied
+    pub data_digest: Hash,
+}
+
 /// Verification data from an oracle
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
 pub struct VerificationData<AccountId> {
@@ -21,6 +21,10 @@ pub trait WeightInfo {
     fn update_recipe() -> Weight;
     fn claim_rewards() -> Weight;
     fn update_fermentation_status() -> Weight;
+    fn record_telemetry_batch(samples: u32, stored: u32) -> Weight;
+    fn update_trusted_sensors() -> Weight;
+    fn update_authorized_endpoints() -> Weight;
+    fn issue_completion_certificate() -> Weight;
 }
 
 /// Weights for elixir_pallet using the Substrate node and recommended hardware.
@@ -80,4 +84,23 @@ impl WeightInfo for () {
     fn update_fermentation_status() -> Weight {
         WEIGHT_PER_SECOND / 20
     }
+
+    fn record_telemetry_batch(samples: u32, stored: u32) -> Weight {
+        (WEIGHT_PER_SECOND / 100)
+            .saturating_add((WEIGHT_PER_SECOND / 2_000).saturating_mul(samples as Weight))
+            .saturating_add((WEIGHT_PER_SECOND / 20_000).saturating_mul(stored as Weight))
+            .saturating_add(RocksDbWeight::get().reads_writes(3, 1))
+    }
+
+    fn update_trusted_sensors() -> Weight {
+        (WEIGHT_PER_SECOND / 100).saturating_add(RocksDbWeight::get().writes(1))
+    }
+
+    fn update_authorized_endpoints() -> Weight {
+        (WEIGHT_PER_SECOND / 100).saturating_add(RocksDbWeight::get().reads_writes(1, 1))
+    }
+
+    fn issue_completion_certificate() -> Weight {
+        (WEIGHT_PER_SECOND / 50).saturating_add(RocksDbWeight::get().reads_writes(3, 1))
+    }
 }
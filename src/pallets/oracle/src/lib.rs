@@ -2,33 +2,62 @@
 
 pub use pallet::*;
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
-        dispatch::DispatchResult,
+        dispatch::{DispatchResult, DispatchResultWithPostInfo},
         pallet_prelude::*,
-        traits::{Currency, ReservableCurrency, Get},
+        traits::{Currency, NamedReservableCurrency, ReservableCurrency, Get},
     };
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
-    use sp_runtime::traits::{Zero, StaticLookup};
+    use sp_runtime::traits::{Zero, StaticLookup, Saturating};
 
     type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+    /// Named reserve holding validator deposits, kept apart from other pallets' reserves
+    pub const ORACLE_RESERVE_ID: [u8; 8] = *b"elxoracl";
+
+    /// Version 1 holds validator deposits under `ORACLE_RESERVE_ID`
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        type Currency: ReservableCurrency<Self::AccountId>;
+        type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
         #[pallet::constant]
         type OracleDeposit: Get<BalanceOf<Self>>;
         type MaxDataLength: Get<u32>;
         type MaxValidatorCount: Get<u32>;
+        /// Default cap on distinct validators submitting for one asset
+        #[pallet::constant]
+        type MaxValidatorsPerAsset: Get<u32>;
+        /// Cap on distinct assets one validator may submit for, bounding removal cost
+        #[pallet::constant]
+        type MaxAssetsPerValidator: Get<u32>;
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            if StorageVersion::get::<Pallet<T>>() < 1 {
+                weight = weight.saturating_add(Self::migrate_to_v1());
+            }
+            weight
+        }
+    }
+
     #[pallet::storage]
     #[pallet::getter(fn price_feeds)]
     pub type PriceFeeds<T: Config> = StorageMap<
@@ -38,6 +67,59 @@ pub mod pallet {
         PriceData<T>,
     >;
 
+    #[pallet::storage]
+    #[pallet::getter(fn submissions)]
+    pub type Submissions<T: Config> = StorageDoubleMap<
+        _,
+        Blake2_128Concat,
+        Vec<u8>, // Asset identifier
+        Blake2_128Concat,
+        T::AccountId,
+        BalanceOf<T>,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn aggregation_method)]
+    pub type AssetAggregation<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Vec<u8>, // Asset identifier
+        AggregationMethod,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn asset_validator_limit)]
+    pub type AssetValidatorLimit<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Vec<u8>, // Asset identifier
+        u32,
+        ValueQuery,
+        T::MaxValidatorsPerAsset,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn asset_submitter_count)]
+    pub type AssetSubmitterCount<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        Vec<u8>, // Asset identifier
+        u32,
+        ValueQuery,
+    >;
+
+    /// Assets each validator holds a submission for, so removal touches only those
+    #[pallet::storage]
+    #[pallet::getter(fn validator_assets)]
+    pub type ValidatorAssets<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::AccountId,
+        BoundedVec<Vec<u8>, T::MaxAssetsPerValidator>,
+        ValueQuery,
+    >;
+
     #[pallet::storage]
     #[pallet::getter(fn validators)]
     pub type Validators<T: Config> = StorageMap<
@@ -58,6 +140,10 @@ pub mod pallet {
         ValidatorRemoved(T::AccountId),
         PriceUpdated(Vec<u8>, BalanceOf<T>, T::BlockNumber),
         PriceAggregated(Vec<u8>, BalanceOf<T>),
+        AssetRegistered(Vec<u8>, AggregationMethod),
+        AssetValidatorLimitSet(Vec<u8>, u32),
+        /// Validator bonded extra stake: (validator, amount, total stake)
+        StakeBonded(T::AccountId, BalanceOf<T>, BalanceOf<T>),
     }
 
     #[pallet::error]
@@ -68,8 +154,21 @@ pub mod pallet {
         DataTooLong,
         InsufficientBalance,
         NotAuthorized,
+        ErrorCorrectionFailed,
         InvalidPrice,
         AssetNotFound,
+        NoSubmissions,
+        AssetValidatorLimitReached,
+        ValidatorAssetLimitReached,
+        ZeroStake,
+    }
+
+    #[derive(Clone, Copy, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen, Default)]
+    pub enum AggregationMethod {
+        #[default]
+        Median,
+        Mean,
+        StakeWeightedMedian,
     }
 
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -102,7 +201,7 @@ pub mod pallet {
             ensure!(count < T::MaxValidatorCount::get(), Error::<T>::ValidatorLimitReached);
             
             let deposit = T::OracleDeposit::get();
-            T::Currency::reserve(&who, deposit)?;
+            T::Currency::reserve_named(&ORACLE_RESERVE_ID, &who, deposit)?;
             
             let validator_info = ValidatorInfo {
                 account: who.clone(),
@@ -119,24 +218,31 @@ pub mod pallet {
         }
         
         #[pallet::call_index(1)]
-        #[pallet::weight(10_000)]
+        #[pallet::weight(Pallet::<T>::remove_validator_weight(T::MaxAssetsPerValidator::get()))]
         pub fn remove_validator(
             origin: OriginFor<T>,
-        ) -> DispatchResult {
+        ) -> DispatchResultWithPostInfo {
             let who = ensure_signed(origin)?;
             
             ensure!(Validators::<T>::contains_key(&who), Error::<T>::ValidatorNotFound);
             
-            let deposit = T::OracleDeposit::get();
-            T::Currency::unreserve(&who, deposit);
+            // Release the deposit together with any bonded stake
+            T::Currency::unreserve_named(&ORACLE_RESERVE_ID, &who, Self::stake_of(&who));
             
             Validators::<T>::remove(&who);
             
+            // Free the validator's slot on every asset it was submitting for
+            let assets = ValidatorAssets::<T>::take(&who);
+            for asset_id in assets.iter() {
+                Submissions::<T>::remove(asset_id, &who);
+                AssetSubmitterCount::<T>::mutate(asset_id, |count| *count = count.saturating_sub(1));
+            }
+            
             let count = ValidatorCount::<T>::get();
             ValidatorCount::<T>::put(count - 1);
             
             Self::deposit_event(Event::ValidatorRemoved(who));
-            Ok(())
+            Ok(Some(Self::remove_validator_weight(assets.len() as u32)).into())
         }
         
         #[pallet::call_index(2)]
@@ -152,6 +258,19 @@ pub mod pallet {
             ensure!(asset_id.len() <= T::MaxDataLength::get() as usize, Error::<T>::DataTooLong);
             ensure!(!price.is_zero(), Error::<T>::InvalidPrice);
             
+            // Keep the set aggregated per asset bounded; existing submitters may always update
+            let is_new_submitter = !Submissions::<T>::contains_key(&asset_id, &who);
+            if is_new_submitter {
+                let submitters = AssetSubmitterCount::<T>::get(&asset_id);
+                ensure!(
+                    submitters < AssetValidatorLimit::<T>::get(&asset_id),
+                    Error::<T>::AssetValidatorLimitReached
+                );
+                ValidatorAssets::<T>::try_mutate(&who, |assets| assets.try_push(asset_id.clone()))
+                    .map_err(|_| Error::<T>::ValidatorAssetLimitReached)?;
+                AssetSubmitterCount::<T>::insert(&asset_id, submitters + 1);
+            }
+            
             let current_block = <frame_system::Pallet<T>>::block_number();
             
             // Update validator info
@@ -162,6 +281,8 @@ pub mod pallet {
                 }
             });
             
+            Submissions::<T>::insert(&asset_id, &who, price);
+            
             // Update or create price feed
             if PriceFeeds::<T>::contains_key(&asset_id) {
                 PriceFeeds::<T>::mutate(&asset_id, |price_data| {
@@ -194,35 +315,176 @@ pub mod pallet {
             
             ensure!(PriceFeeds::<T>::contains_key(&asset_id), Error::<T>::AssetNotFound);
             
-            // In a real implementation, this would aggregate prices from multiple validators
-            // For simplicity, we're just using the latest price
-            let price_data = PriceFeeds::<T>::get(&asset_id).ok_or(Error::<T>::AssetNotFound)?;
+            // Latest submission of every validator still registered, with its stake
+            let submissions: Vec<(BalanceOf<T>, BalanceOf<T>)> = Submissions::<T>::iter_prefix(&asset_id)
+                .filter(|(who, _)| Validators::<T>::contains_key(who))
+                .map(|(who, price)| (price, Self::stake_of(&who)))
+                .collect();
+            
+            let price = Self::aggregate(AssetAggregation::<T>::get(&asset_id), submissions)
+                .ok_or(Error::<T>::NoSubmissions)?;
+            
+            PriceFeeds::<T>::mutate(&asset_id, |price_data| {
+                if let Some(pd) = price_data {
+                    pd.price = price;
+                }
+            });
+            
+            Self::deposit_event(Event::PriceAggregated(asset_id, price));
+            Ok(())
+        }
+        
+        #[pallet::call_index(4)]
+        #[pallet::weight(10_000)]
+        pub fn register_asset(
+            origin: OriginFor<T>,
+            asset_id: Vec<u8>,
+            method: AggregationMethod,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            
+            ensure!(asset_id.len() <= T::MaxDataLength::get() as usize, Error::<T>::DataTooLong);
+            
+            AssetAggregation::<T>::insert(&asset_id, method);
+            
+            Self::deposit_event(Event::AssetRegistered(asset_id, method));
+            Ok(())
+        }
+        
+        #[pallet::call_index(5)]
+        #[pallet::weight(10_000)]
+        pub fn set_asset_validator_limit(
+            origin: OriginFor<T>,
+            asset_id: Vec<u8>,
+            limit: u32,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            
+            ensure!(asset_id.len() <= T::MaxDataLength::get() as usize, Error::<T>::DataTooLong);
+            
+            // Lowering the limit below the current count only blocks new submitters
+            AssetValidatorLimit::<T>::insert(&asset_id, limit);
             
-            Self::deposit_event(Event::PriceAggregated(asset_id, price_data.price));
+            Self::deposit_event(Event::AssetValidatorLimitSet(asset_id, limit));
             Ok(())
         }
+        
+        #[pallet::call_index(6)]
+        #[pallet::weight(10_000)]
+        pub fn bond_stake(
+            origin: OriginFor<T>,
+            amount: BalanceOf<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            ensure!(Validators::<T>::contains_key(&who), Error::<T>::ValidatorNotFound);
+            ensure!(!amount.is_zero(), Error::<T>::ZeroStake);
+            
+            // Bonded stake sits next to the deposit, so it is released with it on removal
+            T::Currency::reserve_named(&ORACLE_RESERVE_ID, &who, amount)?;
+            
+            Self::deposit_event(Event::StakeBonded(who.clone(), amount, Self::stake_of(&who)));
+            Ok(())
+        }
+    }
+
+    impl<T: Config> Pallet<T> {
+        // Move each validator's deposit from the anonymous reserve into `ORACLE_RESERVE_ID`
+        fn migrate_to_v1() -> Weight {
+            let deposit = T::OracleDeposit::get();
+            let mut migrated = 0u64;
+            for who in Validators::<T>::iter_keys() {
+                migrated += 1;
+                let moved = deposit.saturating_sub(T::Currency::unreserve(&who, deposit));
+                // Cannot fail: the amount was freed just above
+                let _ = T::Currency::reserve_named(&ORACLE_RESERVE_ID, &who, moved);
+            }
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(migrated.saturating_mul(2), migrated.saturating_mul(2) + 1)
+        }
+
+        // Stake backing a validator's submissions: its deposit plus any bonded stake
+        pub fn stake_of(who: &T::AccountId) -> BalanceOf<T> {
+            T::Currency::reserved_balance_named(&ORACLE_RESERVE_ID, who)
+        }
+
+        // Weight of `remove_validator` for a validator holding submissions on `assets` assets
+        pub fn remove_validator_weight(assets: u32) -> Weight {
+            let per_asset = T::DbWeight::get().reads_writes(1, 2);
+            T::DbWeight::get()
+                .reads_writes(4, 5)
+                .saturating_add(per_asset.saturating_mul(assets as u64))
+        }
+
+        // Combine (price, stake) submissions into a single price using `method`
+        pub fn aggregate(
+            method: AggregationMethod,
+            mut submissions: Vec<(BalanceOf<T>, BalanceOf<T>)>,
+        ) -> Option<BalanceOf<T>> {
+            if submissions.is_empty() {
+                return None;
+            }
+            submissions.sort_by(|a, b| a.0.cmp(&b.0));
+            let two: BalanceOf<T> = 2u32.into();
+            
+            match method {
+                AggregationMethod::Median => {
+                    let mid = submissions.len() / 2;
+                    if submissions.len() % 2 == 0 {
+                        let (low, high) = (submissions[mid - 1].0, submissions[mid].0);
+                        Some(low / two + high / two + (low % two + high % two) / two)
+                    } else {
+                        Some(submissions[mid].0)
+                    }
+                }
+                AggregationMethod::Mean => {
+                    let count: BalanceOf<T> = (submissions.len() as u32).into();
+                    let sum = submissions
+                        .iter()
+                        .fold(Zero::zero(), |acc: BalanceOf<T>, (price, _)| acc.saturating_add(*price));
+                    Some(sum / count)
+                }
+                AggregationMethod::StakeWeightedMedian => {
+                    let total_stake = submissions
+                        .iter()
+                        .fold(Zero::zero(), |acc: BalanceOf<T>, (_, stake)| acc.saturating_add(*stake));
+                    let mut cumulative: BalanceOf<T> = Zero::zero();
+                    for (price, stake) in submissions.iter() {
+                        cumulative = cumulative.saturating_add(*stake);
+                        if cumulative.saturating_mul(two) >= total_stake {
+                            return Some(*price);
+                        }
+                    }
+                    submissions.last().map(|(price, _)| *price)
+                }
+            }
+        }
     }
 
     // Implement error correction mechanisms as per project requirements
     impl<T: Config> Pallet<T> {
-        // Classical error correction
+        // Classical error correction: restores a damaged parity trailer
         pub fn verify_and_correct_data(data: &mut Vec<u8>) -> Result<(), Error<T>> {
-            // Reed-Solomon error correction implementation
-            // This is a placeholder for the actual implementation
+            let (payload, redundancy) = elixir_error_correction::classical::decode(data)
+                .ok_or(Error::<T>::ErrorCorrectionFailed)?;
+            *data = elixir_error_correction::classical::encode(&payload, redundancy);
             Ok(())
         }
 
         // Bridge error correction for classical-quantum interface
         pub fn bridge_error_correction(data: &mut Vec<u8>) -> Result<(), Error<T>> {
-            // Implement redundancy and verification protocols
-            // This is a placeholder for the actual implementation
+            let payload = elixir_error_correction::bridge::decode(data)
+                .ok_or(Error::<T>::ErrorCorrectionFailed)?;
+            *data = elixir_error_correction::bridge::encode(&payload);
             Ok(())
         }
 
-        // Quantum error correction
+        // Quantum error correction: restores damaged surface code metadata
         pub fn quantum_error_correction(data: &mut Vec<u8>) -> Result<(), Error<T>> {
-            // Surface code implementation for quantum error correction
-            // This is a placeholder for the actual implementation
+            let payload = elixir_error_correction::quantum::recover(data)
+                .ok_or(Error::<T>::ErrorCorrectionFailed)?;
+            *data = elixir_error_correction::quantum::protect(&payload);
             Ok(())
         }
     }
@@ -0,0 +1,167 @@
+//! Tests for the daemonless oracle pallet.
+
+use crate::{
+    mock::*, AggregationMethod, AssetSubmitterCount, Error, PriceFeeds, Submissions, ValidatorAssets, ValidatorCount,
+    ValidatorInfo, Validators, ORACLE_RESERVE_ID,
+};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{GetStorageVersion, Hooks, NamedReservableCurrency, ReservableCurrency, StorageVersion},
+};
+
+/// Reserve the registry pallet holds facility deposits under
+const REGISTRY_RESERVE_ID: [u8; 8] = *b"elxregst";
+
+#[test]
+fn removing_validator_leaves_registry_deposit_intact() {
+    new_test_ext().execute_with(|| {
+        // Account 1 is also a registry facility
+        assert_ok!(Balances::reserve_named(&REGISTRY_RESERVE_ID, &1, 500));
+        assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(1)));
+        assert_eq!(Balances::reserved_balance_named(&ORACLE_RESERVE_ID, &1), DEPOSIT);
+
+        assert_ok!(Oracle::remove_validator(RuntimeOrigin::signed(1)));
+
+        assert_eq!(Balances::reserved_balance_named(&ORACLE_RESERVE_ID, &1), 0);
+        assert_eq!(Balances::reserved_balance_named(&REGISTRY_RESERVE_ID, &1), 500);
+        assert_eq!(Balances::reserved_balance(&1), 500);
+    });
+}
+
+#[test]
+fn migration_moves_anonymous_deposits_into_named_reserve() {
+    new_test_ext().execute_with(|| {
+        // A validator registered when deposits were reserved anonymously, next to a
+        // facility deposit the registry has already moved to its named reserve
+        StorageVersion::new(0).put::<Oracle>();
+        assert_ok!(Balances::reserve(&1, DEPOSIT));
+        assert_ok!(Balances::reserve_named(&REGISTRY_RESERVE_ID, &1, 500));
+        Validators::<Test>::insert(1, ValidatorInfo { account: 1, registered_at: 1, update_count: 0, last_update: 1 });
+        ValidatorCount::<Test>::put(1);
+
+        Oracle::on_runtime_upgrade();
+
+        assert_eq!(Balances::reserved_balance_named(&ORACLE_RESERVE_ID, &1), DEPOSIT);
+        assert_eq!(Balances::reserved_balance_named(&REGISTRY_RESERVE_ID, &1), 500);
+        assert_eq!(Balances::reserved_balance(&1), DEPOSIT + 500);
+        assert_eq!(Oracle::on_chain_storage_version(), StorageVersion::new(1));
+
+        // The migrated deposit is released in full on removal
+        assert_ok!(Oracle::remove_validator(RuntimeOrigin::signed(1)));
+        assert_eq!(Balances::reserved_balance(&1), 500);
+    });
+}
+
+#[test]
+fn removing_validator_frees_only_its_own_asset_slots() {
+    new_test_ext().execute_with(|| {
+        for who in 1..=4 {
+            assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(who)));
+            assert_ok!(Oracle::update_price(RuntimeOrigin::signed(who), b"NRSH".to_vec(), 100));
+        }
+        assert_ok!(Oracle::update_price(RuntimeOrigin::signed(1), b"ELXR".to_vec(), 50));
+        assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(5)));
+        assert_noop!(
+            Oracle::update_price(RuntimeOrigin::signed(5), b"NRSH".to_vec(), 100),
+            Error::<Test>::AssetValidatorLimitReached
+        );
+
+        assert_ok!(Oracle::remove_validator(RuntimeOrigin::signed(1)));
+
+        assert!(ValidatorAssets::<Test>::get(1).is_empty());
+        assert!(!Submissions::<Test>::contains_key(b"NRSH".to_vec(), 1));
+        assert!(!Submissions::<Test>::contains_key(b"ELXR".to_vec(), 1));
+        assert_eq!(AssetSubmitterCount::<Test>::get(b"NRSH".to_vec()), 3);
+        assert_eq!(AssetSubmitterCount::<Test>::get(b"ELXR".to_vec()), 0);
+        assert_eq!(Submissions::<Test>::get(b"NRSH".to_vec(), 2), Some(100));
+        assert_ok!(Oracle::update_price(RuntimeOrigin::signed(5), b"NRSH".to_vec(), 100));
+    });
+}
+
+#[test]
+fn validator_is_capped_at_max_assets() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(1)));
+        assert_ok!(Oracle::update_price(RuntimeOrigin::signed(1), b"NRSH".to_vec(), 100));
+        assert_ok!(Oracle::update_price(RuntimeOrigin::signed(1), b"ELXR".to_vec(), 50));
+
+        assert_noop!(
+            Oracle::update_price(RuntimeOrigin::signed(1), b"KOMB".to_vec(), 10),
+            Error::<Test>::ValidatorAssetLimitReached
+        );
+        assert_eq!(AssetSubmitterCount::<Test>::get(b"KOMB".to_vec()), 0);
+
+        // Updating an asset already submitted for does not count against the cap
+        assert_ok!(Oracle::update_price(RuntimeOrigin::signed(1), b"NRSH".to_vec(), 110));
+        assert_eq!(ValidatorAssets::<Test>::get(1).len(), 2);
+    });
+}
+
+#[test]
+fn removal_weight_tracks_assets_removed() {
+    new_test_ext().execute_with(|| {
+        assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(1)));
+        assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(2)));
+        assert_ok!(Oracle::update_price(RuntimeOrigin::signed(2), b"NRSH".to_vec(), 100));
+
+        let idle = Oracle::remove_validator(RuntimeOrigin::signed(1)).unwrap();
+        let busy = Oracle::remove_validator(RuntimeOrigin::signed(2)).unwrap();
+
+        assert_eq!(idle.actual_weight, Some(Oracle::remove_validator_weight(0)));
+        assert_eq!(busy.actual_weight, Some(Oracle::remove_validator_weight(1)));
+        assert!(Oracle::remove_validator_weight(1).all_gt(Oracle::remove_validator_weight(0)));
+        assert!(Oracle::remove_validator_weight(2).all_gte(Oracle::remove_validator_weight(1)));
+    });
+}
+
+#[test]
+fn bonded_stake_counts_toward_stake_and_is_released_on_removal() {
+    new_test_ext().execute_with(|| {
+        assert_noop!(Oracle::bond_stake(RuntimeOrigin::signed(1), 500), Error::<Test>::ValidatorNotFound);
+        assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(1)));
+        assert_noop!(Oracle::bond_stake(RuntimeOrigin::signed(1), 0), Error::<Test>::ZeroStake);
+
+        assert_ok!(Oracle::bond_stake(RuntimeOrigin::signed(1), 500));
+        assert_eq!(Oracle::stake_of(&1), DEPOSIT + 500);
+
+        assert_ok!(Oracle::remove_validator(RuntimeOrigin::signed(1)));
+        assert_eq!(Balances::reserved_balance(&1), 0);
+    });
+}
+
+#[test]
+fn each_aggregation_method_finalizes_a_different_price() {
+    new_test_ext().execute_with(|| {
+        // Validator 3 backs the outlier with most of the stake
+        for (who, price) in [(1, 100), (2, 200), (3, 900)] {
+            assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(who)));
+            assert_ok!(Oracle::update_price(RuntimeOrigin::signed(who), b"NRSH".to_vec(), price));
+        }
+        assert_ok!(Oracle::bond_stake(RuntimeOrigin::signed(3), 10_000));
+
+        let finalized = |method| {
+            assert_ok!(Oracle::register_asset(RuntimeOrigin::root(), b"NRSH".to_vec(), method));
+            assert_ok!(Oracle::aggregate_prices(RuntimeOrigin::signed(4), b"NRSH".to_vec()));
+            PriceFeeds::<Test>::get(b"NRSH".to_vec()).unwrap().price
+        };
+
+        assert_eq!(finalized(AggregationMethod::Median), 200);
+        assert_eq!(finalized(AggregationMethod::Mean), 400);
+        assert_eq!(finalized(AggregationMethod::StakeWeightedMedian), 900);
+    });
+}
+
+#[test]
+fn equal_stakes_weight_submissions_like_the_median() {
+    new_test_ext().execute_with(|| {
+        for (who, price) in [(1, 100), (2, 200), (3, 900)] {
+            assert_ok!(Oracle::register_validator(RuntimeOrigin::signed(who)));
+            assert_ok!(Oracle::update_price(RuntimeOrigin::signed(who), b"NRSH".to_vec(), price));
+        }
+
+        assert_ok!(Oracle::register_asset(RuntimeOrigin::root(), b"NRSH".to_vec(), AggregationMethod::StakeWeightedMedian));
+        assert_ok!(Oracle::aggregate_prices(RuntimeOrigin::signed(4), b"NRSH".to_vec()));
+
+        assert_eq!(PriceFeeds::<Test>::get(b"NRSH".to_vec()).unwrap().price, 200);
+    });
+}
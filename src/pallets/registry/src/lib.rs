@@ -2,34 +2,93 @@
 
 pub use pallet::*;
 
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
 #[frame_support::pallet]
 pub mod pallet {
     use frame_support::{
         dispatch::DispatchResult,
         pallet_prelude::*,
-        traits::{Currency, ReservableCurrency, ExistenceRequirement},
+        traits::{Currency, NamedReservableCurrency, ReservableCurrency, ExistenceRequirement},
     };
     use frame_system::pallet_prelude::*;
     use sp_std::prelude::*;
-    use sp_runtime::traits::{StaticLookup, Zero};
+    use sp_runtime::traits::{Hash as HashT, StaticLookup, Zero};
 
     type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
+    /// Named reserve holding facility deposits, kept apart from other pallets' reserves
+    pub const REGISTRY_RESERVE_ID: [u8; 8] = *b"elxregst";
+
+    /// Redundancy level recorded in the parity of stored certification blobs
+    pub const CERTIFICATION_REDUNDANCY: u8 = 1;
+
+    /// Version 1 links batches to their ELXR fermentation batch; version 2 holds
+    /// facility deposits under `REGISTRY_RESERVE_ID`
+    const STORAGE_VERSION: StorageVersion = StorageVersion::new(2);
+
     #[pallet::config]
     pub trait Config: frame_system::Config {
         type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
-        type Currency: ReservableCurrency<Self::AccountId>;
+        type Currency: NamedReservableCurrency<Self::AccountId, ReserveIdentifier = [u8; 8]>;
         #[pallet::constant]
         type RegistrationDeposit: Get<BalanceOf<Self>>;
         type MaxFacilityNameLength: Get<u32>;
         type MaxLocationLength: Get<u32>;
         type MaxCertificationLength: Get<u32>;
+        /// Hasher deriving batch hashes from batch ids. Usually the runtime's `Hashing`;
+        /// `Keccak256` lets Ethereum contracts verify batch provenance.
+        type BatchHasher: HashT<Output = Self::Hash>;
+        #[pallet::constant]
+        type MaxCheckpoints: Get<u32>;
+        /// Longest free-text reason a recall may give with `RecallReason::Other`
+        #[pallet::constant]
+        type MaxRecallReasonLength: Get<u32>;
+        /// ELXR fermentation batches registry batches can be produced from
+        type Fermentation: FermentationSource<Self::Hash>;
+    }
+
+    /// Completion state of fermentation batches tracked by the ELXR pallet
+    pub trait FermentationSource<Hash> {
+        /// Whether fermentation of the batch has completed
+        fn is_completed(batch_id: &Vec<u8>) -> bool;
+        /// Completion certificate issued for the batch, if any
+        fn completion_certificate(batch_id: &Vec<u8>) -> Option<Hash>;
+    }
+
+    impl<Hash> FermentationSource<Hash> for () {
+        fn is_completed(_batch_id: &Vec<u8>) -> bool {
+            false
+        }
+
+        fn completion_certificate(_batch_id: &Vec<u8>) -> Option<Hash> {
+            None
+        }
     }
 
     #[pallet::pallet]
     #[pallet::generate_store(pub(super) trait Store)]
+    #[pallet::storage_version(STORAGE_VERSION)]
     pub struct Pallet<T>(_);
 
+    #[pallet::hooks]
+    impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+        fn on_runtime_upgrade() -> Weight {
+            let mut weight = T::DbWeight::get().reads(1);
+            let version = StorageVersion::get::<Pallet<T>>();
+            if version < 1 {
+                weight = weight.saturating_add(Self::migrate_to_v1());
+            }
+            if version < 2 {
+                weight = weight.saturating_add(Self::migrate_to_v2());
+            }
+            weight
+        }
+    }
+
     #[pallet::storage]
     #[pallet::getter(fn facilities)]
     pub type Facilities<T: Config> = StorageMap<
@@ -56,15 +115,34 @@ pub mod pallet {
     #[pallet::getter(fn batch_count)]
     pub type BatchCount<T: Config> = StorageValue<_, u32, ValueQuery>;
 
+    #[pallet::storage]
+    #[pallet::getter(fn checkpoints)]
+    pub type Checkpoints<T: Config> = StorageMap<
+        _,
+        Blake2_128Concat,
+        T::Hash, // Batch hash
+        BoundedVec<(T::BlockNumber, T::Hash), T::MaxCheckpoints>,
+        ValueQuery,
+    >;
+
+    #[pallet::storage]
+    #[pallet::getter(fn shipping_paused)]
+    pub type ShippingPaused<T: Config> = StorageValue<_, bool, ValueQuery>;
+
     #[pallet::event]
     #[pallet::generate_deposit(pub(super) fn deposit_event)]
     pub enum Event<T: Config> {
         FacilityRegistered(T::AccountId, Vec<u8>),
         FacilityUpdated(T::AccountId, Vec<u8>),
+        FacilityDeregistered(T::AccountId),
         BatchRegistered(T::AccountId, T::Hash, Vec<u8>),
         BatchCertified(T::Hash, Vec<u8>),
         BatchShipped(T::Hash, T::AccountId),
         BatchReceived(T::Hash, T::AccountId),
+        BatchCheckpointed(T::Hash, T::Hash),
+        BatchRecalled(T::Hash, RecallReason<T>),
+        ShippingHalted,
+        ShippingResumed,
     }
 
     #[pallet::error]
@@ -79,6 +157,21 @@ pub mod pallet {
         CertificationTooLong,
         InsufficientBalance,
         NotAuthorized,
+        ErrorCorrectionFailed,
+        InvalidStatusTransition,
+        ShippingPaused,
+        TooManyCheckpoints,
+        FermentationNotCompleted,
+    }
+
+    /// Why a batch was recalled
+    #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+    pub enum RecallReason<T: Config> {
+        Contamination,
+        QualityFailure,
+        RegulatoryHold,
+        /// Free text for anything the other reasons do not cover
+        Other(BoundedVec<u8, T::MaxRecallReasonLength>),
     }
 
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -96,9 +189,23 @@ pub mod pallet {
         pub facility: T::AccountId,
         pub batch_id: Vec<u8>,
         pub production_date: T::BlockNumber,
+        /// Certification blob with classical parity appended; read it through `certification_of`
         pub certification: Vec<u8>,
         pub current_owner: T::AccountId,
         pub status: BatchStatus,
+        /// ELXR fermentation batch this batch was produced from, with its completion certificate
+        pub fermentation: Option<(Vec<u8>, T::Hash)>,
+    }
+
+    // `BatchInfo` layout before batches were linked to fermentation batches (storage version 0)
+    #[derive(Encode, Decode)]
+    struct BatchInfoV0<T: Config> {
+        facility: T::AccountId,
+        batch_id: Vec<u8>,
+        production_date: T::BlockNumber,
+        certification: Vec<u8>,
+        current_owner: T::AccountId,
+        status: BatchStatus,
     }
 
     #[derive(Clone, Encode, Decode, Eq, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -107,6 +214,23 @@ pub mod pallet {
         Certified,
         InTransit,
         Delivered,
+        Recalled,
+        Split,
+        Merged,
+    }
+
+    impl BatchStatus {
+        // Produced → Certified → InTransit → Delivered. Any live batch can be recalled,
+        // and batches not in transit can be split or merged; those three are terminal.
+        pub fn can_transition_to(&self, next: &BatchStatus) -> bool {
+            use BatchStatus::*;
+            match (self, next) {
+                (Produced, Certified) | (Certified, InTransit) | (InTransit, Delivered) => true,
+                (Produced | Certified | InTransit | Delivered, Recalled) => true,
+                (Produced | Certified | Delivered, Split | Merged) => true,
+                _ => false,
+            }
+        }
     }
 
     #[pallet::call]
@@ -127,7 +251,7 @@ pub mod pallet {
             ensure!(certification.len() <= T::MaxCertificationLength::get() as usize, Error::<T>::CertificationTooLong);
             
             let deposit = T::RegistrationDeposit::get();
-            T::Currency::reserve(&who, deposit)?;
+            T::Currency::reserve_named(&REGISTRY_RESERVE_ID, &who, deposit)?;
             
             let facility_info = FacilityInfo {
                 owner: who.clone(),
@@ -173,6 +297,7 @@ pub mod pallet {
             Ok(())
         }
         
+        /// Register a batch produced by the caller's facility
         #[pallet::call_index(2)]
         #[pallet::weight(10_000)]
         pub fn register_batch(
@@ -181,33 +306,7 @@ pub mod pallet {
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             
-            ensure!(Facilities::<T>::contains_key(&who), Error::<T>::FacilityNotFound);
-            
-            let batch_hash = T::Hashing::hash_of(&batch_id);
-            ensure!(!Batches::<T>::contains_key(batch_hash), Error::<T>::BatchAlreadyRegistered);
-            
-            let batch_info = BatchInfo {
-                facility: who.clone(),
-                batch_id: batch_id.clone(),
-                production_date: <frame_system::Pallet<T>>::block_number(),
-                certification: Vec::new(),
-                current_owner: who.clone(),
-                status: BatchStatus::Produced,
-            };
-            
-            Batches::<T>::insert(batch_hash, batch_info);
-            
-            Facilities::<T>::mutate(&who, |facility| {
-                if let Some(f) = facility {
-                    f.batch_count += 1;
-                }
-            });
-            
-            let count = BatchCount::<T>::get();
-            BatchCount::<T>::put(count + 1);
-            
-            Self::deposit_event(Event::BatchRegistered(who, batch_hash, batch_id));
-            Ok(())
+            Self::do_register_batch(who, batch_id, None)
         }
         
         #[pallet::call_index(3)]
@@ -224,10 +323,13 @@ pub mod pallet {
             
             let batch = Batches::<T>::get(batch_hash).ok_or(Error::<T>::BatchNotFound)?;
             ensure!(batch.facility == who, Error::<T>::NotAuthorized);
+            ensure!(batch.status.can_transition_to(&BatchStatus::Certified), Error::<T>::InvalidStatusTransition);
             
+            // Stored with parity so damage to the blob can be corrected on read
+            let protected = elixir_error_correction::classical::encode(&certification, CERTIFICATION_REDUNDANCY);
             Batches::<T>::mutate(batch_hash, |b| {
                 if let Some(batch) = b {
-                    batch.certification = certification.clone();
+                    batch.certification = protected;
                     batch.status = BatchStatus::Certified;
                 }
             });
@@ -246,10 +348,13 @@ pub mod pallet {
             let who = ensure_signed(origin)?;
             let to = T::Lookup::lookup(destination)?;
             
+            ensure!(!ShippingPaused::<T>::get(), Error::<T>::ShippingPaused);
+            
             ensure!(Batches::<T>::contains_key(batch_hash), Error::<T>::BatchNotFound);
             
             let batch = Batches::<T>::get(batch_hash).ok_or(Error::<T>::BatchNotFound)?;
             ensure!(batch.current_owner == who, Error::<T>::NotAuthorized);
+            ensure!(batch.status.can_transition_to(&BatchStatus::InTransit), Error::<T>::InvalidStatusTransition);
             
             Batches::<T>::mutate(batch_hash, |b| {
                 if let Some(batch) = b {
@@ -265,12 +370,17 @@ pub mod pallet {
         #[pallet::weight(10_000)]
         pub fn receive_batch(
             origin: OriginFor<T>,
-            batch_hash: T::Hash>,
+            batch_hash: T::Hash,
         ) -> DispatchResult {
             let who = ensure_signed(origin)?;
             
+            ensure!(!ShippingPaused::<T>::get(), Error::<T>::ShippingPaused);
+            
             ensure!(Batches::<T>::contains_key(batch_hash), Error::<T>::BatchNotFound);
             
+            let batch = Batches::<T>::get(batch_hash).ok_or(Error::<T>::BatchNotFound)?;
+            ensure!(batch.status.can_transition_to(&BatchStatus::Delivered), Error::<T>::InvalidStatusTransition);
+            
             Batches::<T>::mutate(batch_hash, |b| {
                 if let Some(batch) = b {
                     batch.current_owner = who.clone();
@@ -281,29 +391,210 @@ pub mod pallet {
             Self::deposit_event(Event::BatchReceived(batch_hash, who));
             Ok(())
         }
+        
+        #[pallet::call_index(6)]
+        #[pallet::weight(10_000)]
+        pub fn deregister_facility(
+            origin: OriginFor<T>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            ensure!(Facilities::<T>::contains_key(&who), Error::<T>::FacilityNotFound);
+            
+            let deposit = T::RegistrationDeposit::get();
+            T::Currency::unreserve_named(&REGISTRY_RESERVE_ID, &who, deposit);
+            
+            Facilities::<T>::remove(&who);
+            
+            let count = FacilityCount::<T>::get();
+            FacilityCount::<T>::put(count - 1);
+            
+            Self::deposit_event(Event::FacilityDeregistered(who));
+            Ok(())
+        }
+        
+        #[pallet::call_index(7)]
+        #[pallet::weight(10_000)]
+        pub fn set_shipping_paused(
+            origin: OriginFor<T>,
+            paused: bool,
+        ) -> DispatchResult {
+            ensure_root(origin)?;
+            
+            // Only shipping and receiving halt; registration, certification, recalls and reads continue
+            ShippingPaused::<T>::put(paused);
+            
+            if paused {
+                Self::deposit_event(Event::ShippingHalted);
+            } else {
+                Self::deposit_event(Event::ShippingResumed);
+            }
+            Ok(())
+        }
+        
+        #[pallet::call_index(8)]
+        #[pallet::weight(10_000)]
+        pub fn checkpoint_batch(
+            origin: OriginFor<T>,
+            batch_hash: T::Hash,
+            external_hash: T::Hash,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            let batch = Batches::<T>::get(batch_hash).ok_or(Error::<T>::BatchNotFound)?;
+            ensure!(batch.current_owner == who, Error::<T>::NotAuthorized);
+            
+            // Anchors an off-chain record (e.g. a lab report) to the batch
+            let now = <frame_system::Pallet<T>>::block_number();
+            Checkpoints::<T>::try_mutate(batch_hash, |checkpoints| {
+                checkpoints.try_push((now, external_hash)).map_err(|_| Error::<T>::TooManyCheckpoints)
+            })?;
+            
+            Self::deposit_event(Event::BatchCheckpointed(batch_hash, external_hash));
+            Ok(())
+        }
+        
+        /// Recall a batch, by its producing facility or root, recording why in the event.
+        /// Stays available while shipping is paused, so compromised batches can be pulled
+        /// during a halt.
+        #[pallet::call_index(9)]
+        #[pallet::weight(10_000)]
+        pub fn recall_batch(
+            origin: OriginFor<T>,
+            batch_hash: T::Hash,
+            reason: RecallReason<T>,
+        ) -> DispatchResult {
+            let who = frame_system::ensure_signed_or_root(origin)?;
+            
+            let batch = Batches::<T>::get(batch_hash).ok_or(Error::<T>::BatchNotFound)?;
+            if let Some(who) = who {
+                ensure!(batch.facility == who, Error::<T>::NotAuthorized);
+            }
+            ensure!(batch.status.can_transition_to(&BatchStatus::Recalled), Error::<T>::InvalidStatusTransition);
+            
+            Batches::<T>::mutate(batch_hash, |b| {
+                if let Some(batch) = b {
+                    batch.status = BatchStatus::Recalled;
+                }
+            });
+            
+            Self::deposit_event(Event::BatchRecalled(batch_hash, reason));
+            Ok(())
+        }
+        
+        /// Register a batch produced by the caller's facility, linked to the completed
+        /// ELXR fermentation batch it was produced from
+        #[pallet::call_index(10)]
+        #[pallet::weight(10_000)]
+        pub fn register_linked_batch(
+            origin: OriginFor<T>,
+            batch_id: Vec<u8>,
+            fermentation_batch: Vec<u8>,
+        ) -> DispatchResult {
+            let who = ensure_signed(origin)?;
+            
+            Self::do_register_batch(who, batch_id, Some(fermentation_batch))
+        }
     }
 
-    // Implement error correction mechanisms as per project requirements
     impl<T: Config> Pallet<T> {
-        // Classical error correction
-        pub fn verify_and_correct_data(data: &mut Vec<u8>) -> Result<(), Error<T>> {
-            // Reed-Solomon error correction implementation
-            // This is a placeholder for the actual implementation
+        // Register `batch_id` under `who`'s facility, linked to `fermentation_batch` if given
+        fn do_register_batch(
+            who: T::AccountId,
+            batch_id: Vec<u8>,
+            fermentation_batch: Option<Vec<u8>>,
+        ) -> DispatchResult {
+            ensure!(Facilities::<T>::contains_key(&who), Error::<T>::FacilityNotFound);
+            
+            let batch_hash = T::BatchHasher::hash_of(&batch_id);
+            ensure!(!Batches::<T>::contains_key(batch_hash), Error::<T>::BatchAlreadyRegistered);
+            
+            // A linked fermentation batch must have completed and been certified
+            let fermentation = match fermentation_batch {
+                Some(elxr_batch) => {
+                    let certificate = T::Fermentation::is_completed(&elxr_batch)
+                        .then(|| T::Fermentation::completion_certificate(&elxr_batch))
+                        .flatten()
+                        .ok_or(Error::<T>::FermentationNotCompleted)?;
+                    Some((elxr_batch, certificate))
+                }
+                None => None,
+            };
+            
+            let batch_info = BatchInfo {
+                facility: who.clone(),
+                batch_id: batch_id.clone(),
+                production_date: <frame_system::Pallet<T>>::block_number(),
+                certification: Vec::new(),
+                current_owner: who.clone(),
+                status: BatchStatus::Produced,
+                fermentation,
+            };
+            
+            Batches::<T>::insert(batch_hash, batch_info);
+            
+            Facilities::<T>::mutate(&who, |facility| {
+                if let Some(f) = facility {
+                    f.batch_count += 1;
+                }
+            });
+            
+            let count = BatchCount::<T>::get();
+            BatchCount::<T>::put(count + 1);
+            
+            Self::deposit_event(Event::BatchRegistered(who, batch_hash, batch_id));
             Ok(())
         }
 
-        // Bridge error correction for classical-quantum interface
-        pub fn bridge_error_correction(data: &mut Vec<u8>) -> Result<(), Error<T>> {
-            // Implement redundancy and verification protocols
-            // This is a placeholder for the actual implementation
-            Ok(())
+        // Batches registered before fermentation linking have no fermentation batch.
+        // Certifications were stored raw and gain the parity `certification_of` reads.
+        fn migrate_to_v1() -> Weight {
+            let mut migrated = 0u64;
+            Batches::<T>::translate::<BatchInfoV0<T>, _>(|_, old| {
+                migrated += 1;
+                Some(BatchInfo {
+                    facility: old.facility,
+                    batch_id: old.batch_id,
+                    production_date: old.production_date,
+                    certification: if old.certification.is_empty() {
+                        old.certification
+                    } else {
+                        elixir_error_correction::classical::encode(&old.certification, CERTIFICATION_REDUNDANCY)
+                    },
+                    current_owner: old.current_owner,
+                    status: old.status,
+                    fermentation: None,
+                })
+            });
+            StorageVersion::new(1).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(migrated, migrated + 1)
         }
 
-        // Quantum error correction
-        pub fn quantum_error_correction(data: &mut Vec<u8>) -> Result<(), Error<T>> {
-            // Surface code implementation for quantum error correction
-            // This is a placeholder for the actual implementation
-            Ok(())
+        // Move each facility's deposit from the anonymous reserve into `REGISTRY_RESERVE_ID`
+        fn migrate_to_v2() -> Weight {
+            let deposit = T::RegistrationDeposit::get();
+            let mut migrated = 0u64;
+            for who in Facilities::<T>::iter_keys() {
+                migrated += 1;
+                let moved = deposit.saturating_sub(T::Currency::unreserve(&who, deposit));
+                // Cannot fail: the amount was freed just above
+                let _ = T::Currency::reserve_named(&REGISTRY_RESERVE_ID, &who, moved);
+            }
+            StorageVersion::new(2).put::<Pallet<T>>();
+
+            T::DbWeight::get().reads_writes(migrated.saturating_mul(2), migrated.saturating_mul(2) + 1)
+        }
+    }
+
+    // Implement error correction mechanisms as per project requirements
+    impl<T: Config> Pallet<T> {
+        /// Certification blob of `batch_hash`, with damaged parity corrected
+        pub fn certification_of(batch_hash: T::Hash) -> Result<Vec<u8>, Error<T>> {
+            let batch = Batches::<T>::get(batch_hash).ok_or(Error::<T>::BatchNotFound)?;
+            let (payload, _) = elixir_error_correction::classical::decode(&batch.certification)
+                .ok_or(Error::<T>::ErrorCorrectionFailed)?;
+            Ok(payload)
         }
     }
 }
@@ -0,0 +1,131 @@
+//! Test runtime for the kombucha registry pallet.
+
+use crate as pallet_kombucha_registry;
+use frame_support::{
+    parameter_types,
+    traits::{ConstU128, ConstU32, ConstU64, Everything},
+};
+use sp_core::H256;
+use sp_runtime::{
+    testing::Header,
+    traits::{BlakeTwo256, IdentityLookup, Keccak256},
+};
+use sp_std::{cell::RefCell, collections::btree_map::BTreeMap};
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+pub type AccountId = u64;
+pub type Balance = u128;
+
+/// Deposit reserved for each registered facility
+pub const DEPOSIT: Balance = 1_000;
+
+frame_support::construct_runtime!(
+    pub enum Test where
+        Block = Block,
+        NodeBlock = Block,
+        UncheckedExtrinsic = UncheckedExtrinsic,
+    {
+        System: frame_system,
+        Balances: pallet_balances,
+        Registry: pallet_kombucha_registry,
+    }
+);
+
+impl frame_system::Config for Test {
+    type BaseCallFilter = Everything;
+    type BlockWeights = ();
+    type BlockLength = ();
+    type DbWeight = ();
+    type RuntimeOrigin = RuntimeOrigin;
+    type RuntimeCall = RuntimeCall;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<Self::AccountId>;
+    type Header = Header;
+    type RuntimeEvent = RuntimeEvent;
+    type BlockHashCount = ConstU64<250>;
+    type Version = ();
+    type PalletInfo = PalletInfo;
+    type AccountData = pallet_balances::AccountData<Balance>;
+    type OnNewAccount = ();
+    type OnKilledAccount = ();
+    type SystemWeightInfo = ();
+    type SS58Prefix = ();
+    type OnSetCode = ();
+    type MaxConsumers = ConstU32<16>;
+}
+
+impl pallet_balances::Config for Test {
+    type MaxLocks = ConstU32<50>;
+    type MaxReserves = ConstU32<2>;
+    type ReserveIdentifier = [u8; 8];
+    type Balance = Balance;
+    type RuntimeEvent = RuntimeEvent;
+    type DustRemoval = ();
+    type ExistentialDeposit = ConstU128<1>;
+    type AccountStore = System;
+    type WeightInfo = ();
+}
+
+thread_local! {
+    static CERTIFICATES: RefCell<BTreeMap<Vec<u8>, H256>> = RefCell::new(BTreeMap::new());
+}
+
+/// Fermentation batches completed by each test, with their completion certificates
+pub struct MockFermentation;
+
+impl MockFermentation {
+    /// Mark `batch_id` as completed with `certificate`
+    pub fn complete(batch_id: &[u8], certificate: H256) {
+        CERTIFICATES.with(|c| c.borrow_mut().insert(batch_id.to_vec(), certificate));
+    }
+}
+
+impl pallet_kombucha_registry::FermentationSource<H256> for MockFermentation {
+    fn is_completed(batch_id: &Vec<u8>) -> bool {
+        CERTIFICATES.with(|c| c.borrow().contains_key(batch_id))
+    }
+
+    fn completion_certificate(batch_id: &Vec<u8>) -> Option<H256> {
+        CERTIFICATES.with(|c| c.borrow().get(batch_id).copied())
+    }
+}
+
+parameter_types! {
+    pub const RegistrationDeposit: Balance = DEPOSIT;
+}
+
+impl pallet_kombucha_registry::Config for Test {
+    type RuntimeEvent = RuntimeEvent;
+    type Currency = Balances;
+    type RegistrationDeposit = RegistrationDeposit;
+    type MaxFacilityNameLength = ConstU32<32>;
+    type MaxLocationLength = ConstU32<32>;
+    type MaxCertificationLength = ConstU32<64>;
+    // Differs from `Hashing` so tests catch batch hashes derived with the wrong hasher
+    type BatchHasher = Keccak256;
+    type MaxCheckpoints = ConstU32<4>;
+    type MaxRecallReasonLength = ConstU32<32>;
+    type Fermentation = MockFermentation;
+}
+
+/// Accounts endowed at genesis
+pub const ENDOWED: [AccountId; 4] = [1, 2, 3, 4];
+
+pub fn new_test_ext() -> sp_io::TestExternalities {
+    let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    pallet_balances::GenesisConfig::<Test> {
+        balances: ENDOWED.iter().map(|who| (*who, 100_000)).collect(),
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    let mut ext = sp_io::TestExternalities::new(storage);
+    ext.execute_with(|| System::set_block_number(1));
+    ext
+}
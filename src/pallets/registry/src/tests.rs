@@ -0,0 +1,461 @@
+//! Tests for the kombucha registry pallet.
+
+use crate::{
+    mock::*, BatchInfo, BatchStatus, Batches, Error, FacilityCount, FacilityInfo, Facilities, RecallReason, CERTIFICATION_REDUNDANCY,
+    REGISTRY_RESERVE_ID,
+};
+use codec::{Decode, Encode};
+use frame_support::{
+    assert_noop, assert_ok,
+    traits::{GetStorageVersion, Hooks, NamedReservableCurrency, ReservableCurrency, StorageVersion},
+};
+use sp_core::H256;
+use sp_runtime::traits::{BlakeTwo256, Hash};
+
+fn register_facility(who: AccountId) {
+    assert_ok!(Registry::register_facility(
+        RuntimeOrigin::signed(who),
+        b"Brewery".to_vec(),
+        b"Portland".to_vec(),
+        Vec::new(),
+    ));
+}
+
+fn batch_hash(batch_id: &[u8]) -> H256 {
+    <Test as crate::Config>::BatchHasher::hash_of(&batch_id.to_vec())
+}
+
+#[test]
+fn unlinked_batch_has_no_fermentation() {
+    new_test_ext().execute_with(|| {
+        register_facility(1);
+
+        assert_ok!(Registry::register_batch(RuntimeOrigin::signed(1), b"kb-1".to_vec()));
+
+        let batch = Batches::<Test>::get(batch_hash(b"kb-1")).unwrap();
+        assert_eq!(batch.fermentation, None);
+        assert_eq!(batch.status, BatchStatus::Produced);
+    });
+}
+
+#[test]
+fn batch_is_keyed_by_keccak_hash_of_its_id() {
+    new_test_ext().execute_with(|| {
+        register_facility(1);
+        let batch_id = b"kb-1".to_vec();
+        // What an Ethereum contract computes over the SCALE-encoded batch id
+        let expected = H256(sp_io::hashing::keccak_256(&batch_id.encode()));
+        assert_ne!(expected, BlakeTwo256::hash_of(&batch_id));
+
+        assert_ok!(Registry::register_batch(RuntimeOrigin::signed(1), batch_id.clone()));
+
+        assert_eq!(Batches::<Test>::get(expected).unwrap().batch_id, batch_id);
+        System::assert_last_event(crate::Event::BatchRegistered(1, expected, batch_id).into());
+    });
+}
+
+#[test]
+fn register_batch_keeps_its_original_call_encoding() {
+    let batch_id = b"kb-1".to_vec();
+    let call = crate::Call::<Test>::register_batch { batch_id: batch_id.clone() };
+
+    let mut expected = vec![2u8];
+    expected.extend(batch_id.encode());
+    assert_eq!(call.encode(), expected);
+}
+
+#[test]
+fn linked_batch_records_completion_certificate() {
+    new_test_ext().execute_with(|| {
+        register_facility(1);
+        let certificate = H256::repeat_byte(7);
+        MockFermentation::complete(b"elxr-1", certificate);
+
+        assert_ok!(Registry::register_linked_batch(RuntimeOrigin::signed(1), b"kb-1".to_vec(), b"elxr-1".to_vec()));
+
+        let batch = Batches::<Test>::get(batch_hash(b"kb-1")).unwrap();
+        assert_eq!(batch.fermentation, Some((b"elxr-1".to_vec(), certificate)));
+    });
+}
+
+#[test]
+fn linking_incomplete_fermentation_batch_fails() {
+    new_test_ext().execute_with(|| {
+        register_facility(1);
+
+        assert_noop!(
+            Registry::register_linked_batch(RuntimeOrigin::signed(1), b"kb-1".to_vec(), b"elxr-unknown".to_vec()),
+            Error::<Test>::FermentationNotCompleted
+        );
+    });
+}
+
+#[test]
+fn migration_adds_empty_fermentation_link() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(0).put::<Registry>();
+        let hash = batch_hash(b"kb-old");
+        // Version 0 layout: (facility, batch_id, production_date, certification, current_owner, status)
+        let old = (1u64, b"kb-old".to_vec(), 1u64, b"cert".to_vec(), 2u64, BatchStatus::Delivered);
+        sp_io::storage::set(&Batches::<Test>::hashed_key_for(hash), &old.encode());
+
+        Registry::on_runtime_upgrade();
+
+        assert_eq!(
+            Batches::<Test>::get(hash),
+            Some(BatchInfo {
+                facility: 1,
+                batch_id: b"kb-old".to_vec(),
+                production_date: 1,
+                certification: elixir_error_correction::classical::encode(b"cert", CERTIFICATION_REDUNDANCY),
+                current_owner: 2,
+                status: BatchStatus::Delivered,
+                fermentation: None,
+            })
+        );
+        assert_eq!(Registry::certification_of(hash), Ok(b"cert".to_vec()));
+        assert_eq!(Registry::on_chain_storage_version(), StorageVersion::new(2));
+    });
+}
+
+/// Reserve the daemonless oracle holds validator deposits under
+const ORACLE_RESERVE_ID: [u8; 8] = *b"elxoracl";
+
+#[test]
+fn deregistering_facility_leaves_oracle_deposit_intact() {
+    new_test_ext().execute_with(|| {
+        // Account 1 is also an oracle validator
+        assert_ok!(Balances::reserve_named(&ORACLE_RESERVE_ID, &1, 300));
+        register_facility(1);
+        assert_eq!(Balances::reserved_balance_named(&REGISTRY_RESERVE_ID, &1), DEPOSIT);
+
+        assert_ok!(Registry::deregister_facility(RuntimeOrigin::signed(1)));
+
+        assert_eq!(Balances::reserved_balance_named(&REGISTRY_RESERVE_ID, &1), 0);
+        assert_eq!(Balances::reserved_balance_named(&ORACLE_RESERVE_ID, &1), 300);
+        assert_eq!(Balances::reserved_balance(&1), 300);
+    });
+}
+
+#[test]
+fn migration_moves_anonymous_deposits_into_named_reserve() {
+    new_test_ext().execute_with(|| {
+        StorageVersion::new(1).put::<Registry>();
+        assert_ok!(Balances::reserve(&1, DEPOSIT));
+        assert_ok!(Balances::reserve_named(&ORACLE_RESERVE_ID, &1, 300));
+        Facilities::<Test>::insert(1, FacilityInfo {
+            owner: 1,
+            name: b"Brewery".to_vec(),
+            location: Vec::new(),
+            certification: Vec::new(),
+            registered_at: 1,
+            batch_count: 0,
+        });
+        FacilityCount::<Test>::put(1);
+
+        Registry::on_runtime_upgrade();
+
+        assert_eq!(Balances::reserved_balance_named(&REGISTRY_RESERVE_ID, &1), DEPOSIT);
+        assert_eq!(Balances::reserved_balance_named(&ORACLE_RESERVE_ID, &1), 300);
+        assert_eq!(Registry::on_chain_storage_version(), StorageVersion::new(2));
+
+        assert_ok!(Registry::deregister_facility(RuntimeOrigin::signed(1)));
+        assert_eq!(Balances::reserved_balance(&1), 300);
+    });
+}
+
+fn certify(batch_id: &[u8], certification: &[u8]) -> H256 {
+    register_facility(1);
+    assert_ok!(Registry::register_batch(RuntimeOrigin::signed(1), batch_id.to_vec()));
+    let hash = batch_hash(batch_id);
+    assert_ok!(Registry::certify_batch(RuntimeOrigin::signed(1), hash, certification.to_vec()));
+    hash
+}
+
+#[test]
+fn certification_is_stored_with_parity() {
+    new_test_ext().execute_with(|| {
+        let hash = certify(b"kb-1", b"organic");
+
+        let stored = Batches::<Test>::get(hash).unwrap().certification;
+        assert_eq!(stored.len(), b"organic".len() + elixir_error_correction::classical::PARITY_LEN);
+        assert_eq!(Registry::certification_of(hash), Ok(b"organic".to_vec()));
+    });
+}
+
+#[test]
+fn damaged_certification_parity_is_recovered() {
+    new_test_ext().execute_with(|| {
+        let hash = certify(b"kb-1", b"organic");
+
+        // Damage a minority of the parity bytes
+        Batches::<Test>::mutate(hash, |batch| {
+            let certification = &mut batch.as_mut().unwrap().certification;
+            let len = certification.len();
+            for byte in &mut certification[len - 7..] {
+                *byte = 0xFF;
+            }
+        });
+
+        assert_eq!(Registry::certification_of(hash), Ok(b"organic".to_vec()));
+    });
+}
+
+#[test]
+fn badly_damaged_certification_is_rejected() {
+    new_test_ext().execute_with(|| {
+        let hash = certify(b"kb-1", b"organic");
+
+        // Damage half the parity bytes, leaving no majority to recover from
+        Batches::<Test>::mutate(hash, |batch| {
+            let certification = &mut batch.as_mut().unwrap().certification;
+            let len = certification.len();
+            for byte in &mut certification[len - 8..] {
+                *byte = 0xFF;
+            }
+        });
+
+        assert_eq!(Registry::certification_of(hash), Err(Error::<Test>::ErrorCorrectionFailed));
+    });
+}
+
+/// Register and ship `batch_id` from facility 1 to account 2
+fn ship(batch_id: &[u8]) -> H256 {
+    let hash = certify(batch_id, b"organic");
+    assert_ok!(Registry::ship_batch(RuntimeOrigin::signed(1), hash, 2));
+    hash
+}
+
+#[test]
+fn shipping_and_receiving_are_blocked_while_paused() {
+    new_test_ext().execute_with(|| {
+        let in_transit = ship(b"kb-1");
+        assert_ok!(Registry::register_batch(RuntimeOrigin::signed(1), b"kb-2".to_vec()));
+        assert_ok!(Registry::certify_batch(RuntimeOrigin::signed(1), batch_hash(b"kb-2"), Vec::new()));
+
+        assert_ok!(Registry::set_shipping_paused(RuntimeOrigin::root(), true));
+
+        assert_noop!(
+            Registry::ship_batch(RuntimeOrigin::signed(1), batch_hash(b"kb-2"), 2),
+            Error::<Test>::ShippingPaused
+        );
+        assert_noop!(Registry::receive_batch(RuntimeOrigin::signed(2), in_transit), Error::<Test>::ShippingPaused);
+    });
+}
+
+#[test]
+fn recall_works_while_shipping_is_paused() {
+    new_test_ext().execute_with(|| {
+        let hash = ship(b"kb-1");
+        assert_ok!(Registry::set_shipping_paused(RuntimeOrigin::root(), true));
+
+        assert_ok!(Registry::recall_batch(RuntimeOrigin::signed(1), hash, RecallReason::Contamination));
+
+        assert_eq!(Batches::<Test>::get(hash).unwrap().status, BatchStatus::Recalled);
+        System::assert_last_event(crate::Event::BatchRecalled(hash, RecallReason::Contamination).into());
+    });
+}
+
+#[test]
+fn only_the_facility_or_root_may_recall() {
+    new_test_ext().execute_with(|| {
+        let hash = certify(b"kb-1", b"organic");
+
+        assert_noop!(
+            Registry::recall_batch(RuntimeOrigin::signed(2), hash, RecallReason::QualityFailure),
+            Error::<Test>::NotAuthorized
+        );
+        assert_ok!(Registry::recall_batch(RuntimeOrigin::root(), hash, RecallReason::RegulatoryHold));
+        assert_noop!(
+            Registry::recall_batch(RuntimeOrigin::root(), hash, RecallReason::RegulatoryHold),
+            Error::<Test>::InvalidStatusTransition
+        );
+    });
+}
+
+#[test]
+fn recall_reasons_round_trip_through_the_event() {
+    new_test_ext().execute_with(|| {
+        let other = RecallReason::<Test>::Other(b"mislabelled allergens".to_vec().try_into().unwrap());
+        let reasons =
+            [RecallReason::Contamination, RecallReason::QualityFailure, RecallReason::RegulatoryHold, other];
+
+        register_facility(1);
+        for (i, reason) in reasons.iter().cloned().enumerate() {
+            let batch_id = vec![b'k', b'b', i as u8];
+            assert_ok!(Registry::register_batch(RuntimeOrigin::signed(1), batch_id.clone()));
+            let hash = batch_hash(&batch_id);
+            assert_eq!(RecallReason::<Test>::decode(&mut &reason.encode()[..]), Ok(reason.clone()));
+
+            assert_ok!(Registry::recall_batch(RuntimeOrigin::signed(1), hash, reason.clone()));
+            System::assert_last_event(crate::Event::BatchRecalled(hash, reason).into());
+        }
+    });
+}
+
+#[test]
+fn other_recall_reason_carries_its_text() {
+    new_test_ext().execute_with(|| {
+        let hash = certify(b"kb-1", b"organic");
+        let text = b"supplier notice 42".to_vec();
+
+        assert_ok!(Registry::recall_batch(
+            RuntimeOrigin::signed(1),
+            hash,
+            RecallReason::Other(text.clone().try_into().unwrap())
+        ));
+
+        let recorded = System::events().into_iter().find_map(|record| match record.event {
+            RuntimeEvent::Registry(crate::Event::BatchRecalled(_, RecallReason::Other(text))) => Some(text.into_inner()),
+            _ => None,
+        });
+        assert_eq!(recorded, Some(text));
+        // Longer text than `MaxRecallReasonLength` cannot be expressed at all
+        assert!(RecallReason::<Test>::decode(&mut &(3u8, vec![0u8; 33]).encode()[..]).is_err());
+    });
+}
+
+#[test]
+fn shipping_resumes_after_unpause() {
+    new_test_ext().execute_with(|| {
+        let hash = certify(b"kb-1", b"organic");
+        assert_ok!(Registry::set_shipping_paused(RuntimeOrigin::root(), true));
+        assert_ok!(Registry::set_shipping_paused(RuntimeOrigin::root(), false));
+
+        assert_ok!(Registry::ship_batch(RuntimeOrigin::signed(1), hash, 2));
+        assert_ok!(Registry::receive_batch(RuntimeOrigin::signed(2), hash));
+
+        let batch = Batches::<Test>::get(hash).unwrap();
+        assert_eq!(batch.status, BatchStatus::Delivered);
+        assert_eq!(batch.current_owner, 2);
+    });
+}
+
+#[test]
+fn status_transition_matrix() {
+    use BatchStatus::*;
+    let statuses = [Produced, Certified, InTransit, Delivered, Recalled, Split, Merged];
+    let allowed = [
+        (Produced, Certified),
+        (Certified, InTransit),
+        (InTransit, Delivered),
+        (Produced, Recalled),
+        (Certified, Recalled),
+        (InTransit, Recalled),
+        (Delivered, Recalled),
+        (Produced, Split),
+        (Certified, Split),
+        (Delivered, Split),
+        (Produced, Merged),
+        (Certified, Merged),
+        (Delivered, Merged),
+    ];
+
+    for from in statuses.iter() {
+        for to in statuses.iter() {
+            let expected = allowed.contains(&(from.clone(), to.clone()));
+            assert_eq!(from.can_transition_to(to), expected, "{:?} -> {:?}", from, to);
+        }
+    }
+}
+
+#[test]
+fn delivered_batch_cannot_be_shipped_again() {
+    new_test_ext().execute_with(|| {
+        let hash = ship(b"kb-1");
+        assert_ok!(Registry::receive_batch(RuntimeOrigin::signed(2), hash));
+
+        assert_noop!(Registry::ship_batch(RuntimeOrigin::signed(2), hash, 3), Error::<Test>::InvalidStatusTransition);
+    });
+}
+
+#[test]
+fn in_transit_batch_cannot_be_certified() {
+    new_test_ext().execute_with(|| {
+        let hash = ship(b"kb-1");
+
+        assert_noop!(
+            Registry::certify_batch(RuntimeOrigin::signed(1), hash, b"organic".to_vec()),
+            Error::<Test>::InvalidStatusTransition
+        );
+    });
+}
+
+#[test]
+fn split_and_merged_batches_are_terminal() {
+    use BatchStatus::*;
+    // A batch in transit must arrive before it can be split or merged
+    assert!(!InTransit.can_transition_to(&Split));
+    assert!(!InTransit.can_transition_to(&Merged));
+
+    for terminal in [Split, Merged] {
+        for next in [Produced, Certified, InTransit, Delivered, Recalled, Split, Merged] {
+            assert!(!terminal.can_transition_to(&next), "{:?} -> {:?}", terminal, next);
+        }
+    }
+}
+
+#[test]
+fn recalled_batch_is_terminal() {
+    new_test_ext().execute_with(|| {
+        let hash = certify(b"kb-1", b"organic");
+        assert_ok!(Registry::recall_batch(RuntimeOrigin::signed(1), hash, RecallReason::QualityFailure));
+
+        assert_noop!(Registry::ship_batch(RuntimeOrigin::signed(1), hash, 2), Error::<Test>::InvalidStatusTransition);
+    });
+}
+
+#[test]
+fn checkpoints_are_kept_in_order_with_their_blocks() {
+    new_test_ext().execute_with(|| {
+        register_facility(1);
+        assert_ok!(Registry::register_batch(RuntimeOrigin::signed(1), b"kb-1".to_vec()));
+        let hash = batch_hash(b"kb-1");
+
+        for (block, byte) in [(1, 0xA1), (2, 0xB2), (5, 0xC3)] {
+            System::set_block_number(block);
+            assert_ok!(Registry::checkpoint_batch(RuntimeOrigin::signed(1), hash, H256::repeat_byte(byte)));
+            System::assert_last_event(crate::Event::BatchCheckpointed(hash, H256::repeat_byte(byte)).into());
+        }
+
+        assert_eq!(
+            Registry::checkpoints(hash).into_inner(),
+            vec![(1, H256::repeat_byte(0xA1)), (2, H256::repeat_byte(0xB2)), (5, H256::repeat_byte(0xC3))]
+        );
+    });
+}
+
+#[test]
+fn only_the_batch_owner_may_checkpoint() {
+    new_test_ext().execute_with(|| {
+        register_facility(1);
+        assert_ok!(Registry::register_batch(RuntimeOrigin::signed(1), b"kb-1".to_vec()));
+
+        assert_noop!(
+            Registry::checkpoint_batch(RuntimeOrigin::signed(2), batch_hash(b"kb-1"), H256::repeat_byte(1)),
+            Error::<Test>::NotAuthorized
+        );
+        assert_noop!(
+            Registry::checkpoint_batch(RuntimeOrigin::signed(1), batch_hash(b"kb-unknown"), H256::repeat_byte(1)),
+            Error::<Test>::BatchNotFound
+        );
+    });
+}
+
+#[test]
+fn checkpoints_are_bounded() {
+    new_test_ext().execute_with(|| {
+        register_facility(1);
+        assert_ok!(Registry::register_batch(RuntimeOrigin::signed(1), b"kb-1".to_vec()));
+        let hash = batch_hash(b"kb-1");
+
+        for byte in 0..4 {
+            assert_ok!(Registry::checkpoint_batch(RuntimeOrigin::signed(1), hash, H256::repeat_byte(byte)));
+        }
+
+        assert_noop!(
+            Registry::checkpoint_batch(RuntimeOrigin::signed(1), hash, H256::repeat_byte(4)),
+            Error::<Test>::TooManyCheckpoints
+        );
+    });
+}